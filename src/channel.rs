@@ -0,0 +1,198 @@
+//! Channel that can be used as [`event::Source`].
+//!
+//! See the [`channel`] function for documentation.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::sync::{mpsc, Arc};
+
+use crate::sys::pipe;
+use crate::{event, Interest, Registry, Token};
+
+/// Create a new channel whose [`Receiver`] becomes readable when a value is
+/// sent, similar to [`std::sync::mpsc::channel`].
+///
+/// This allows a background thread to send values to a thread running an
+/// event loop, without needing a separate [`Waker`]. The receiver is backed by
+/// a Unix pipe, which is written to for each value sent.
+///
+/// Once the receiver is readable all values should be received using
+/// [`Receiver::try_recv`], until it returns [`TryRecvError::Empty`], otherwise
+/// no new readiness events will be returned. When all senders are dropped the
+/// receiver becomes read closed, after which `try_recv` returns
+/// [`TryRecvError::Disconnected`].
+///
+/// [`Waker`]: crate::Waker
+/// [`TryRecvError::Empty`]: mpsc::TryRecvError::Empty
+/// [`TryRecvError::Disconnected`]: mpsc::TryRecvError::Disconnected
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::sync::mpsc::TryRecvError;
+/// use std::thread;
+///
+/// use mio::{channel, Events, Interest, Poll, Token};
+///
+/// const CHANNEL: Token = Token(0);
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let (sender, mut receiver) = channel()?;
+/// poll.registry().register(&mut receiver, CHANNEL, Interest::READABLE)?;
+///
+/// let handle = thread::spawn(move || sender.send("Hello world!").unwrap());
+///
+/// 'outer: loop {
+///     poll.poll(&mut events, None)?;
+///     for event in events.iter() {
+///         assert_eq!(event.token(), CHANNEL);
+///         loop {
+///             match receiver.try_recv() {
+///                 Ok(msg) => {
+///                     assert_eq!(msg, "Hello world!");
+///                     break 'outer;
+///                 }
+///                 Err(TryRecvError::Empty) => break,
+///                 Err(TryRecvError::Disconnected) => panic!("sender dropped"),
+///             }
+///         }
+///     }
+/// }
+/// # handle.join().unwrap();
+/// #     Ok(())
+/// # }
+/// ```
+pub fn channel<T>() -> io::Result<(Sender<T>, Receiver<T>)> {
+    let (pipe_sender, pipe_receiver) = pipe::new()?;
+    let (sender, receiver) = mpsc::channel();
+    let sender = Sender {
+        inner: sender,
+        pipe: Arc::new(pipe_sender),
+    };
+    let receiver = Receiver {
+        inner: receiver,
+        pipe: pipe_receiver,
+    };
+    Ok((sender, receiver))
+}
+
+/// Sending half of a [`channel`].
+///
+/// The sender can be cloned to send to the same channel from multiple threads.
+pub struct Sender<T> {
+    inner: mpsc::Sender<T>,
+    pipe: Arc<pipe::Sender>,
+}
+
+impl<T> Sender<T> {
+    /// Send a value, making the [`Receiver`] readable.
+    ///
+    /// This returns an error if the receiver has been dropped, returning the
+    /// value.
+    pub fn send(&self, value: T) -> Result<(), mpsc::SendError<T>> {
+        self.inner.send(value)?;
+        loop {
+            match (&*self.pipe).write(&[1]) {
+                // If the pipe is full the receiver is already readable.
+                Ok(_) => break,
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                // The receiver is dropped after the value was sent.
+                Err(_) => break,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        Sender {
+            inner: self.inner.clone(),
+            pipe: self.pipe.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").field("pipe", &self.pipe).finish()
+    }
+}
+
+/// Receiving half of a [`channel`].
+pub struct Receiver<T> {
+    inner: mpsc::Receiver<T>,
+    pipe: pipe::Receiver,
+}
+
+impl<T> Receiver<T> {
+    /// Attempt to receive a value, without blocking.
+    ///
+    /// Returns [`TryRecvError::Empty`] if no value is available, after which
+    /// the receiver becomes readable again once a value is sent.
+    ///
+    /// [`TryRecvError::Empty`]: mpsc::TryRecvError::Empty
+    pub fn try_recv(&self) -> Result<T, mpsc::TryRecvError> {
+        match self.inner.try_recv() {
+            Err(mpsc::TryRecvError::Empty) => {
+                // Drain the pipe so that the next send triggers a new
+                // readiness event. A value could have been sent between the
+                // first receive and emptying the pipe, so receive once more.
+                self.drain_pipe();
+                self.inner.try_recv()
+            }
+            res => res,
+        }
+    }
+
+    fn drain_pipe(&self) {
+        let mut buf = [0; 64];
+        loop {
+            match (&self.pipe).read(&mut buf) {
+                Ok(0) => break,
+                Ok(_) => continue,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                // Either the pipe is empty (`WouldBlock`), or reading from it
+                // failed, in which case we can't do anything about it.
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+impl<T> event::Source for Receiver<T> {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.pipe.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.pipe.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.pipe.deregister(registry)
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("pipe", &self.pipe)
+            .finish()
+    }
+}
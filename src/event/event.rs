@@ -24,6 +24,18 @@ impl Event {
         sys::event::token(&self.inner)
     }
 
+    /// Returns true if the event was triggered by the [`Waker`] returned by
+    /// [`Poll::shutdown_waker`].
+    ///
+    /// This is the same as checking the event's token against
+    /// [`Token::SHUTDOWN`].
+    ///
+    /// [`Waker`]: crate::Waker
+    /// [`Poll::shutdown_waker`]: crate::Poll::shutdown_waker
+    pub fn is_shutdown(&self) -> bool {
+        self.token() == Token::SHUTDOWN
+    }
+
     /// Returns true if the event contains readable readiness.
     ///
     /// # Notes
@@ -68,6 +80,71 @@ impl Event {
         sys::event::is_error(&self.inner)
     }
 
+    /// Returns true if the event contains hang up readiness, i.e. both halves
+    /// of the connection are closed.
+    ///
+    /// # Notes
+    ///
+    /// Unlike [`is_error`] this doesn't indicate that an error occurred. A
+    /// hang up implies both [`is_read_closed`] and [`is_write_closed`] on
+    /// [epoll], it's the same flag that causes those methods to return true
+    /// when both halves of the socket are closed. Use this method to
+    /// distinguish a full hang up from only one half of the connection being
+    /// shut down.
+    ///
+    /// Method is available on all platforms, but not all platforms report a
+    /// hang up separately.
+    ///
+    /// The table below shows what flags are checked on what OS.
+    ///
+    /// | [OS selector] | Flag(s) checked |
+    /// |---------------|-----------------|
+    /// | [epoll]       | `EPOLLHUP`      |
+    /// | [kqueue]      | *Not supported* |
+    ///
+    /// [`is_error`]: Event::is_error
+    /// [`is_read_closed`]: Event::is_read_closed
+    /// [`is_write_closed`]: Event::is_write_closed
+    /// [OS selector]: ../struct.Poll.html#implementation-notes
+    /// [epoll]: https://man7.org/linux/man-pages/man7/epoll.7.html
+    /// [kqueue]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
+    pub fn is_hup(&self) -> bool {
+        sys::event::is_hup(&self.inner)
+    }
+
+    /// Returns true if the event contains read hang up readiness, i.e. the
+    /// peer has shutdown the write half of its socket.
+    ///
+    /// # Notes
+    ///
+    /// This differs from [`is_read_closed`], which on [epoll] also returns true
+    /// if the connection is fully closed ([`is_hup`]) without `EPOLLRDHUP`
+    /// being set. Read hang up readiness is set once a `FIN` packet from the
+    /// peer is received, the write half of the local stream can still be used
+    /// though. An event where this returns true, but [`is_hup`] doesn't, means
+    /// only the peer's write half was shutdown. `EPOLLRDHUP` is always
+    /// requested for readable interest, so no additional interest is required.
+    ///
+    /// Method is available on all platforms, but not all platforms are able to
+    /// distinguish the peer shutting down from other causes of the read half
+    /// being closed.
+    ///
+    /// The table below shows what flags are checked on what OS.
+    ///
+    /// | [OS selector] | Flag(s) checked |
+    /// |---------------|-----------------|
+    /// | [epoll]       | `EPOLLRDHUP`    |
+    /// | [kqueue]      | `EV_EOF` on `EVFILT_READ` |
+    ///
+    /// [`is_read_closed`]: Event::is_read_closed
+    /// [`is_hup`]: Event::is_hup
+    /// [OS selector]: ../struct.Poll.html#implementation-notes
+    /// [epoll]: https://man7.org/linux/man-pages/man7/epoll.7.html
+    /// [kqueue]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
+    pub fn is_read_hup(&self) -> bool {
+        sys::event::is_read_hup(&self.inner)
+    }
+
     /// Returns true if the event contains read closed readiness.
     ///
     /// # Notes
@@ -80,6 +157,10 @@ impl Event {
     /// * The peer stream has shutdown the write half its socket; this sends a
     ///   `FIN` packet that has been received by the local stream
     ///
+    /// The peer stream shutting down only the write half of its socket does
+    /// *not* result in write closed readiness ([`is_write_closed`]) on any
+    /// platform, the local stream can still write to the peer.
+    ///
     /// Method is a best effort implementation. While some platforms may not
     /// return readiness when read half is closed, it is guaranteed that
     /// false-positives will not occur.
@@ -88,13 +169,15 @@ impl Event {
     ///
     /// | [OS selector] | Flag(s) checked |
     /// |---------------|-----------------|
-    /// | [epoll]       | `EPOLLHUP`, or  |
-    /// |               | `EPOLLIN` and `EPOLLRDHUP` |
-    /// | [kqueue]      | `EV_EOF`        |
+    /// | [epoll]       | `EPOLLHUP` or `EPOLLRDHUP` |
+    /// | [kqueue]      | `EV_EOF` on `EVFILT_READ` |
+    /// | [IOCP]        | `AFD_POLL_DISCONNECT`, `AFD_POLL_ABORT` or `AFD_POLL_CONNECT_FAIL` |
     ///
+    /// [`is_write_closed`]: Event::is_write_closed
     /// [OS selector]: ../struct.Poll.html#implementation-notes
     /// [epoll]: https://man7.org/linux/man-pages/man7/epoll.7.html
     /// [kqueue]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
+    /// [IOCP]: https://docs.microsoft.com/en-us/windows/win32/fileio/i-o-completion-ports
     pub fn is_read_closed(&self) -> bool {
         sys::event::is_read_closed(&self.inner)
     }
@@ -120,11 +203,13 @@ impl Event {
     /// | [epoll]       | `EPOLLHUP`, or  |
     /// |               | only `EPOLLERR`, or |
     /// |               | `EPOLLOUT` and `EPOLLERR` |
-    /// | [kqueue]      | `EV_EOF`        |
+    /// | [kqueue]      | `EV_EOF` on `EVFILT_WRITE` |
+    /// | [IOCP]        | `AFD_POLL_ABORT` or `AFD_POLL_CONNECT_FAIL` |
     ///
     /// [OS selector]: ../struct.Poll.html#implementation-notes
     /// [epoll]: https://man7.org/linux/man-pages/man7/epoll.7.html
     /// [kqueue]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
+    /// [IOCP]: https://docs.microsoft.com/en-us/windows/win32/fileio/i-o-completion-ports
     pub fn is_write_closed(&self) -> bool {
         sys::event::is_write_closed(&self.inner)
     }
@@ -183,6 +268,33 @@ impl Event {
         sys::event::is_lio(&self.inner)
     }
 
+    /// Returns the number of bytes available to read, if reported by the OS
+    /// selector.
+    ///
+    /// This allows a single read to be sized to drain the source exactly.
+    /// Note however that more data can arrive between the event being
+    /// returned and the read.
+    ///
+    /// # Notes
+    ///
+    /// Method is available on all platforms, but not all platforms report the
+    /// number of bytes available, `None` is returned on those platforms. On
+    /// [epoll] use `FIONREAD` instead.
+    ///
+    /// The table below shows what field is used on what OS.
+    ///
+    /// | [OS selector] | Field used      |
+    /// |---------------|-----------------|
+    /// | [epoll]       | *Not supported* |
+    /// | [kqueue]      | `data` of an `EVFILT_READ` event |
+    ///
+    /// [OS selector]: ../struct.Poll.html#implementation-notes
+    /// [epoll]: https://man7.org/linux/man-pages/man7/epoll.7.html
+    /// [kqueue]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
+    pub fn read_data(&self) -> Option<usize> {
+        sys::event::read_data(&self.inner)
+    }
+
     /// Create a reference to an `Event` from a platform specific event.
     pub(crate) fn from_sys_event_ref(sys_event: &sys::Event) -> &Event {
         unsafe {
@@ -207,6 +319,8 @@ impl fmt::Debug for Event {
             .field("readable", &self.is_readable())
             .field("writable", &self.is_writable())
             .field("error", &self.is_error())
+            .field("hup", &self.is_hup())
+            .field("read_hup", &self.is_read_hup())
             .field("read_closed", &self.is_read_closed())
             .field("write_closed", &self.is_write_closed())
             .field("priority", &self.is_priority())
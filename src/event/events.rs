@@ -1,5 +1,7 @@
 use crate::event::Event;
 use crate::sys;
+#[cfg(feature = "test-util")]
+use crate::{test::Readiness, Token};
 
 use std::fmt;
 
@@ -81,6 +83,13 @@ pub struct Iter<'a> {
 impl Events {
     /// Return a new `Events` capable of holding up to `capacity` events.
     ///
+    /// The capacity is also the batch size used by the OS selector, e.g. on
+    /// Windows it's the maximum number of completions retrieved from the I/O
+    /// completion port in a single `GetQueuedCompletionStatusEx` call. See
+    /// [`reserve`] for tuning it under heavy load.
+    ///
+    /// [`reserve`]: Events::reserve
+    ///
     /// # Examples
     ///
     /// ```
@@ -107,6 +116,73 @@ impl Events {
         self.inner.capacity()
     }
 
+    /// Reserves capacity for at least `additional` more `Event` values.
+    ///
+    /// The capacity determines the maximum number of events a single call to
+    /// [`Poll::poll`] can return. If polls consistently fill the buffer it
+    /// means readiness events are waiting to be picked up by the next call,
+    /// growing the capacity reduces the number of polls needed under heavy
+    /// load.
+    ///
+    /// [`Poll::poll`]: ../struct.Poll.html#method.poll
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio::Events;
+    ///
+    /// let mut events = Events::with_capacity(16);
+    /// events.reserve(1024);
+    /// assert!(events.capacity() >= 1024);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional);
+    }
+
+    /// Replaces the buffer with one capable of holding up to `capacity`
+    /// events.
+    ///
+    /// Unlike [`reserve`] this can also shrink the buffer. Any `Event` values
+    /// currently held are dropped. See [`reserve`] for why growing the
+    /// capacity can be useful.
+    ///
+    /// [`reserve`]: Events::reserve
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio::Events;
+    ///
+    /// let mut events = Events::with_capacity(1024);
+    /// events.set_capacity(128);
+    /// assert_eq!(128, events.capacity());
+    /// ```
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.inner = sys::Events::with_capacity(capacity);
+    }
+
+    /// Returns the number of `Event` values in `self`.
+    ///
+    /// This is the number of events returned by the last call to
+    /// [`Poll::poll`]. If it's equal to [`capacity`] more events may be ready,
+    /// see [`reserve`].
+    ///
+    /// [`Poll::poll`]: ../struct.Poll.html#method.poll
+    /// [`capacity`]: Events::capacity
+    /// [`reserve`]: Events::reserve
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio::Events;
+    ///
+    /// let events = Events::with_capacity(1024);
+    /// assert_eq!(events.len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
     /// Returns `true` if `self` contains no `Event` values.
     ///
     /// # Examples
@@ -185,6 +261,37 @@ impl Events {
         self.inner.clear();
     }
 
+    /// Retains only the events for which `f` returns `true`, removing all
+    /// other events.
+    ///
+    /// See [`TokenAllocator::remove_stale`] for an example.
+    ///
+    /// [`TokenAllocator::remove_stale`]: crate::TokenAllocator::remove_stale
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&Event) -> bool,
+    {
+        self.inner
+            .retain(|sys_event| f(Event::from_sys_event_ref(sys_event)));
+    }
+
+    /// Add an event for `token` with `readiness`.
+    ///
+    /// This is used by [`test::Selector`] implementations to inject events.
+    ///
+    /// [`test::Selector`]: crate::test::Selector
+    #[cfg(feature = "test-util")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+    pub fn push(&mut self, token: Token, readiness: Readiness) {
+        sys::event::push(&mut self.inner, token, readiness);
+    }
+
+    /// Rotates the events in place such that the event at `mid` becomes the
+    /// first event.
+    pub(crate) fn rotate_left(&mut self, mid: usize) {
+        self.inner.rotate_left(mid);
+    }
+
     /// Returns the inner `sys::Events`.
     pub(crate) fn sys(&mut self) -> &mut sys::Events {
         &mut self.inner
@@ -0,0 +1,170 @@
+use crate::event::Event;
+use crate::sys;
+
+use std::mem::MaybeUninit;
+use std::{fmt, slice};
+
+/// A fixed size collection of readiness events.
+///
+/// `EventsArray` is the stack allocated counterpart of [`Events`]. It is
+/// passed to [`Poll::poll_array`] which fills it without allocating, making it
+/// a good fit for latency sensitive loops that only wait on a handful of
+/// sources. At most `N` events are returned by a single poll, any remaining
+/// events are returned by the next call.
+///
+/// This is only available on Unix.
+///
+/// [`Events`]: crate::Events
+/// [`Poll::poll_array`]: crate::Poll::poll_array
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::event::EventsArray;
+/// use mio::Poll;
+/// use std::time::Duration;
+///
+/// let mut events = EventsArray::<8>::new();
+/// let mut poll = Poll::new()?;
+/// #
+/// # assert!(events.is_empty());
+///
+/// // Register `event::Source`s with `poll`.
+///
+/// poll.poll_array(&mut events, Some(Duration::from_millis(100)))?;
+///
+/// for event in events.iter() {
+///     println!("Got an event for {:?}", event.token());
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+pub struct EventsArray<const N: usize> {
+    events: [MaybeUninit<sys::Event>; N],
+    /// Number of initialised events at the start of `events`.
+    len: usize,
+}
+
+/// [`EventsArray`] iterator.
+///
+/// This struct is created by the [`iter`] method on [`EventsArray`].
+///
+/// [`iter`]: EventsArray::iter
+#[derive(Debug, Clone)]
+pub struct ArrayIter<'a> {
+    inner: slice::Iter<'a, sys::Event>,
+}
+
+impl<const N: usize> EventsArray<N> {
+    /// Return a new, empty, `EventsArray` capable of holding up to `N` events.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio::event::EventsArray;
+    ///
+    /// let events = EventsArray::<4>::new();
+    /// assert_eq!(4, events.capacity());
+    /// ```
+    pub const fn new() -> EventsArray<N> {
+        EventsArray {
+            // SAFETY: an array of `MaybeUninit` doesn't require
+            // initialisation.
+            events: unsafe { MaybeUninit::uninit().assume_init() },
+            len: 0,
+        }
+    }
+
+    /// Returns the number of `Event` values that `self` can hold, i.e. `N`.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if `self` contains no `Event` values.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the `Event` values.
+    pub fn iter(&self) -> ArrayIter<'_> {
+        ArrayIter {
+            inner: self.as_sys().iter(),
+        }
+    }
+
+    /// Clearing all `Event` values from container explicitly.
+    ///
+    /// # Notes
+    ///
+    /// Events are cleared before every `poll`, so it is not required to call
+    /// this manually.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    fn as_sys(&self) -> &[sys::Event] {
+        // SAFETY: the first `len` events are initialised, see `set_len`.
+        unsafe { slice::from_raw_parts(self.events.as_ptr().cast(), self.len) }
+    }
+
+    /// Returns the buffer to fill, clearing all current events.
+    pub(crate) fn sys(&mut self) -> &mut [MaybeUninit<sys::Event>] {
+        self.len = 0;
+        &mut self.events
+    }
+
+    /// # Safety
+    ///
+    /// The first `len` events in the buffer returned by `sys` must be
+    /// initialised.
+    pub(crate) unsafe fn set_len(&mut self, len: usize) {
+        debug_assert!(len <= N);
+        self.len = len;
+    }
+}
+
+impl<const N: usize> Default for EventsArray<N> {
+    fn default() -> EventsArray<N> {
+        EventsArray::new()
+    }
+}
+
+// `EventsArray` doesn't automatically implement `Send` or `Sync` on kqueue
+// platforms because of the `udata: *mut c_void` field in `libc::kevent`. See
+// `sys::Events` in the kqueue selector why this is safe.
+unsafe impl<const N: usize> Send for EventsArray<N> {}
+unsafe impl<const N: usize> Sync for EventsArray<N> {}
+
+impl<'a, const N: usize> IntoIterator for &'a EventsArray<N> {
+    type Item = &'a Event;
+    type IntoIter = ArrayIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> Iterator for ArrayIter<'a> {
+    type Item = &'a Event;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(Event::from_sys_event_ref)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<const N: usize> fmt::Debug for EventsArray<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
@@ -3,8 +3,12 @@
 #[allow(clippy::module_inception)]
 mod event;
 mod events;
+#[cfg(unix)]
+mod events_array;
 mod source;
 
 pub use self::event::Event;
 pub use self::events::{Events, Iter};
+#[cfg(unix)]
+pub use self::events_array::{ArrayIter, EventsArray};
 pub use self::source::Source;
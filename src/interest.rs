@@ -23,6 +23,8 @@ const WRITABLE: u8 = 0b0010;
 const AIO: u8 = 0b0100;
 const LIO: u8 = 0b1000;
 const PRIORITY: u8 = 0b10000;
+const WAKEUP: u8 = 0b100000;
+const HUP: u8 = 0b1000000;
 
 impl Interest {
     /// Returns a `Interest` set representing readable interests.
@@ -51,6 +53,48 @@ impl Interest {
     #[cfg(any(target_os = "linux", target_os = "android"))]
     pub const PRIORITY: Interest = Interest(unsafe { NonZeroU8::new_unchecked(PRIORITY) });
 
+    /// Returns a `Interest` set that prevents the system from suspending while
+    /// an event is being processed.
+    ///
+    /// This sets `EPOLLWAKEUP`, which should be combined with another interest,
+    /// e.g. [`Interest::READABLE`]. The system is kept awake from the moment
+    /// the event is queued until the next call to [`Poll::poll`]. This requires
+    /// the `CAP_BLOCK_SUSPEND` capability, without it the flag is ignored by
+    /// the kernel.
+    ///
+    /// [`Poll::poll`]: crate::Poll::poll
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const WAKEUP: Interest = Interest(unsafe { NonZeroU8::new_unchecked(WAKEUP) });
+
+    /// Returns a `Interest` set representing hangup interests.
+    ///
+    /// On Linux and Android this sets `EPOLLRDHUP`, without any other interest
+    /// only being notified when the peer closes the connection or pipe, with
+    /// [`Event::is_read_closed`] set, and not when data becomes available to
+    /// read. This can be used to cheaply watch for closure of e.g. a child
+    /// process' pipe.
+    ///
+    /// kqueue has no hangup only filter, on these platforms this registers
+    /// `EVFILT_READ` and the closure is reported using `EV_EOF`. Unlike on
+    /// Linux events are also returned when data becomes available to read, so
+    /// check [`Event::is_read_closed`] before assuming the peer has closed.
+    ///
+    /// [`Event::is_read_closed`]: crate::event::Event::is_read_closed
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    pub const HUP: Interest = Interest(unsafe { NonZeroU8::new_unchecked(HUP) });
+
     /// Add together two `Interest`.
     ///
     /// This does the same thing as the `BitOr` implementation, but is a
@@ -94,6 +138,44 @@ impl Interest {
         NonZeroU8::new(self.0.get() & !other.0.get()).map(Interest)
     }
 
+    /// Returns true if `self` contains all interests in `other`.
+    ///
+    /// ```
+    /// use mio::Interest;
+    ///
+    /// const RW_INTERESTS: Interest = Interest::READABLE.add(Interest::WRITABLE);
+    ///
+    /// assert!(RW_INTERESTS.contains(Interest::READABLE));
+    /// assert!(RW_INTERESTS.contains(RW_INTERESTS));
+    /// assert!(!Interest::READABLE.contains(RW_INTERESTS));
+    /// ```
+    #[must_use]
+    pub const fn contains(self, other: Interest) -> bool {
+        (self.0.get() & other.0.get()) == other.0.get()
+    }
+
+    /// Returns the interests that are in both `self` and `other`.
+    ///
+    /// Returns `None` if `self` and `other` have no interests in common.
+    ///
+    /// ```
+    /// use mio::Interest;
+    ///
+    /// const RW_INTERESTS: Interest = Interest::READABLE.add(Interest::WRITABLE);
+    ///
+    /// let interest = RW_INTERESTS.intersection(Interest::WRITABLE);
+    /// assert_eq!(interest, Some(Interest::WRITABLE));
+    ///
+    /// assert_eq!(Interest::READABLE.intersection(Interest::WRITABLE), None);
+    /// ```
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub const fn intersection(self, other: Interest) -> Option<Interest> {
+        match NonZeroU8::new(self.0.get() & other.0.get()) {
+            Some(interest) => Some(Interest(interest)),
+            None => None,
+        }
+    }
+
     /// Returns true if the value includes readable readiness.
     #[must_use]
     pub const fn is_readable(self) -> bool {
@@ -123,6 +205,18 @@ impl Interest {
     pub const fn is_priority(self) -> bool {
         (self.0.get() & PRIORITY) != 0
     }
+
+    /// Returns true if `Interest` contains wakeup interest.
+    #[must_use]
+    pub const fn is_wakeup(self) -> bool {
+        (self.0.get() & WAKEUP) != 0
+    }
+
+    /// Returns true if `Interest` contains hangup interest.
+    #[must_use]
+    pub const fn is_hup(self) -> bool {
+        (self.0.get() & HUP) != 0
+    }
 }
 
 impl ops::BitOr for Interest {
@@ -142,6 +236,12 @@ impl ops::BitOrAssign for Interest {
 }
 
 impl fmt::Debug for Interest {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
+
+impl fmt::Display for Interest {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut one = false;
         if self.is_readable() {
@@ -195,6 +295,35 @@ impl fmt::Debug for Interest {
                 write!(fmt, "PRIORITY")?;
                 one = true
             }
+            if self.is_wakeup() {
+                if one {
+                    write!(fmt, " | ")?
+                }
+                write!(fmt, "WAKEUP")?;
+                one = true
+            }
+        }
+        #[cfg(any(
+            target_os = "android",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "netbsd",
+            target_os = "openbsd",
+            target_os = "tvos",
+            target_os = "visionos",
+            target_os = "watchos",
+        ))]
+        {
+            if self.is_hup() {
+                if one {
+                    write!(fmt, " | ")?
+                }
+                write!(fmt, "HUP")?;
+                one = true
+            }
         }
         debug_assert!(one, "printing empty interests");
         Ok(())
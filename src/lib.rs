@@ -51,8 +51,14 @@ mod token;
 #[cfg(not(target_os = "wasi"))]
 mod waker;
 
+#[cfg(all(unix, feature = "os-ext"))]
+mod channel;
 pub mod event;
 
+#[cfg(feature = "test-util")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+pub mod test;
+
 cfg_io_source! {
     mod io_source;
 }
@@ -61,11 +67,14 @@ cfg_net! {
     pub mod net;
 }
 
+#[cfg(all(unix, feature = "os-ext"))]
+#[cfg_attr(docsrs, doc(cfg(all(unix, feature = "os-ext"))))]
+pub use channel::{channel, Receiver, Sender};
 #[doc(no_inline)]
 pub use event::Events;
 pub use interest::Interest;
 pub use poll::{Poll, Registry};
-pub use token::Token;
+pub use token::{Token, TokenAllocator};
 #[cfg(not(target_os = "wasi"))]
 pub use waker::Waker;
 
@@ -82,6 +91,9 @@ pub mod unix {
         pub use crate::sys::pipe::{new, Receiver, Sender};
     }
 
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub use crate::sys::pipe::splice;
     pub use crate::sys::SourceFd;
 }
 
@@ -125,6 +137,27 @@ pub mod features {
     #![cfg_attr(not(feature = "net"), doc = "## Network types (disabled)")]
     //!
     //! The `net` feature enables networking primitives in the `net` module.
+    //!
+    #![cfg_attr(feature = "test-util", doc = "## `test-util` (enabled)")]
+    #![cfg_attr(not(feature = "test-util"), doc = "## `test-util` (disabled)")]
+    //!
+    //! The `test-util` feature enables the `test` module, which contains
+    //! utilities to test code using `Poll` without relying on the OS.
+    //!
+    #![cfg_attr(feature = "socket2", doc = "## `socket2` (enabled)")]
+    #![cfg_attr(not(feature = "socket2"), doc = "## `socket2` (disabled)")]
+    //!
+    //! The `socket2` feature implements `TryFrom<socket2::Socket>` for
+    //! `TcpStream`, `TcpListener` and `UdpSocket`, and the reverse conversions
+    //! into `socket2::Socket`. This allows sockets configured using `socket2`
+    //! to be used with Mio. To borrow a Mio socket as a `socket2` socket use
+    //! `socket2::SockRef::from(&socket)`.
+    //!
+    #![cfg_attr(feature = "serde", doc = "## `serde` (enabled)")]
+    #![cfg_attr(not(feature = "serde"), doc = "## `serde` (disabled)")]
+    //!
+    //! The `serde` feature implements `Serialize` and `Deserialize` for
+    //! `Token`, e.g. to persist a mapping of tokens to connection metadata.
 }
 
 pub mod guide {
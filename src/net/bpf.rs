@@ -0,0 +1,39 @@
+/// A classic BPF (cBPF) instruction.
+///
+/// This has the same layout as the kernel's `struct sock_filter`, a program is
+/// a slice of these instructions. See the `filter(2)` and `bpf(4)` manuals for
+/// a description of the instruction set. The opcodes, e.g. `BPF_RET` and
+/// `BPF_K`, can be found in the `libc` crate.
+///
+/// # Examples
+///
+/// A program that always returns zero.
+///
+/// ```
+/// use mio::net::SockFilter;
+///
+/// const BPF_RET: u16 = 0x06;
+/// const BPF_K: u16 = 0x00;
+///
+/// let program = [SockFilter::new(BPF_RET | BPF_K, 0, 0, 0)];
+/// # let _ = program;
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct SockFilter {
+    /// Opcode of the instruction.
+    pub code: u16,
+    /// Jump offset if the condition is true.
+    pub jt: u8,
+    /// Jump offset if the condition is false.
+    pub jf: u8,
+    /// Generic field, e.g. an immediate value.
+    pub k: u32,
+}
+
+impl SockFilter {
+    /// Create a new instruction.
+    pub const fn new(code: u16, jt: u8, jf: u8, k: u32) -> SockFilter {
+        SockFilter { code, jt, jf, k }
+    }
+}
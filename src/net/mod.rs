@@ -26,14 +26,56 @@
 //! give is to always call receive with a large enough buffer.
 
 mod tcp;
-pub use self::tcp::{TcpListener, TcpStream};
+#[cfg(not(target_os = "wasi"))]
+pub use self::tcp::bind_dualstack;
+pub use self::tcp::{TcpIncoming, TcpListener, TcpStream};
+
+#[cfg(all(feature = "socket2", any(unix, windows)))]
+#[cfg_attr(docsrs, doc(cfg(feature = "socket2")))]
+mod socket2;
+
+#[cfg(target_os = "linux")]
+mod bpf;
+#[cfg(target_os = "linux")]
+#[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+pub use self::bpf::SockFilter;
+
+#[cfg(target_os = "linux")]
+mod recv_msg;
+#[cfg(target_os = "linux")]
+#[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+pub use self::recv_msg::{HwTimestamp, RecvFull, RecvMsg};
+
+#[cfg(target_os = "linux")]
+mod recv_flags;
+#[cfg(target_os = "linux")]
+#[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+pub use self::recv_flags::RecvFlags;
+
+#[cfg(target_os = "linux")]
+mod send_flags;
+#[cfg(target_os = "linux")]
+#[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+pub use self::send_flags::SendFlags;
+
+#[cfg(target_os = "linux")]
+mod zerocopy;
+#[cfg(target_os = "linux")]
+#[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+pub use self::zerocopy::ZcCompletion;
 
 #[cfg(not(target_os = "wasi"))]
 mod udp;
 #[cfg(not(target_os = "wasi"))]
 pub use self::udp::UdpSocket;
+#[cfg(unix)]
+#[cfg_attr(docsrs, doc(cfg(unix)))]
+pub use self::udp::interface_index_by_name;
 
 #[cfg(unix)]
 mod uds;
 #[cfg(unix)]
-pub use self::uds::{UnixDatagram, UnixListener, UnixStream};
+pub use self::uds::{UnixDatagram, UnixIncoming, UnixListener, UnixStream};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+pub use self::uds::UCred;
@@ -0,0 +1,44 @@
+use std::ops;
+
+/// Flags used by [`UdpSocket::recv_from_with_flags`].
+///
+/// Flags can be combined using the `|` operator.
+///
+/// [`UdpSocket::recv_from_with_flags`]: crate::net::UdpSocket::recv_from_with_flags
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RecvFlags(u8);
+
+// These must be unique.
+const DONT_WAIT: u8 = 0b0001;
+
+impl RecvFlags {
+    /// No flags.
+    pub const EMPTY: RecvFlags = RecvFlags(0);
+
+    /// Sets `MSG_DONTWAIT`, making the call non-blocking even if the socket is
+    /// in blocking mode, see [`UdpSocket::set_nonblocking`].
+    ///
+    /// [`UdpSocket::set_nonblocking`]: crate::net::UdpSocket::set_nonblocking
+    pub const DONT_WAIT: RecvFlags = RecvFlags(DONT_WAIT);
+
+    /// Returns true if the value includes [`RecvFlags::DONT_WAIT`].
+    pub const fn is_dont_wait(self) -> bool {
+        (self.0 & DONT_WAIT) != 0
+    }
+}
+
+impl ops::BitOr for RecvFlags {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        RecvFlags(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for RecvFlags {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
@@ -0,0 +1,167 @@
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, SystemTime};
+
+/// A single datagram received by [`UdpSocket::recv_mmsg`].
+///
+/// [`UdpSocket::recv_mmsg`]: crate::net::UdpSocket::recv_mmsg
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RecvMsg {
+    len: usize,
+    addr: SocketAddr,
+    timestamp: Option<SystemTime>,
+}
+
+impl RecvMsg {
+    #[cfg(all(feature = "os-poll", target_os = "linux"))]
+    pub(crate) const fn new(len: usize, addr: SocketAddr, timestamp: Option<SystemTime>) -> RecvMsg {
+        RecvMsg {
+            len,
+            addr,
+            timestamp,
+        }
+    }
+
+    /// Number of bytes read into the buffer.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the datagram was empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Address the datagram was received from.
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Software receive timestamp of the datagram.
+    ///
+    /// This is only set if timestamping was enabled using
+    /// [`UdpSocket::set_recv_timestamp`] before the datagram was received.
+    ///
+    /// [`UdpSocket::set_recv_timestamp`]: crate::net::UdpSocket::set_recv_timestamp
+    pub const fn timestamp(&self) -> Option<SystemTime> {
+        self.timestamp
+    }
+}
+
+/// A datagram, including all enabled ancillary data, received by
+/// [`UdpSocket::recv_full`].
+///
+/// [`UdpSocket::recv_full`]: crate::net::UdpSocket::recv_full
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RecvFull {
+    pub(crate) len: usize,
+    pub(crate) addr: SocketAddr,
+    pub(crate) dst_addr: Option<IpAddr>,
+    pub(crate) orig_dst_addr: Option<SocketAddr>,
+    pub(crate) interface: Option<u32>,
+    pub(crate) ecn: Option<u8>,
+    pub(crate) timestamp: Option<SystemTime>,
+    pub(crate) hw_timestamp: Option<HwTimestamp>,
+}
+
+impl RecvFull {
+    /// Number of bytes read into the buffer.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the datagram was empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Address the datagram was received from.
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Destination address of the datagram, i.e. the address in the IP
+    /// header.
+    ///
+    /// This is only set if enabled using [`UdpSocket::set_recv_pktinfo`].
+    ///
+    /// [`UdpSocket::set_recv_pktinfo`]: crate::net::UdpSocket::set_recv_pktinfo
+    pub const fn dst_addr(&self) -> Option<IpAddr> {
+        self.dst_addr
+    }
+
+    /// Original destination address and port of the datagram.
+    ///
+    /// This is only set if enabled using [`UdpSocket::set_recv_orig_dst_addr`].
+    ///
+    /// [`UdpSocket::set_recv_orig_dst_addr`]: crate::net::UdpSocket::set_recv_orig_dst_addr
+    pub const fn orig_dst_addr(&self) -> Option<SocketAddr> {
+        self.orig_dst_addr
+    }
+
+    /// Index of the interface the datagram was received on.
+    ///
+    /// This is only set if enabled using [`UdpSocket::set_recv_pktinfo`].
+    ///
+    /// [`UdpSocket::set_recv_pktinfo`]: crate::net::UdpSocket::set_recv_pktinfo
+    pub const fn interface(&self) -> Option<u32> {
+        self.interface
+    }
+
+    /// Explicit Congestion Notification (ECN) bits of the datagram, i.e. the
+    /// lowest two bits of the IPv4 TOS or IPv6 traffic class field.
+    ///
+    /// This is only set if enabled using [`UdpSocket::set_recv_ecn`].
+    ///
+    /// [`UdpSocket::set_recv_ecn`]: crate::net::UdpSocket::set_recv_ecn
+    pub const fn ecn(&self) -> Option<u8> {
+        self.ecn
+    }
+
+    /// Software receive timestamp of the datagram.
+    ///
+    /// This is only set if timestamping was enabled using
+    /// [`UdpSocket::set_recv_timestamp`] before the datagram was received.
+    ///
+    /// [`UdpSocket::set_recv_timestamp`]: crate::net::UdpSocket::set_recv_timestamp
+    pub const fn timestamp(&self) -> Option<SystemTime> {
+        self.timestamp
+    }
+    /// Hardware receive timestamp of the datagram.
+    ///
+    /// This is only set if hardware timestamping was enabled using
+    /// [`UdpSocket::set_recv_hw_timestamp`] before the datagram was received,
+    /// and the network device supports it.
+    ///
+    /// [`UdpSocket::set_recv_hw_timestamp`]: crate::net::UdpSocket::set_recv_hw_timestamp
+    pub const fn hw_timestamp(&self) -> Option<HwTimestamp> {
+        self.hw_timestamp
+    }
+}
+
+/// Hardware receive timestamp, as reported by `SO_TIMESTAMPING`.
+///
+/// See [`RecvFull::hw_timestamp`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct HwTimestamp {
+    pub(crate) raw: Duration,
+    pub(crate) system: Option<SystemTime>,
+}
+
+impl HwTimestamp {
+    /// Raw hardware timestamp, i.e. the time since an unspecified epoch as
+    /// kept by the clock of the network device.
+    pub const fn raw(&self) -> Duration {
+        self.raw
+    }
+
+    /// Hardware timestamp converted to system time by the kernel.
+    ///
+    /// This is only set by older kernels (`SOF_TIMESTAMPING_SYS_HARDWARE` is
+    /// deprecated), convert [`raw`] yourself otherwise, e.g. using the PTP
+    /// clock of the network device.
+    ///
+    /// [`raw`]: HwTimestamp::raw
+    pub const fn system(&self) -> Option<SystemTime> {
+        self.system
+    }
+}
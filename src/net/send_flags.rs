@@ -0,0 +1,57 @@
+use std::ops;
+
+/// Flags used by [`UdpSocket::send_to_with_flags`].
+///
+/// Flags can be combined using the `|` operator.
+///
+/// [`UdpSocket::send_to_with_flags`]: crate::net::UdpSocket::send_to_with_flags
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SendFlags(u8);
+
+// These must be unique.
+const CONFIRM: u8 = 0b0001;
+const DONT_WAIT: u8 = 0b0010;
+
+impl SendFlags {
+    /// No flags.
+    pub const EMPTY: SendFlags = SendFlags(0);
+
+    /// Sets `MSG_CONFIRM`, telling the kernel the path to the destination is
+    /// still valid, e.g. because a reply was received from it.
+    ///
+    /// This keeps the neighbor (ARP) entry of the next hop fresh, avoiding
+    /// unnecessary probes.
+    pub const CONFIRM: SendFlags = SendFlags(CONFIRM);
+
+    /// Sets `MSG_DONTWAIT`, making the call non-blocking even if the socket is
+    /// in blocking mode, see [`UdpSocket::set_nonblocking`].
+    ///
+    /// [`UdpSocket::set_nonblocking`]: crate::net::UdpSocket::set_nonblocking
+    pub const DONT_WAIT: SendFlags = SendFlags(DONT_WAIT);
+
+    /// Returns true if the value includes [`SendFlags::CONFIRM`].
+    pub const fn is_confirm(self) -> bool {
+        (self.0 & CONFIRM) != 0
+    }
+
+    /// Returns true if the value includes [`SendFlags::DONT_WAIT`].
+    pub const fn is_dont_wait(self) -> bool {
+        (self.0 & DONT_WAIT) != 0
+    }
+}
+
+impl ops::BitOr for SendFlags {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        SendFlags(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for SendFlags {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
@@ -0,0 +1,86 @@
+//! Conversions between `socket2::Socket` and the `net` types.
+
+use std::io;
+use std::net;
+
+use ::socket2::{Socket, Type};
+
+use crate::net::{TcpListener, TcpStream, UdpSocket};
+
+/// Checks that `socket` is an IPv4 or IPv6 socket of type `ty` and puts it in
+/// non-blocking mode.
+fn prepare(socket: &Socket, ty: Type) -> io::Result<()> {
+    if socket.r#type()? != ty {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "socket has the wrong type",
+        ));
+    }
+    if socket.local_addr()?.as_socket().is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "socket is not an IPv4 or IPv6 socket",
+        ));
+    }
+    socket.set_nonblocking(true)
+}
+
+/// Converts a stream socket configured using `socket2`, e.g. connected using
+/// `Socket::connect`, into a `TcpStream`.
+///
+/// Returns an `InvalidInput` error if the socket isn't an IPv4 or IPv6 stream
+/// socket. The socket is put in non-blocking mode.
+impl TryFrom<Socket> for TcpStream {
+    type Error = io::Error;
+
+    fn try_from(socket: Socket) -> io::Result<TcpStream> {
+        prepare(&socket, Type::STREAM)?;
+        Ok(TcpStream::from_std(net::TcpStream::from(socket)))
+    }
+}
+
+/// Converts a listening socket configured using `socket2` into a
+/// `TcpListener`.
+///
+/// Returns an `InvalidInput` error if the socket isn't an IPv4 or IPv6 stream
+/// socket. The socket is put in non-blocking mode.
+impl TryFrom<Socket> for TcpListener {
+    type Error = io::Error;
+
+    fn try_from(socket: Socket) -> io::Result<TcpListener> {
+        prepare(&socket, Type::STREAM)?;
+        Ok(TcpListener::from_std(net::TcpListener::from(socket)))
+    }
+}
+
+/// Converts a bound datagram socket configured using `socket2` into a
+/// `UdpSocket`.
+///
+/// Returns an `InvalidInput` error if the socket isn't an IPv4 or IPv6
+/// datagram socket. The socket is put in non-blocking mode.
+impl TryFrom<Socket> for UdpSocket {
+    type Error = io::Error;
+
+    fn try_from(socket: Socket) -> io::Result<UdpSocket> {
+        prepare(&socket, Type::DGRAM)?;
+        Ok(UdpSocket::from_std(net::UdpSocket::from(socket)))
+    }
+}
+
+impl From<TcpStream> for Socket {
+    fn from(stream: TcpStream) -> Socket {
+        Socket::from(net::TcpStream::from(stream))
+    }
+}
+
+impl From<TcpListener> for Socket {
+    fn from(listener: TcpListener) -> Socket {
+        Socket::from(net::TcpListener::from(listener))
+    }
+}
+
+impl From<UdpSocket> for Socket {
+    fn from(socket: UdpSocket) -> Socket {
+        Socket::from(net::UdpSocket::from(socket))
+    }
+}
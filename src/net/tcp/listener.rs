@@ -1,3 +1,5 @@
+#[cfg(not(target_os = "wasi"))]
+use std::net::{Ipv4Addr, Ipv6Addr};
 use std::net::{self, SocketAddr};
 #[cfg(any(unix, target_os = "wasi"))]
 use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, RawFd};
@@ -6,7 +8,11 @@ use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, RawFd};
 #[cfg(target_os = "hermit")]
 use std::os::hermit::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, RawFd};
 #[cfg(windows)]
-use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
+use std::os::windows::io::{
+    AsRawSocket, AsSocket, BorrowedSocket, FromRawSocket, IntoRawSocket, RawSocket,
+};
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+use std::time::Duration;
 use std::{fmt, io};
 
 use crate::io_source::IoSource;
@@ -14,7 +20,7 @@ use crate::net::TcpStream;
 #[cfg(any(unix, target_os = "hermit"))]
 use crate::sys::tcp::set_reuseaddr;
 #[cfg(not(target_os = "wasi"))]
-use crate::sys::tcp::{bind, listen, new_for_addr};
+use crate::sys::tcp::{bind, listen, new_for_addr, set_only_v6};
 use crate::{event, sys, Interest, Registry, Token};
 
 /// A structure representing a socket server
@@ -59,6 +65,78 @@ impl TcpListener {
     /// 4. Calls `listen` on the socket to prepare it to receive new connections.
     #[cfg(not(target_os = "wasi"))]
     pub fn bind(addr: SocketAddr) -> io::Result<TcpListener> {
+        let listener = TcpListener::new_for_addr(addr)?;
+        bind(&listener.inner, addr)?;
+        listen(&listener.inner, 1024)?;
+        Ok(listener)
+    }
+
+    /// Bind a new TCP listener to the IPv6 address `addr`, setting the
+    /// `IPV6_V6ONLY` option to `only_v6` before binding.
+    ///
+    /// With `only_v6` set to `false` the listener also accepts IPv4
+    /// connections, the peer address of which is returned as an IPv4-mapped
+    /// IPv6 address (e.g. `::ffff:127.0.0.1`). Some platforms, such as
+    /// OpenBSD, don't support this and return an error.
+    ///
+    /// Returns an error of the kind `InvalidInput` if `addr` is an IPv4
+    /// address.
+    #[cfg(not(target_os = "wasi"))]
+    pub fn bind_with_only_v6(addr: SocketAddr, only_v6: bool) -> io::Result<TcpListener> {
+        if !addr.is_ipv6() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "IPV6_V6ONLY requires an IPv6 address",
+            ));
+        }
+        let listener = TcpListener::new_for_addr(addr)?;
+        set_only_v6(&listener.inner, only_v6)?;
+        bind(&listener.inner, addr)?;
+        listen(&listener.inner, 1024)?;
+        Ok(listener)
+    }
+
+    /// Bind a new TCP listener to `addr`, setting the `IP_FREEBIND` option
+    /// before binding.
+    ///
+    /// This allows binding to an address that isn't (yet) assigned to a local
+    /// interface, e.g. an address that is only assigned when an interface
+    /// comes up or a failover address.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn bind_freebind(addr: SocketAddr) -> io::Result<TcpListener> {
+        let listener = TcpListener::new_for_addr(addr)?;
+        sys::tcp::set_freebind(&listener.inner, true)?;
+        bind(&listener.inner, addr)?;
+        listen(&listener.inner, 1024)?;
+        Ok(listener)
+    }
+
+    /// Bind a new TCP listener to `addr`, setting the `IP_TRANSPARENT` option
+    /// before binding.
+    ///
+    /// This allows binding to non-local addresses and, combined with a
+    /// `TPROXY` firewall rule, accepting connections destined for arbitrary
+    /// addresses, as used by transparent proxies. The original destination of
+    /// an accepted connection is its [`local_addr`].
+    ///
+    /// Setting this option requires the `CAP_NET_ADMIN` capability, without
+    /// it a `PermissionDenied` error is returned.
+    ///
+    /// [`local_addr`]: TcpStream::local_addr
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn bind_transparent(addr: SocketAddr) -> io::Result<TcpListener> {
+        let listener = TcpListener::new_for_addr(addr)?;
+        sys::tcp::set_transparent(&listener.inner, true)?;
+        bind(&listener.inner, addr)?;
+        listen(&listener.inner, 1024)?;
+        Ok(listener)
+    }
+
+    /// Create a new, unbound, socket for `addr`.
+    #[cfg(not(target_os = "wasi"))]
+    fn new_for_addr(addr: SocketAddr) -> io::Result<TcpListener> {
         let socket = new_for_addr(addr)?;
         #[cfg(any(unix, target_os = "hermit"))]
         let listener = unsafe { TcpListener::from_raw_fd(socket) };
@@ -75,8 +153,6 @@ impl TcpListener {
         #[cfg(not(windows))]
         set_reuseaddr(&listener.inner, true)?;
 
-        bind(&listener.inner, addr)?;
-        listen(&listener.inner, 1024)?;
         Ok(listener)
     }
 
@@ -92,6 +168,16 @@ impl TcpListener {
         }
     }
 
+    /// Creates a new `TcpListener` from a standard `net::TcpListener`, setting
+    /// it in non-blocking mode.
+    ///
+    /// This is the same as [`TcpListener::from_std`], but doesn't require the
+    /// caller to call `set_nonblocking(true)` first.
+    pub fn from_std_nonblocking(listener: net::TcpListener) -> io::Result<TcpListener> {
+        listener.set_nonblocking(true)?;
+        Ok(TcpListener::from_std(listener))
+    }
+
     /// Accepts a new `TcpStream`.
     ///
     /// This may return an `Err(e)` where `e.kind()` is
@@ -106,6 +192,42 @@ impl TcpListener {
         })
     }
 
+    /// Returns an iterator over the connections pending on this listener.
+    ///
+    /// The iterator calls [`accept`] for each item and returns `None` once
+    /// accepting would block, rather than blocking like the standard library's
+    /// version. This makes it suitable to accept all pending connections after
+    /// receiving a readable event. The returned streams are in non-blocking
+    /// mode, like all streams returned by [`accept`].
+    ///
+    /// Note that after returning `None` the iterator may return new
+    /// connections again, it's not fused.
+    ///
+    /// [`accept`]: TcpListener::accept
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::TcpListener;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// // After receiving a readable event for the listener.
+    /// for res in listener.incoming() {
+    ///     let (stream, address) = res?;
+    ///     // Handle the connection...
+    /// #   drop((stream, address));
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn incoming(&self) -> TcpIncoming<'_> {
+        TcpIncoming { listener: self }
+    }
+
     /// Returns the local socket address of this listener.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         self.inner.local_addr()
@@ -128,14 +250,202 @@ impl TcpListener {
         self.inner.ttl()
     }
 
+    /// Defers accepting connections until the client has sent data.
+    ///
+    /// With this set the listener only becomes readable, and [`accept`] only
+    /// returns a connection, once the first data of the connection has
+    /// arrived. This avoids waking up for connections that haven't sent their
+    /// request yet. A zero `timeout` disables the option.
+    ///
+    /// On Linux and Android this sets the `TCP_DEFER_ACCEPT` option. After
+    /// `timeout` (rounded up to whole seconds) connections that haven't sent
+    /// any data are accepted anyway.
+    ///
+    /// On FreeBSD this installs the `dataready` accept filter using the
+    /// `SO_ACCEPTFILTER` option, which doesn't support a timeout. A non-zero
+    /// `timeout` installs the filter, a zero `timeout` removes it. The
+    /// `accf_data` kernel module must be loaded.
+    ///
+    /// [`accept`]: TcpListener::accept
+    #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux")))
+    )]
+    pub fn set_defer_accept(&self, timeout: Duration) -> io::Result<()> {
+        sys::tcp::set_defer_accept(&self.inner, timeout)
+    }
+
+    /// Gets the value of the `TCP_DEFER_ACCEPT` option on this socket.
+    ///
+    /// For more information about this option, see [`set_defer_accept`][link].
+    ///
+    /// [link]: #method.set_defer_accept
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn defer_accept(&self) -> io::Result<Duration> {
+        sys::tcp::defer_accept(&self.inner)
+    }
+
+    /// Get the value of the `IP_FREEBIND` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`bind_freebind`][link].
+    ///
+    /// [link]: TcpListener::bind_freebind
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn freebind(&self) -> io::Result<bool> {
+        sys::tcp::freebind(&self.inner)
+    }
+
+    /// Get the value of the `IP_TRANSPARENT` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`bind_transparent`][link].
+    ///
+    /// [link]: TcpListener::bind_transparent
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn transparent(&self) -> io::Result<bool> {
+        sys::tcp::transparent(&self.inner)
+    }
+
+    /// Get the value of the `IPV6_V6ONLY` option on this socket.
+    ///
+    /// Returns an error if this is not an IPv6 socket.
+    #[cfg(not(target_os = "wasi"))]
+    pub fn only_v6(&self) -> io::Result<bool> {
+        sys::tcp::only_v6(&*self.inner)
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
     /// the field in the process. This can be useful for checking errors between
-    /// calls.
+    /// calls. A following call returns `Ok(None)`, until another error occurs.
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
+
+    /// Moves this listener into or out of non-blocking mode.
+    ///
+    /// Mio puts all listeners it creates in non-blocking mode and depends on it, a
+    /// blocking call would block the thread polling for events. This allows a
+    /// listener to be used in blocking mode temporarily, e.g. to perform a
+    /// synchronous handshake on a separate thread.
+    ///
+    /// # Notes
+    ///
+    /// While in blocking mode the listener must not be registered with a
+    /// [`Registry`]. Deregister it before switching to blocking mode and only
+    /// register it again after switching back to non-blocking mode.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.inner.set_nonblocking(nonblocking)
+    }
+
+    /// Sets or clears the `FD_CLOEXEC` flag on this listener.
+    ///
+    /// Mio sets `FD_CLOEXEC` on all listeners it creates, meaning the listener is
+    /// closed when the process calls `exec`. Clearing the flag allows a child
+    /// process to inherit the listener.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn set_cloexec(&self, cloexec: bool) -> io::Result<()> {
+        sys::set_cloexec(self.as_raw_fd(), cloexec)
+    }
+
+    /// Returns `true` if the `FD_CLOEXEC` flag is set on this listener.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn is_cloexec(&self) -> io::Result<bool> {
+        sys::is_cloexec(self.as_raw_fd())
+    }
+}
+
+/// Bind TCP listeners to `port` on all IPv4 and IPv6 addresses.
+///
+/// Where supported this returns a single listener bound to `[::]:port` with
+/// the `IPV6_V6ONLY` option disabled, which accepts both IPv6 connections and
+/// IPv4 connections (using IPv4-mapped IPv6 addresses). On platforms that
+/// don't allow IPv6 sockets to accept IPv4 connections, such as OpenBSD, it
+/// returns two listeners: one bound to `[::]:port` and one bound to
+/// `0.0.0.0:port`.
+///
+/// If IPv6 is not available, e.g. in a container without IPv6 support, it
+/// falls back to a single listener bound to `0.0.0.0:port`.
+///
+/// If `port` is zero the OS picks a port, the same port is used for both
+/// listeners.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::bind_dualstack;
+/// use mio::{Interest, Poll, Token};
+///
+/// let poll = Poll::new()?;
+/// let mut listeners = bind_dualstack(0)?;
+/// for (n, listener) in listeners.iter_mut().enumerate() {
+///     poll.registry()
+///         .register(listener, Token(n), Interest::READABLE)?;
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+#[cfg(not(target_os = "wasi"))]
+pub fn bind_dualstack(port: u16) -> io::Result<Vec<TcpListener>> {
+    bind_dualstack_with(port, bind_v6)
+}
+
+/// Bind the IPv6 listener for [`bind_dualstack`], also returning whether or
+/// not it accepts IPv4 connections.
+#[cfg(not(target_os = "wasi"))]
+fn bind_v6(port: u16) -> io::Result<(TcpListener, bool)> {
+    let addr = SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), port);
+    let listener = TcpListener::new_for_addr(addr)?;
+    // Some platforms, e.g. OpenBSD, don't allow disabling `IPV6_V6ONLY`, in
+    // which case a separate IPv4 listener is needed.
+    let dualstack = set_only_v6(&listener.inner, false).is_ok();
+    bind(&listener.inner, addr)?;
+    listen(&listener.inner, 1024)?;
+    Ok((listener, dualstack))
+}
+
+#[cfg(not(target_os = "wasi"))]
+fn bind_dualstack_with<F>(port: u16, bind_v6: F) -> io::Result<Vec<TcpListener>>
+where
+    F: FnOnce(u16) -> io::Result<(TcpListener, bool)>,
+{
+    let v4_addr = |port| SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), port);
+    match bind_v6(port) {
+        Ok((listener, true)) => Ok(vec![listener]),
+        Ok((listener, false)) => {
+            // IPv6 sockets are IPv6 only, so we need a separate IPv4 socket.
+            let port = listener.local_addr()?.port();
+            let v4_listener = TcpListener::bind(v4_addr(port))?;
+            Ok(vec![listener, v4_listener])
+        }
+        // IPv6 is not available, only listen on IPv4.
+        Err(err) if is_ipv6_unavailable(&err) => {
+            TcpListener::bind(v4_addr(port)).map(|listener| vec![listener])
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Returns `true` if `err` means IPv6 is not supported (`EAFNOSUPPORT`) or
+/// the IPv6 address is not available (`EADDRNOTAVAIL`) on this host.
+#[cfg(not(target_os = "wasi"))]
+fn is_ipv6_unavailable(err: &io::Error) -> bool {
+    #[cfg(any(unix, target_os = "hermit"))]
+    const EAFNOSUPPORT: i32 = libc::EAFNOSUPPORT;
+    #[cfg(windows)]
+    const EAFNOSUPPORT: i32 = windows_sys::Win32::Networking::WinSock::WSAEAFNOSUPPORT;
+    err.kind() == io::ErrorKind::AddrNotAvailable || err.raw_os_error() == Some(EAFNOSUPPORT)
 }
 
 impl event::Source for TcpListener {
@@ -216,6 +526,13 @@ impl AsRawSocket for TcpListener {
     }
 }
 
+#[cfg(windows)]
+impl AsSocket for TcpListener {
+    fn as_socket(&self) -> BorrowedSocket<'_> {
+        self.inner.as_socket()
+    }
+}
+
 #[cfg(windows)]
 impl FromRawSocket for TcpListener {
     /// Converts a `RawSocket` to a `TcpListener`.
@@ -246,3 +563,53 @@ impl From<TcpListener> for net::TcpListener {
         }
     }
 }
+
+/// An iterator over the connections pending on a [`TcpListener`].
+///
+/// This struct is created by [`TcpListener::incoming`], see its documentation
+/// for more.
+#[derive(Debug)]
+pub struct TcpIncoming<'a> {
+    listener: &'a TcpListener,
+}
+
+impl Iterator for TcpIncoming<'_> {
+    type Item = io::Result<(TcpStream, SocketAddr)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.listener.accept() {
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => None,
+            res => Some(res),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "os-poll", not(target_os = "wasi")))]
+mod tests {
+    use std::io;
+
+    use super::bind_dualstack_with;
+
+    #[test]
+    fn bind_dualstack_without_ipv6() {
+        #[cfg(any(unix, target_os = "hermit"))]
+        let err = io::Error::from_raw_os_error(libc::EAFNOSUPPORT);
+        #[cfg(windows)]
+        let err = io::Error::from_raw_os_error(
+            windows_sys::Win32::Networking::WinSock::WSAEAFNOSUPPORT,
+        );
+        let listeners = bind_dualstack_with(0, |_| Err(err)).unwrap();
+        assert_eq!(listeners.len(), 1);
+        assert!(listeners[0].local_addr().unwrap().is_ipv4());
+
+        let err = io::Error::from(io::ErrorKind::AddrNotAvailable);
+        let listeners = bind_dualstack_with(0, |_| Err(err)).unwrap();
+        assert_eq!(listeners.len(), 1);
+        assert!(listeners[0].local_addr().unwrap().is_ipv4());
+
+        // Other errors are returned.
+        let err = io::Error::from(io::ErrorKind::PermissionDenied);
+        let err = bind_dualstack_with(0, |_| Err(err)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+    }
+}
@@ -1,5 +1,7 @@
 mod listener;
-pub use self::listener::TcpListener;
+#[cfg(not(target_os = "wasi"))]
+pub use self::listener::bind_dualstack;
+pub use self::listener::{TcpIncoming, TcpListener};
 
 mod stream;
 pub use self::stream::TcpStream;
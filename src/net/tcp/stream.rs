@@ -1,6 +1,8 @@
 use std::fmt;
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
 use std::net::{self, Shutdown, SocketAddr};
+#[cfg(target_os = "linux")]
+use std::sync::{Arc, Mutex, OnceLock};
 #[cfg(any(unix, target_os = "wasi"))]
 use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, RawFd};
 // TODO: once <https://github.com/rust-lang/rust/issues/126198> is fixed this
@@ -8,9 +10,29 @@ use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, RawFd};
 #[cfg(target_os = "hermit")]
 use std::os::hermit::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, RawFd};
 #[cfg(windows)]
-use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
+use std::os::windows::io::{
+    AsRawSocket, AsSocket, BorrowedSocket, FromRawSocket, IntoRawSocket, RawSocket,
+};
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+use std::time::Duration;
 
 use crate::io_source::IoSource;
+#[cfg(target_os = "linux")]
+use crate::net::ZcCompletion;
+#[cfg(not(target_os = "wasi"))]
+use crate::sys;
 #[cfg(not(target_os = "wasi"))]
 use crate::sys::tcp::{connect, new_for_addr};
 use crate::{event, Interest, Registry, Token};
@@ -49,6 +71,10 @@ use crate::{event, Interest, Registry, Token};
 /// ```
 pub struct TcpStream {
     inner: IoSource<net::TcpStream>,
+    /// Sequence id of the next `MSG_ZEROCOPY` send, shared with streams
+    /// created using `try_clone`. Only allocated once needed.
+    #[cfg(target_os = "linux")]
+    zerocopy_id: OnceLock<Arc<Mutex<u32>>>,
 }
 
 impl TcpStream {
@@ -80,6 +106,8 @@ impl TcpStream {
     ///     went wrong.
     ///  6. Now the stream can be used.
     ///
+    /// Steps 4 and 5 are implemented by [`TcpStream::poll_connected`].
+    ///
     /// This may return a `WouldBlock` in which case the socket connection
     /// cannot be completed immediately, it usually means there are insufficient
     /// entries in the routing cache.
@@ -96,6 +124,39 @@ impl TcpStream {
         Ok(stream)
     }
 
+    /// Check if a non-blocking connect, started by [`TcpStream::connect`], has
+    /// completed.
+    ///
+    /// Returns `Ok(true)` if the stream is connected, `Ok(false)` if the
+    /// connect is still in progress and an error if connecting failed, e.g.
+    /// `ConnectionRefused`. This should be called after receiving a writable
+    /// event, see the [`connect`] documentation.
+    ///
+    /// # Notes
+    ///
+    /// On Windows Mio doesn't use `ConnectEx`, but a non-blocking `connect`
+    /// like on Unix. Its completion is reported as a writable event, or as an
+    /// error event if connecting failed, after which the error is retrieved
+    /// using `SO_ERROR`. This means the semantics of this method are the same
+    /// on all platforms. Do note that Windows retries connecting to a port no
+    /// one is listening on, so it can take a couple of seconds before
+    /// `ConnectionRefused` is returned.
+    ///
+    /// [`connect`]: TcpStream::connect
+    #[cfg(not(target_os = "wasi"))]
+    pub fn poll_connected(&self) -> io::Result<bool> {
+        if let Some(err) = self.take_error()? {
+            return Err(err);
+        }
+        match self.peer_addr() {
+            Ok(_) => Ok(true),
+            Err(ref err) if err.kind() == io::ErrorKind::NotConnected => Ok(false),
+            #[cfg(unix)]
+            Err(ref err) if err.raw_os_error() == Some(libc::EINPROGRESS) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Creates a new `TcpStream` from a standard `net::TcpStream`.
     ///
     /// This function is intended to be used to wrap a TCP stream from the
@@ -111,9 +172,50 @@ impl TcpStream {
     pub fn from_std(stream: net::TcpStream) -> TcpStream {
         TcpStream {
             inner: IoSource::new(stream),
+            #[cfg(target_os = "linux")]
+            zerocopy_id: OnceLock::new(),
         }
     }
 
+    /// Creates a new `TcpStream` from a standard `net::TcpStream`, setting it
+    /// in non-blocking mode.
+    ///
+    /// This is the same as [`TcpStream::from_std`], but doesn't require the
+    /// caller to call `set_nonblocking(true)` first.
+    pub fn from_std_nonblocking(stream: net::TcpStream) -> io::Result<TcpStream> {
+        stream.set_nonblocking(true)?;
+        Ok(TcpStream::from_std(stream))
+    }
+
+    /// Duplicates this stream for use in the process with id `target_pid`.
+    ///
+    /// This is the Windows equivalent of passing a file descriptor using
+    /// `SCM_RIGHTS` on Unix. The returned bytes are a `WSAPROTOCOL_INFOW`
+    /// structure, as filled in by `WSADuplicateSocketW`, which must be sent to
+    /// the target process (by some other means) and passed to
+    /// [`TcpStream::from_shared`] there to create the stream.
+    ///
+    /// Both streams refer to the same connection, but are independent sources
+    /// that must be registered separately.
+    #[cfg(windows)]
+    #[cfg_attr(docsrs, doc(cfg(windows)))]
+    pub fn share(&self, target_pid: u32) -> io::Result<Vec<u8>> {
+        sys::tcp::share(&self.inner, target_pid)
+    }
+
+    /// Creates a `TcpStream` from the bytes returned by [`TcpStream::share`].
+    ///
+    /// The returned stream is put in non-blocking mode. Returns an error of
+    /// kind [`InvalidInput`] if `info` doesn't have the size of a
+    /// `WSAPROTOCOL_INFOW` structure.
+    ///
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    #[cfg(windows)]
+    #[cfg_attr(docsrs, doc(cfg(windows)))]
+    pub fn from_shared(info: &[u8]) -> io::Result<TcpStream> {
+        sys::tcp::from_shared(info).map(TcpStream::from_std)
+    }
+
     /// Returns the socket address of the remote peer of this TCP connection.
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
         self.inner.peer_addr()
@@ -124,6 +226,37 @@ impl TcpStream {
         self.inner.local_addr()
     }
 
+    /// Creates a new independently owned handle to the underlying socket.
+    ///
+    /// The returned `TcpStream` refers to the same connection as `self`, but
+    /// is a separate [`event::Source`] with its own registration. This allows,
+    /// for example, reading and writing the connection from different threads
+    /// or `Poll` instances.
+    ///
+    /// # Notes
+    ///
+    /// The clone is not registered, even if `self` is. Each handle must be
+    /// registered, and deregistered, independently. Dropping one handle does
+    /// not deregister the other.
+    ///
+    /// Both handles share the same socket, thus I/O on one handle affects the
+    /// readiness of the other. For example reading all data using one handle
+    /// means no data is available for reading on the other.
+    pub fn try_clone(&self) -> io::Result<TcpStream> {
+        #[cfg(target_os = "linux")]
+        {
+            // The kernel keeps a single sequence counter for the socket, so
+            // the clone must share it.
+            let zerocopy_id = self.zerocopy_id().clone();
+            self.inner.try_clone().map(|stream| TcpStream {
+                inner: IoSource::new(stream),
+                zerocopy_id: OnceLock::from(zerocopy_id),
+            })
+        }
+        #[cfg(not(target_os = "linux"))]
+        self.inner.try_clone().map(TcpStream::from_std)
+    }
+
     /// Shuts down the read, write, or both halves of this connection.
     ///
     /// This function will cause all pending and future I/O on the specified
@@ -194,15 +327,442 @@ impl TcpStream {
         self.inner.ttl()
     }
 
+    /// Get the value of the `IPV6_V6ONLY` option on this socket.
+    ///
+    /// Returns an error if this is not an IPv6 socket.
+    #[cfg(not(target_os = "wasi"))]
+    pub fn only_v6(&self) -> io::Result<bool> {
+        sys::tcp::only_v6(&*self.inner)
+    }
+
+    /// Gets the idle time before TCP keepalive probes are sent.
+    ///
+    /// This is the value of the `TCP_KEEPIDLE` option, or `TCP_KEEPALIVE` on
+    /// Apple platforms. Keepalive probes are only sent if `SO_KEEPALIVE` is
+    /// enabled, but this returns the effective value regardless.
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "illumos",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(
+            target_os = "android",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "illumos",
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "netbsd",
+            target_os = "tvos",
+            target_os = "visionos",
+            target_os = "watchos",
+        )))
+    )]
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        sys::tcp::keepalive_time(&self.inner)
+    }
+
+    /// Gets the time between TCP keepalive probes, the value of the
+    /// `TCP_KEEPINTVL` option.
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "illumos",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(
+            target_os = "android",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "illumos",
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "netbsd",
+            target_os = "tvos",
+            target_os = "visionos",
+            target_os = "watchos",
+        )))
+    )]
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        sys::tcp::keepalive_interval(&self.inner)
+    }
+
+    /// Gets the number of unanswered TCP keepalive probes before the
+    /// connection is dropped, the value of the `TCP_KEEPCNT` option.
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "illumos",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(
+            target_os = "android",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "illumos",
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "netbsd",
+            target_os = "tvos",
+            target_os = "visionos",
+            target_os = "watchos",
+        )))
+    )]
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        sys::tcp::keepalive_retries(&self.inner)
+    }
+
+    /// Sets the value of the `SO_RCVLOWAT` option on this socket.
+    ///
+    /// This sets the minimum number of bytes that must be buffered before the
+    /// socket is considered readable, avoiding wake ups for small amounts of
+    /// data.
+    ///
+    /// # Notes
+    ///
+    /// Readiness is still reported for other reasons, such as the peer closing
+    /// the connection, so a read may return fewer than `lowat` bytes.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn set_recv_lowat(&self, lowat: u32) -> io::Result<()> {
+        sys::tcp::set_recv_lowat(&self.inner, lowat)
+    }
+
+    /// Gets the value of the `SO_RCVLOWAT` option on this socket.
+    ///
+    /// For more information about this option, see [`set_recv_lowat`][link].
+    ///
+    /// [link]: #method.set_recv_lowat
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn recv_lowat(&self) -> io::Result<u32> {
+        sys::tcp::recv_lowat(&self.inner)
+    }
+
+    /// Sets the value of the `TCP_CORK` option on this socket.
+    ///
+    /// If set, only full frames are sent until the option is cleared again,
+    /// after which any pending data is sent immediately. The kernel limits
+    /// the time data is held back to 200ms.
+    ///
+    /// See [`corked`] to ensure the option is always cleared again.
+    ///
+    /// [`corked`]: TcpStream::corked
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn set_cork(&self, cork: bool) -> io::Result<()> {
+        sys::tcp::set_cork(&self.inner, cork)
+    }
+
+    /// Gets the value of the `TCP_CORK` option on this socket.
+    ///
+    /// For more information about this option, see [`set_cork`][link].
+    ///
+    /// [link]: #method.set_cork
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn cork(&self) -> io::Result<bool> {
+        sys::tcp::cork(&self.inner)
+    }
+
+    /// Corks the socket, calls `f` and uncorks the socket again, flushing any
+    /// data written by `f`.
+    ///
+    /// The socket is uncorked when `f` returns, including when it returns an
+    /// error or panics. If `f` returns an error that error is returned,
+    /// otherwise an error uncorking the socket is returned. See [`set_cork`]
+    /// for more information about corking.
+    ///
+    /// [`set_cork`]: TcpStream::set_cork
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::io::Write;
+    ///
+    /// use mio::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080".parse()?)?;
+    /// // Send the header and body in as few packets as possible.
+    /// stream.corked(|mut stream| {
+    ///     stream.write_all(b"header")?;
+    ///     stream.write_all(b"body")
+    /// })?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn corked<F, T>(&self, f: F) -> io::Result<T>
+    where
+        F: FnOnce(&TcpStream) -> io::Result<T>,
+    {
+        /// Uncorks the socket if `f` panics.
+        struct Uncork<'a>(&'a TcpStream);
+
+        impl Drop for Uncork<'_> {
+            fn drop(&mut self) {
+                let _ = self.0.set_cork(false);
+            }
+        }
+
+        self.set_cork(true)?;
+        let guard = Uncork(self);
+        let res = f(self);
+        std::mem::forget(guard);
+        let uncorked = self.set_cork(false);
+        let value = res?;
+        uncorked.map(|()| value)
+    }
+
+    /// Sets the value of the `SO_BUSY_POLL` option on this socket.
+    ///
+    /// This sets the approximate time the kernel will busy poll the device
+    /// receive queue when no data is available, instead of waiting for an
+    /// interrupt. A `timeout` of zero disables busy polling.
+    ///
+    /// Busy polling trades CPU time for latency: the CPU spins while waiting
+    /// for packets, so enabling it can keep a core fully occupied even when
+    /// little traffic arrives. It should only be used by latency-sensitive
+    /// applications that can afford to dedicate CPU time to it.
+    ///
+    /// # Notes
+    ///
+    /// Increasing the value requires the `CAP_NET_ADMIN` capability, without
+    /// it this returns a `PermissionDenied` error. The value is set in
+    /// microseconds, `timeout` is truncated to whole microseconds.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_busy_poll(&self, timeout: Duration) -> io::Result<()> {
+        sys::tcp::set_busy_poll(&self.inner, timeout)
+    }
+
+    /// Gets the value of the `SO_BUSY_POLL` option on this socket.
+    ///
+    /// For more information about this option, see [`set_busy_poll`][link].
+    ///
+    /// [link]: #method.set_busy_poll
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn busy_poll(&self) -> io::Result<Duration> {
+        sys::tcp::busy_poll(&self.inner)
+    }
+
+    /// Sets the value of the `SO_ZEROCOPY` option on this socket.
+    ///
+    /// This must be enabled before [`send_zerocopy`] can be used.
+    ///
+    /// [`send_zerocopy`]: TcpStream::send_zerocopy
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_zerocopy(&self, zerocopy: bool) -> io::Result<()> {
+        sys::tcp::set_zerocopy(&self.inner, zerocopy)
+    }
+
+    /// Gets the value of the `SO_ZEROCOPY` option on this socket.
+    ///
+    /// For more information about this option, see [`set_zerocopy`][link].
+    ///
+    /// [link]: #method.set_zerocopy
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn zerocopy(&self) -> io::Result<bool> {
+        sys::tcp::zerocopy(&self.inner)
+    }
+
+    /// Sends data on the socket using `MSG_ZEROCOPY`. On success, returns the
+    /// number of bytes written and the sequence id of this send.
+    ///
+    /// With zerocopy the kernel doesn't copy `buf`, but references the pages
+    /// directly. This means that **the data in `buf` must not be modified**
+    /// until the kernel reports the send as completed. Completions are reported
+    /// on the socket's error queue, which will trigger an [error event], and
+    /// can be read using [`poll_zerocopy_completions`].
+    ///
+    /// Each successful send is assigned a sequence id by the kernel, starting
+    /// at zero and incremented by one for each send. Mio keeps track of this
+    /// counter, including for streams created using [`try_clone`], so the
+    /// returned id matches the id in the completion. This assumes all zerocopy
+    /// sends on the socket are made using this method, starting from when the
+    /// `TcpStream` was created.
+    ///
+    /// Zerocopy has a setup cost per send, it's only worth it for large writes
+    /// (roughly over 10 KB). It must be enabled first using
+    /// [`set_zerocopy`].
+    ///
+    /// [error event]: crate::event::Event::is_error
+    /// [`poll_zerocopy_completions`]: TcpStream::poll_zerocopy_completions
+    /// [`set_zerocopy`]: TcpStream::set_zerocopy
+    /// [`try_clone`]: TcpStream::try_clone
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn send_zerocopy(&self, buf: &[u8]) -> io::Result<(usize, u32)> {
+        // Hold the lock while sending to ensure the ids are assigned in the
+        // same order as the kernel does.
+        let mut next_id = self.zerocopy_id().lock().unwrap();
+        let n = self
+            .inner
+            .do_io(|inner| sys::tcp::send_zerocopy(inner, buf))?;
+        let id = *next_id;
+        *next_id = id.wrapping_add(1);
+        Ok((n, id))
+    }
+
+    #[cfg(target_os = "linux")]
+    fn zerocopy_id(&self) -> &Arc<Mutex<u32>> {
+        self.zerocopy_id.get_or_init(|| Arc::new(Mutex::new(0)))
+    }
+
+    /// Reads all zerocopy completion notifications from the socket's error
+    /// queue, see [`send_zerocopy`].
+    ///
+    /// Returns an empty vector if no completions are pending.
+    ///
+    /// [`send_zerocopy`]: TcpStream::send_zerocopy
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn poll_zerocopy_completions(&self) -> io::Result<Vec<ZcCompletion>> {
+        sys::tcp::zerocopy_completions(&self.inner)
+    }
+
+    /// Gets the value of the `SO_INCOMING_CPU` option on this socket.
+    ///
+    /// Returns the CPU the kernel associates with this connection, i.e. the
+    /// CPU that processed the most recently received packet. This can be used
+    /// to hand the stream to a worker pinned to that CPU. Returns `-1` if the
+    /// kernel hasn't associated a CPU with the connection yet.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn incoming_cpu(&self) -> io::Result<i32> {
+        sys::tcp::incoming_cpu(&self.inner)
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
     /// the field in the process. This can be useful for checking errors between
-    /// calls.
+    /// calls. A following call returns `Ok(None)`, until another error occurs.
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
 
+    /// Returns the number of bytes available to read from this stream, using
+    /// the `FIONREAD` ioctl.
+    ///
+    /// This can be used to allocate a buffer of the exact size before reading.
+    /// Note that more bytes may arrive between this call and the read.
+    #[cfg(any(unix, windows))]
+    #[cfg_attr(docsrs, doc(cfg(any(unix, windows))))]
+    pub fn available(&self) -> io::Result<usize> {
+        sys::tcp::available(&self.inner)
+    }
+
+    /// Returns true if the read position of this stream is at the urgent
+    /// (out-of-band) data mark, using the `SIOCATMARK` ioctl.
+    ///
+    /// Reads stop at the mark, so once a read returns fewer bytes than
+    /// expected this can be used to determine whether the urgent data has
+    /// been reached and handling should switch accordingly.
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(
+            target_os = "android",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "netbsd",
+            target_os = "openbsd",
+            target_os = "tvos",
+            target_os = "visionos",
+            target_os = "watchos",
+        )))
+    )]
+    pub fn at_mark(&self) -> io::Result<bool> {
+        sys::tcp::at_mark(&self.inner)
+    }
+
+    /// Moves this stream into or out of non-blocking mode.
+    ///
+    /// Mio puts all streams it creates in non-blocking mode and depends on it, a
+    /// blocking call would block the thread polling for events. This allows a
+    /// stream to be used in blocking mode temporarily, e.g. to perform a
+    /// synchronous handshake on a separate thread.
+    ///
+    /// # Notes
+    ///
+    /// While in blocking mode the stream must not be registered with a
+    /// [`Registry`]. Deregister it before switching to blocking mode and only
+    /// register it again after switching back to non-blocking mode.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.inner.set_nonblocking(nonblocking)
+    }
+
+    /// Sets or clears the `FD_CLOEXEC` flag on this stream.
+    ///
+    /// Mio sets `FD_CLOEXEC` on all streams it creates, meaning the stream is
+    /// closed when the process calls `exec`. Clearing the flag allows a child
+    /// process to inherit the stream.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn set_cloexec(&self, cloexec: bool) -> io::Result<()> {
+        sys::set_cloexec(self.as_raw_fd(), cloexec)
+    }
+
+    /// Returns `true` if the `FD_CLOEXEC` flag is set on this stream.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn is_cloexec(&self) -> io::Result<bool> {
+        sys::is_cloexec(self.as_raw_fd())
+    }
+
     /// Receives data on the socket from the remote address to which it is
     /// connected, without removing that data from the queue. On success,
     /// returns the number of bytes peeked.
@@ -213,6 +773,195 @@ impl TcpStream {
         self.inner.peek(buf)
     }
 
+    /// Read as much of `buf` as is available without blocking.
+    ///
+    /// This keeps reading until either `buf` is full, the peer closed the
+    /// connection or no more data is available, returning the number of bytes
+    /// read. Unlike [`Read::read_exact`] this does **not** block waiting for
+    /// the full buffer, instead callers should keep track of the returned
+    /// length and resume reading into the remainder of `buf` once the stream
+    /// is readable again.
+    ///
+    /// If no bytes could be read at all this returns a [`WouldBlock`] error,
+    /// like `read`. A return value smaller than `buf.len()` thus means the
+    /// stream either hit `WouldBlock` or the peer closed the connection, which
+    /// can be distinguished by calling this method again.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn recv_exact_nonblocking(&self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut read = 0;
+        while read < buf.len() {
+            match self.inner.do_io(|inner| (&*inner).read(&mut buf[read..])) {
+                Ok(0) => break,
+                Ok(n) => read += n,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock && read != 0 => break,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(read)
+    }
+
+    /// Write as much of `buf` as possible without blocking.
+    ///
+    /// Unlike [`Write::write_all`] this doesn't return an error if the socket's
+    /// send buffer is full, instead it returns the number of bytes written so
+    /// far. If this is less than `buf.len()` the caller should wait for a
+    /// writable event and resume writing from that position.
+    ///
+    /// If the connection is closed ([`BrokenPipe`] or [`ConnectionReset`])
+    /// after some bytes were written this returns the number of bytes written,
+    /// as the next write returns the error again. Any other error is returned
+    /// directly, even if some bytes were already written.
+    ///
+    /// [`BrokenPipe`]: io::ErrorKind::BrokenPipe
+    /// [`ConnectionReset`]: io::ErrorKind::ConnectionReset
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    /// use mio::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect(listener.local_addr()?)?;
+    /// # let _peer = listener.accept()?;
+    ///
+    /// // Wait for the stream to be writable...
+    /// # std::thread::sleep(std::time::Duration::from_millis(100));
+    ///
+    /// let mut buf: &[u8] = b"Hello world!";
+    /// let n = stream.write_all_nonblocking(buf)?;
+    /// buf = &buf[n..];
+    /// if !buf.is_empty() {
+    ///     // Wait for the stream to be writable again and write the rest of
+    ///     // `buf`.
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn write_all_nonblocking(&self, buf: &[u8]) -> io::Result<usize> {
+        let mut written = 0;
+        while written < buf.len() {
+            match self.inner.do_io(|mut inner| inner.write(&buf[written..])) {
+                Ok(0) => {
+                    return if written == 0 {
+                        Err(io::ErrorKind::WriteZero.into())
+                    } else {
+                        Ok(written)
+                    };
+                }
+                Ok(n) => written += n,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(ref err) if written != 0 && is_closed(err) => break,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(written)
+    }
+
+    /// Write as much of `bufs` as possible without blocking.
+    ///
+    /// This is the vectored version of [`write_all_nonblocking`], see it for
+    /// more information. Returns the total number of bytes written, across all
+    /// buffers.
+    ///
+    /// [`write_all_nonblocking`]: TcpStream::write_all_nonblocking
+    pub fn write_all_vectored_nonblocking(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        let total = bufs.iter().map(|buf| buf.len()).sum::<usize>();
+        let mut written = 0;
+        // Index of the first buffer not (completely) written and the number
+        // of bytes of it that are written.
+        let mut index = 0;
+        let mut offset = 0;
+        while written < total {
+            let res = if offset == 0 {
+                self.inner
+                    .do_io(|mut inner| inner.write_vectored(&bufs[index..]))
+            } else {
+                // Avoid allocating to create a new slice of buffers by only
+                // writing the rest of the partially written buffer.
+                self.inner
+                    .do_io(|mut inner| inner.write(&bufs[index][offset..]))
+            };
+            match res {
+                Ok(0) => {
+                    return if written == 0 {
+                        Err(io::ErrorKind::WriteZero.into())
+                    } else {
+                        Ok(written)
+                    };
+                }
+                Ok(mut n) => {
+                    written += n;
+                    // Skip over the written (parts of) buffers.
+                    while index < bufs.len() && offset + n >= bufs[index].len() {
+                        n -= bufs[index].len() - offset;
+                        offset = 0;
+                        index += 1;
+                    }
+                    offset += n;
+                }
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(ref err) if written != 0 && is_closed(err) => break,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(written)
+    }
+
+    /// Send up to `count` bytes of `file`, starting at `offset`, without
+    /// copying them into user space, using `sendfile(2)`.
+    ///
+    /// On success, returns the number of bytes sent and the offset to use for
+    /// the next call. If the file is only partially sent the call should be
+    /// repeated once the socket is writable again, zero bytes sent means the
+    /// end of the file is reached. If the socket's send buffer is full this
+    /// returns a [`WouldBlock`] error.
+    ///
+    /// If `count` is zero nothing is sent and `(0, offset)` is returned on all
+    /// platforms, i.e. it doesn't send the remainder of the file like
+    /// `sendfile(2)` does on BSD-like systems.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(
+            target_os = "android",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "tvos",
+            target_os = "visionos",
+            target_os = "watchos",
+        )))
+    )]
+    pub fn send_file<F>(&self, file: &F, offset: u64, count: usize) -> io::Result<(usize, u64)>
+    where
+        F: AsRawFd + ?Sized,
+    {
+        self.inner
+            .do_io(|inner| sys::tcp::send_file(inner, file.as_raw_fd(), offset, count))
+            .map(|n| (n, offset + n as u64))
+    }
+
     /// Execute an I/O operation ensuring that the socket receives more events
     /// if it hits a [`WouldBlock`] error.
     ///
@@ -398,6 +1147,13 @@ impl AsRawSocket for TcpStream {
     }
 }
 
+#[cfg(windows)]
+impl AsSocket for TcpStream {
+    fn as_socket(&self) -> BorrowedSocket<'_> {
+        self.inner.as_socket()
+    }
+}
+
 #[cfg(windows)]
 impl FromRawSocket for TcpStream {
     /// Converts a `RawSocket` to a `TcpStream`.
@@ -428,3 +1184,12 @@ impl From<TcpStream> for net::TcpStream {
         }
     }
 }
+
+/// Returns true if `err` means the connection was closed, in which case the
+/// next write will return the error again.
+fn is_closed(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset
+    )
+}
@@ -7,6 +7,8 @@
 //!
 //! [portability guidelines]: ../struct.Poll.html#portability
 
+#[cfg(unix)]
+use std::io::{IoSlice, IoSliceMut};
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
 #[cfg(any(unix, target_os = "wasi"))]
 use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, RawFd};
@@ -15,10 +17,16 @@ use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, RawFd};
 #[cfg(target_os = "hermit")]
 use std::os::hermit::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, RawFd};
 #[cfg(windows)]
-use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
+use std::os::windows::io::{
+    AsRawSocket, AsSocket, BorrowedSocket, FromRawSocket, IntoRawSocket, RawSocket,
+};
+#[cfg(target_os = "linux")]
+use std::time::{Duration, Instant};
 use std::{fmt, io, net};
 
 use crate::io_source::IoSource;
+#[cfg(target_os = "linux")]
+use crate::net::{RecvFlags, RecvFull, RecvMsg, SendFlags, SockFilter};
 use crate::{event, sys, Interest, Registry, Token};
 
 /// A User Datagram Protocol socket.
@@ -136,6 +144,16 @@ impl UdpSocket {
         }
     }
 
+    /// Creates a new `UdpSocket` from a standard `net::UdpSocket`, setting it
+    /// in non-blocking mode.
+    ///
+    /// This is the same as [`UdpSocket::from_std`], but doesn't require the
+    /// caller to call `set_nonblocking(true)` first.
+    pub fn from_std_nonblocking(socket: net::UdpSocket) -> io::Result<UdpSocket> {
+        socket.set_nonblocking(true)?;
+        Ok(UdpSocket::from_std(socket))
+    }
+
     /// Returns the socket address that this socket was created from.
     ///
     /// # Examples
@@ -186,6 +204,39 @@ impl UdpSocket {
         self.inner.peer_addr()
     }
 
+    /// Creates a new independently owned handle to the underlying socket.
+    ///
+    /// The returned `UdpSocket` refers to the same socket as `self`, but is a
+    /// separate [`event::Source`] with its own registration. This allows, for
+    /// example, receiving on one thread while sending from another.
+    ///
+    /// # Notes
+    ///
+    /// The clone is not registered, even if `self` is. Each handle must be
+    /// registered, and deregistered, independently.
+    ///
+    /// Readiness is shared at the OS level: a datagram received using one
+    /// handle is no longer available to the other.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// let clone = socket.try_clone()?;
+    /// assert_eq!(socket.local_addr()?, clone.local_addr()?);
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn try_clone(&self) -> io::Result<UdpSocket> {
+        self.inner.try_clone().map(UdpSocket::from_std)
+    }
+
     /// Sends data on the socket to the given address. On success, returns the
     /// number of bytes written.
     ///
@@ -214,6 +265,35 @@ impl UdpSocket {
         self.inner.do_io(|inner| inner.send_to(buf, target))
     }
 
+    /// Sends data on the socket to the given address, passing `flags` to
+    /// `sendto(2)`. On success, returns the number of bytes written.
+    ///
+    /// See [`SendFlags`] for the supported flags.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn send_to_with_flags(
+        &self,
+        buf: &[u8],
+        target: SocketAddr,
+        flags: SendFlags,
+    ) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_to_with_flags(inner, buf, target, flags))
+    }
+
+    /// Sends data from multiple buffers as a single datagram to the given
+    /// address, using `sendmsg(2)`. On success, returns the number of bytes
+    /// written.
+    ///
+    /// This allows e.g. sending a header and payload stored in separate
+    /// buffers without first concatenating them.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn send_to_vectored(&self, bufs: &[IoSlice<'_>], target: SocketAddr) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_to_vectored(inner, bufs, target))
+    }
+
     /// Receives data from the socket. On success, returns the number of bytes
     /// read and the address from whence the data came.
     ///
@@ -249,6 +329,80 @@ impl UdpSocket {
         self.inner.do_io(|inner| inner.recv_from(buf))
     }
 
+    /// Receives data from the socket, passing `flags` to `recvfrom(2)`. On
+    /// success, returns the number of bytes read and the address from whence
+    /// the data came.
+    ///
+    /// See [`RecvFlags`] for the supported flags.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn recv_from_with_flags(
+        &self,
+        buf: &mut [u8],
+        flags: RecvFlags,
+    ) -> io::Result<(usize, SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_from_with_flags(inner, buf, flags))
+    }
+
+    /// Receives data from the socket, reporting if the datagram was truncated.
+    ///
+    /// This works like [`recv_from`], but also returns whether the datagram
+    /// was larger than `buf`, in which case the remainder of the datagram is
+    /// discarded. Callers can use this to detect truncation and grow their
+    /// buffer, rather than silently losing data.
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// let sender = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// sender.send_to(b"Hello world!", socket.local_addr()?)?;
+    ///
+    /// // Wait for the socket to be readable...
+    /// # std::thread::sleep(std::time::Duration::from_millis(100));
+    ///
+    /// let mut buf = [0; 5];
+    /// let (n, truncated, addr) = socket.recv_from_trunc(&mut buf)?;
+    /// assert_eq!(&buf[..n], b"Hello");
+    /// assert!(truncated);
+    /// assert_eq!(addr, sender.local_addr()?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn recv_from_trunc(&self, buf: &mut [u8]) -> io::Result<(usize, bool, SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_from_trunc(inner, buf))
+    }
+
+    /// Receives a single datagram into multiple buffers, using `recvmsg(2)`.
+    /// On success, returns the number of bytes read and the address from
+    /// whence the data came.
+    ///
+    /// The buffers are filled in order, this allows e.g. receiving a fixed
+    /// size header and the payload of a datagram into separate buffers. If
+    /// the datagram is larger than the buffers combined the remainder is
+    /// discarded.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn recv_from_vectored(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_from_vectored(inner, bufs))
+    }
+
     /// Receives data from the socket, without removing it from the input queue.
     /// On success, returns the number of bytes read and the address from whence
     /// the data came.
@@ -384,6 +538,47 @@ impl UdpSocket {
         self.inner.broadcast()
     }
 
+    /// Sets the value of the `SO_DONTROUTE` option on this socket.
+    ///
+    /// When enabled outgoing datagrams bypass the routing table and are only
+    /// sent to hosts on directly attached networks. Sending to a destination
+    /// that is not on a directly attached network then fails, usually with a
+    /// `ENETUNREACH` error.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn set_dont_route(&self, dont_route: bool) -> io::Result<()> {
+        sys::udp::set_dont_route(&self.inner, dont_route)
+    }
+
+    /// Gets the value of the `SO_DONTROUTE` option on this socket.
+    ///
+    /// For more information about this option, see [`set_dont_route`].
+    ///
+    /// [`set_dont_route`]: UdpSocket::set_dont_route
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn dont_route(&self) -> io::Result<bool> {
+        sys::udp::dont_route(&self.inner)
+    }
+
+    /// Sets whether ICMP port unreachable messages are reported as errors.
+    ///
+    /// By default Windows reports a `WSAECONNRESET` error ([`ConnectionReset`])
+    /// on the next call to [`recv_from`] when a previous [`send_to`] resulted
+    /// in an ICMP port unreachable message. To match the behaviour on Unix
+    /// this is disabled for sockets created using [`UdpSocket::bind`], this
+    /// method can be used to restore the Windows behaviour. This uses the
+    /// `SIO_UDP_CONNRESET` ioctl.
+    ///
+    /// [`ConnectionReset`]: io::ErrorKind::ConnectionReset
+    /// [`recv_from`]: UdpSocket::recv_from
+    /// [`send_to`]: UdpSocket::send_to
+    #[cfg(windows)]
+    #[cfg_attr(docsrs, doc(cfg(windows)))]
+    pub fn set_connreset(&self, connreset: bool) -> io::Result<()> {
+        sys::udp::set_connreset(&self.inner, connreset)
+    }
+
     /// Sets the value of the `IP_MULTICAST_LOOP` option for this socket.
     ///
     /// If enabled, multicast packets will be looped back to the local socket.
@@ -423,6 +618,80 @@ impl UdpSocket {
         self.inner.multicast_ttl_v4()
     }
 
+    /// Sets the value of the `IP_MULTICAST_IF` option for this socket.
+    ///
+    /// Specifies the interface, by its local IPv4 address, used for outgoing
+    /// multicast packets. If `interface` is [`Ipv4Addr::UNSPECIFIED`] the
+    /// system chooses the interface, which is the default.
+    #[cfg(any(unix, windows))]
+    #[cfg_attr(docsrs, doc(cfg(any(unix, windows))))]
+    pub fn set_multicast_if_v4(&self, interface: Ipv4Addr) -> io::Result<()> {
+        sys::udp::set_multicast_if_v4(&self.inner, interface)
+    }
+
+    /// Gets the value of the `IP_MULTICAST_IF` option for this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_multicast_if_v4`][link].
+    ///
+    /// [link]: #method.set_multicast_if_v4
+    #[cfg(any(unix, windows))]
+    #[cfg_attr(docsrs, doc(cfg(any(unix, windows))))]
+    pub fn multicast_if_v4(&self) -> io::Result<Ipv4Addr> {
+        sys::udp::multicast_if_v4(&self.inner)
+    }
+
+    /// Sets the value of the `IPV6_MULTICAST_HOPS` option for this socket.
+    ///
+    /// Indicates the hop limit of outgoing multicast packets for this socket.
+    /// The default value is 1 which means that multicast packets don't leave
+    /// the local network unless explicitly requested.
+    ///
+    /// Note that this may not have any affect on IPv4 sockets.
+    #[cfg(any(unix, windows))]
+    #[cfg_attr(docsrs, doc(cfg(any(unix, windows))))]
+    pub fn set_multicast_hops_v6(&self, hops: u32) -> io::Result<()> {
+        sys::udp::set_multicast_hops_v6(&self.inner, hops)
+    }
+
+    /// Gets the value of the `IPV6_MULTICAST_HOPS` option for this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_multicast_hops_v6`][link].
+    ///
+    /// [link]: #method.set_multicast_hops_v6
+    #[cfg(any(unix, windows))]
+    #[cfg_attr(docsrs, doc(cfg(any(unix, windows))))]
+    pub fn multicast_hops_v6(&self) -> io::Result<u32> {
+        sys::udp::multicast_hops_v6(&self.inner)
+    }
+
+    /// Sets the value of the `IPV6_UNICAST_HOPS` option for this socket.
+    ///
+    /// Indicates the hop limit of outgoing unicast packets for this socket,
+    /// the IPv6 equivalent of [`set_ttl`][link].
+    ///
+    /// Note that this may not have any affect on IPv4 sockets.
+    ///
+    /// [link]: #method.set_ttl
+    #[cfg(any(unix, windows))]
+    #[cfg_attr(docsrs, doc(cfg(any(unix, windows))))]
+    pub fn set_unicast_hops_v6(&self, hops: u32) -> io::Result<()> {
+        sys::udp::set_unicast_hops_v6(&self.inner, hops)
+    }
+
+    /// Gets the value of the `IPV6_UNICAST_HOPS` option for this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_unicast_hops_v6`][link].
+    ///
+    /// [link]: #method.set_unicast_hops_v6
+    #[cfg(any(unix, windows))]
+    #[cfg_attr(docsrs, doc(cfg(any(unix, windows))))]
+    pub fn unicast_hops_v6(&self) -> io::Result<u32> {
+        sys::udp::unicast_hops_v6(&self.inner)
+    }
+
     /// Sets the value of the `IPV6_MULTICAST_LOOP` option for this socket.
     ///
     /// Controls whether this socket sees the multicast packets it sends itself.
@@ -496,6 +765,369 @@ impl UdpSocket {
         self.inner.ttl()
     }
 
+    /// Sets the value of the `SO_BUSY_POLL` option on this socket.
+    ///
+    /// This sets the approximate time the kernel will busy poll the device
+    /// receive queue when no data is available, instead of waiting for an
+    /// interrupt. A `timeout` of zero disables busy polling.
+    ///
+    /// Busy polling trades CPU time for latency: the CPU spins while waiting
+    /// for packets, so enabling it can keep a core fully occupied even when
+    /// little traffic arrives. It should only be used by latency-sensitive
+    /// applications that can afford to dedicate CPU time to it.
+    ///
+    /// # Notes
+    ///
+    /// Increasing the value requires the `CAP_NET_ADMIN` capability, without
+    /// it this returns a `PermissionDenied` error. The value is set in
+    /// microseconds, `timeout` is truncated to whole microseconds.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_busy_poll(&self, timeout: Duration) -> io::Result<()> {
+        sys::udp::set_busy_poll(&self.inner, timeout)
+    }
+
+    /// Gets the value of the `SO_BUSY_POLL` option on this socket.
+    ///
+    /// For more information about this option, see [`set_busy_poll`][link].
+    ///
+    /// [link]: #method.set_busy_poll
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn busy_poll(&self) -> io::Result<Duration> {
+        sys::udp::busy_poll(&self.inner)
+    }
+
+    /// Attaches a classic BPF program to the `SO_REUSEPORT` group of this
+    /// socket, using the `SO_ATTACH_REUSEPORT_CBPF` option.
+    ///
+    /// By default the kernel distributes datagrams among the sockets in a
+    /// `SO_REUSEPORT` group using a hash of the 4-tuple. With a steering
+    /// program attached the value returned by the program is used as index into
+    /// the group instead, which allows sharding on custom fields. If the index
+    /// is out of range the kernel falls back to the default hashing.
+    ///
+    /// Index into the group is the order in which the sockets were bound. The
+    /// program applies to the entire group, so it only needs to be attached to
+    /// a single socket.
+    ///
+    /// # Notes
+    ///
+    /// The socket must be created with `SO_REUSEPORT` set before it's bound,
+    /// which `UdpSocket::bind` doesn't do. Use [`UdpSocket::from_std`] to wrap
+    /// such a socket.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn attach_reuseport_cbpf(&self, program: &[SockFilter]) -> io::Result<()> {
+        sys::udp::attach_reuseport_cbpf(&self.inner, program)
+    }
+
+    /// Attaches a classic BPF (cBPF) filter to this socket, using the
+    /// `SO_ATTACH_FILTER` option.
+    ///
+    /// The filter is run by the kernel for every datagram received on this
+    /// socket. The value returned by the program is the number of bytes of the
+    /// datagram to keep, returning zero drops the datagram before it reaches
+    /// the receive queue. Attaching a filter replaces any previously attached
+    /// filter.
+    ///
+    /// # Notes
+    ///
+    /// Datagrams already in the receive queue are not filtered.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn attach_filter(&self, program: &[SockFilter]) -> io::Result<()> {
+        sys::udp::attach_filter(&self.inner, program)
+    }
+
+    /// Removes the filter attached using [`attach_filter`], using the
+    /// `SO_DETACH_FILTER` option.
+    ///
+    /// Returns an error if no filter is attached.
+    ///
+    /// [`attach_filter`]: UdpSocket::attach_filter
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn detach_filter(&self) -> io::Result<()> {
+        sys::udp::detach_filter(&self.inner)
+    }
+
+    /// Attaches an eBPF program to the `SO_REUSEPORT` group of this socket,
+    /// using the `SO_ATTACH_REUSEPORT_EBPF` option.
+    ///
+    /// `program` must be a file descriptor to a loaded eBPF program of type
+    /// `BPF_PROG_TYPE_SOCKET_FILTER`. See [`attach_reuseport_cbpf`] for more
+    /// information about steering programs.
+    ///
+    /// [`attach_reuseport_cbpf`]: UdpSocket::attach_reuseport_cbpf
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn attach_reuseport_ebpf(&self, program: BorrowedFd<'_>) -> io::Result<()> {
+        sys::udp::attach_reuseport_ebpf(&self.inner, program)
+    }
+
+    /// Sets the value of the `SO_TXTIME` option on this socket.
+    ///
+    /// This enables scheduling the transmit time of datagrams, using
+    /// [`send_at`]. `clockid` is the clock the transmit time is expressed in,
+    /// e.g. `libc::CLOCK_MONOTONIC` or `libc::CLOCK_TAI`, and `flags` are the
+    /// `SOF_TXTIME_*` flags, e.g. `libc::SOF_TXTIME_DEADLINE_MODE`. Using a
+    /// clock other than `CLOCK_MONOTONIC` requires the `CAP_NET_ADMIN`
+    /// capability.
+    ///
+    /// # Notes
+    ///
+    /// The transmit time is enforced by the queueing discipline, which means
+    /// that the `fq` or `etf` qdisc must be configured on the outgoing
+    /// interface. Without it the transmit time is ignored and datagrams are
+    /// sent immediately.
+    ///
+    /// [`send_at`]: UdpSocket::send_at
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_txtime(&self, clockid: i32, flags: u32) -> io::Result<()> {
+        sys::udp::set_txtime(&self.inner, clockid, flags)
+    }
+
+    /// Sends data on the socket to the given address, scheduled to be
+    /// transmitted at `when`. On success, returns the number of bytes written.
+    ///
+    /// This attaches a `SCM_TXTIME` control message to the datagram, converting
+    /// `when` into the clock configured with [`set_txtime`], which must be
+    /// called first. If `when` is in the past the current time is used.
+    ///
+    /// [`set_txtime`]: UdpSocket::set_txtime
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn send_at(&self, buf: &[u8], target: SocketAddr, when: Instant) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_at(inner, buf, target, when))
+    }
+
+    /// Sets the value of the `SO_TIMESTAMPNS` option on this socket.
+    ///
+    /// When enabled the kernel records a software timestamp for each received
+    /// datagram, which is returned by [`recv_mmsg`] in
+    /// [`RecvMsg::timestamp`]. Only datagrams received after the option is
+    /// enabled are timestamped.
+    ///
+    /// [`recv_mmsg`]: UdpSocket::recv_mmsg
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_recv_timestamp(&self, timestamp: bool) -> io::Result<()> {
+        sys::udp::set_recv_timestamp(&self.inner, timestamp)
+    }
+
+    /// Gets the value of the `SO_TIMESTAMPNS` option on this socket.
+    ///
+    /// For more information about this option, see [`set_recv_timestamp`].
+    ///
+    /// [`set_recv_timestamp`]: UdpSocket::set_recv_timestamp
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn recv_timestamp(&self) -> io::Result<bool> {
+        sys::udp::recv_timestamp(&self.inner)
+    }
+
+    /// Enables or disables hardware receive timestamps using the
+    /// `SO_TIMESTAMPING` option on this socket.
+    ///
+    /// When enabled the raw hardware timestamp of each received datagram is
+    /// returned by [`recv_full`] in [`RecvFull::hw_timestamp`]. This requires
+    /// hardware timestamping to be enabled on the network device, e.g. using
+    /// the `SIOCSHWTSTAMP` ioctl, which Mio doesn't do.
+    ///
+    /// Note that this overwrites all `SO_TIMESTAMPING` flags.
+    ///
+    /// [`recv_full`]: UdpSocket::recv_full
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_recv_hw_timestamp(&self, timestamp: bool) -> io::Result<()> {
+        sys::udp::set_recv_hw_timestamp(&self.inner, timestamp)
+    }
+
+    /// Returns `true` if raw hardware receive timestamps are enabled using the
+    /// `SO_TIMESTAMPING` option on this socket.
+    ///
+    /// For more information about this option, see [`set_recv_hw_timestamp`].
+    ///
+    /// [`set_recv_hw_timestamp`]: UdpSocket::set_recv_hw_timestamp
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn recv_hw_timestamp(&self) -> io::Result<bool> {
+        sys::udp::recv_hw_timestamp(&self.inner)
+    }
+
+    /// Sets the value of the `IP_PKTINFO` option on this socket, or
+    /// `IPV6_RECVPKTINFO` for IPv6 sockets.
+    ///
+    /// When enabled the destination address and interface index of each
+    /// received datagram is returned by [`recv_full`] in
+    /// [`RecvFull::dst_addr`] and [`RecvFull::interface`].
+    ///
+    /// [`recv_full`]: UdpSocket::recv_full
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_recv_pktinfo(&self, pktinfo: bool) -> io::Result<()> {
+        sys::udp::set_recv_pktinfo(&self.inner, pktinfo)
+    }
+
+    /// Gets the value of the `IP_PKTINFO` option on this socket, or
+    /// `IPV6_RECVPKTINFO` for IPv6 sockets.
+    ///
+    /// For more information about this option, see [`set_recv_pktinfo`].
+    ///
+    /// [`set_recv_pktinfo`]: UdpSocket::set_recv_pktinfo
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn recv_pktinfo(&self) -> io::Result<bool> {
+        sys::udp::recv_pktinfo(&self.inner)
+    }
+
+    /// Sets the value of the `IP_RECVTOS` option on this socket, or
+    /// `IPV6_RECVTCLASS` for IPv6 sockets.
+    ///
+    /// When enabled the Explicit Congestion Notification (ECN) bits of each
+    /// received datagram are returned by [`recv_full`] in [`RecvFull::ecn`].
+    ///
+    /// [`recv_full`]: UdpSocket::recv_full
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_recv_ecn(&self, ecn: bool) -> io::Result<()> {
+        sys::udp::set_recv_ecn(&self.inner, ecn)
+    }
+
+    /// Gets the value of the `IP_RECVTOS` option on this socket, or
+    /// `IPV6_RECVTCLASS` for IPv6 sockets.
+    ///
+    /// For more information about this option, see [`set_recv_ecn`].
+    ///
+    /// [`set_recv_ecn`]: UdpSocket::set_recv_ecn
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn recv_ecn(&self) -> io::Result<bool> {
+        sys::udp::recv_ecn(&self.inner)
+    }
+
+    /// Sets the value of the `IP_TRANSPARENT` option on this socket.
+    ///
+    /// This allows the socket to bind to non-local addresses, send from them
+    /// and, combined with a `TPROXY` firewall rule, receive datagrams destined
+    /// for arbitrary addresses, as used by transparent proxies. Use
+    /// [`set_recv_orig_dst_addr`] to learn the original destination of the
+    /// received datagrams.
+    ///
+    /// Setting this option requires the `CAP_NET_ADMIN` capability, without
+    /// it a `PermissionDenied` error is returned.
+    ///
+    /// [`set_recv_orig_dst_addr`]: UdpSocket::set_recv_orig_dst_addr
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_transparent(&self, transparent: bool) -> io::Result<()> {
+        sys::udp::set_transparent(&self.inner, transparent)
+    }
+
+    /// Gets the value of the `IP_TRANSPARENT` option on this socket.
+    ///
+    /// For more information about this option, see [`set_transparent`].
+    ///
+    /// [`set_transparent`]: UdpSocket::set_transparent
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn transparent(&self) -> io::Result<bool> {
+        sys::udp::transparent(&self.inner)
+    }
+
+    /// Sets the value of the `IP_RECVORIGDSTADDR` option on this socket, or
+    /// `IPV6_RECVORIGDSTADDR` for IPv6 sockets.
+    ///
+    /// When enabled the original destination address and port of each
+    /// received datagram is returned by [`recv_full`] in
+    /// [`RecvFull::orig_dst_addr`]. For datagrams redirected by a `TPROXY`
+    /// firewall rule this differs from the local address of the socket.
+    ///
+    /// [`recv_full`]: UdpSocket::recv_full
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_recv_orig_dst_addr(&self, orig_dst_addr: bool) -> io::Result<()> {
+        sys::udp::set_recv_orig_dst_addr(&self.inner, orig_dst_addr)
+    }
+
+    /// Gets the value of the `IP_RECVORIGDSTADDR` option on this socket, or
+    /// `IPV6_RECVORIGDSTADDR` for IPv6 sockets.
+    ///
+    /// For more information about this option, see
+    /// [`set_recv_orig_dst_addr`].
+    ///
+    /// [`set_recv_orig_dst_addr`]: UdpSocket::set_recv_orig_dst_addr
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn recv_orig_dst_addr(&self) -> io::Result<bool> {
+        sys::udp::recv_orig_dst_addr(&self.inner)
+    }
+
+    /// Receives a single datagram, including all enabled ancillary data, using
+    /// a single `recvmsg(2)` system call.
+    ///
+    /// Which fields of the returned [`RecvFull`] are set depends on the enabled
+    /// options:
+    ///  * [`set_recv_pktinfo`]: destination address and interface index.
+    ///  * [`set_recv_ecn`]: ECN bits.
+    ///  * [`set_recv_timestamp`]: software receive timestamp.
+    ///  * [`set_recv_hw_timestamp`]: hardware receive timestamp.
+    ///  * [`set_recv_orig_dst_addr`]: original destination address.
+    ///
+    /// [`set_recv_hw_timestamp`]: UdpSocket::set_recv_hw_timestamp
+    /// [`set_recv_orig_dst_addr`]: UdpSocket::set_recv_orig_dst_addr
+    /// [`set_recv_pktinfo`]: UdpSocket::set_recv_pktinfo
+    /// [`set_recv_ecn`]: UdpSocket::set_recv_ecn
+    /// [`set_recv_timestamp`]: UdpSocket::set_recv_timestamp
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn recv_full(&self, buf: &mut [u8]) -> io::Result<RecvFull> {
+        self.inner.do_io(|inner| sys::udp::recv_full(inner, buf))
+    }
+
+    /// Receives multiple datagrams using a single `recvmmsg(2)` system call.
+    ///
+    /// Each buffer in `bufs` receives at most one datagram, the returned
+    /// messages are in the same order as `bufs`, i.e. the first message is
+    /// read into `bufs[0]`. Fewer messages than buffers are returned if no
+    /// more datagrams are queued. If no datagrams are queued at all this
+    /// returns an error of the kind `WouldBlock`.
+    ///
+    /// If timestamping is enabled using [`set_recv_timestamp`] (or the
+    /// `SO_TIMESTAMP` or `SO_TIMESTAMPING` options are set directly) the
+    /// software receive timestamp of each datagram is returned as well.
+    ///
+    /// [`set_recv_timestamp`]: UdpSocket::set_recv_timestamp
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn recv_mmsg(&self, bufs: &mut [&mut [u8]]) -> io::Result<Vec<RecvMsg>> {
+        self.inner.do_io(|inner| sys::udp::recv_mmsg(inner, bufs))
+    }
+
+    /// Sends multiple messages, using segmentation offload, with a single
+    /// `sendmmsg(2)` system call. On success, returns the number of messages
+    /// sent.
+    ///
+    /// Each message is a tuple of `(buf, segment_size, target)`. The buffer
+    /// of each message is split by the kernel (or the network card) into
+    /// datagrams of `segment_size` bytes, using the `UDP_SEGMENT` control
+    /// message, all of which are sent to `target`. The last datagram may be
+    /// smaller. This is known as generic segmentation offload (GSO) and
+    /// greatly reduces the per datagram overhead of sending.
+    ///
+    /// Note that the kernel limits the number of datagrams a single buffer can
+    /// be split into (`UDP_MAX_SEGMENTS`), and that segmentation offload is
+    /// not supported by all kernels. In both cases an error is returned.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn send_batch_segmented(&self, msgs: &[(&[u8], u16, SocketAddr)]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_batch_segmented(inner, msgs))
+    }
+
     /// Executes an operation of the `IP_ADD_MEMBERSHIP` type.
     ///
     /// This function specifies a new multicast group for this socket to join.
@@ -513,6 +1145,12 @@ impl UdpSocket {
     /// This function specifies a new multicast group for this socket to join.
     /// The address must be a valid multicast address, and `interface` is the
     /// index of the interface to join/leave (or 0 to indicate any interface).
+    ///
+    /// The interface index is the same as the scope zone of link-local
+    /// addresses, e.g. the `2` in `fe80::1%2`. On Unix it can be looked up by
+    /// the interface's name using [`interface_index_by_name`].
+    ///
+    /// [`interface_index_by_name`]: crate::net::interface_index_by_name
     #[allow(clippy::trivially_copy_pass_by_ref)]
     pub fn join_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
         self.inner.join_multicast_v6(multiaddr, interface)
@@ -540,6 +1178,33 @@ impl UdpSocket {
         self.inner.leave_multicast_v6(multiaddr, interface)
     }
 
+    /// Joins the IPv6 multicast `group` on the interface with index
+    /// `interface`, using `IPV6_ADD_MEMBERSHIP`.
+    ///
+    /// `interface` is the scope zone of the interface, e.g. the `2` in
+    /// `fe80::1%2`, **not** an address. Zero lets the system choose the
+    /// interface. On Unix the index can be looked up by the interface's name
+    /// using [`interface_index_by_name`].
+    ///
+    /// This is the same as [`join_multicast_v6`], but makes the meaning of the
+    /// interface argument explicit.
+    ///
+    /// [`interface_index_by_name`]: crate::net::interface_index_by_name
+    /// [`join_multicast_v6`]: UdpSocket::join_multicast_v6
+    pub fn join_multicast_v6_if(&self, group: Ipv6Addr, interface: u32) -> io::Result<()> {
+        self.inner.join_multicast_v6(&group, interface)
+    }
+
+    /// Leaves the IPv6 multicast `group` on the interface with index
+    /// `interface`, using `IPV6_DROP_MEMBERSHIP`.
+    ///
+    /// For more information see [`join_multicast_v6_if`].
+    ///
+    /// [`join_multicast_v6_if`]: UdpSocket::join_multicast_v6_if
+    pub fn leave_multicast_v6_if(&self, group: Ipv6Addr, interface: u32) -> io::Result<()> {
+        self.inner.leave_multicast_v6(&group, interface)
+    }
+
     /// Get the value of the `IPV6_V6ONLY` option on this socket.
     #[allow(clippy::trivially_copy_pass_by_ref)]
     pub fn only_v6(&self) -> io::Result<bool> {
@@ -550,11 +1215,61 @@ impl UdpSocket {
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
     /// the field in the process. This can be useful for checking errors between
-    /// calls.
+    /// calls. A following call returns `Ok(None)`, until another error occurs.
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
 
+    /// Returns the number of bytes available to read from this socket, using
+    /// the `FIONREAD` ioctl.
+    ///
+    /// On Linux this is the size of the next datagram, which can be used to
+    /// allocate a buffer of the exact size before receiving it. On other
+    /// platforms, e.g. macOS and Windows, it's the total number of bytes of all
+    /// queued datagrams.
+    #[cfg(any(unix, windows))]
+    #[cfg_attr(docsrs, doc(cfg(any(unix, windows))))]
+    pub fn available(&self) -> io::Result<usize> {
+        sys::udp::available(&self.inner)
+    }
+
+    /// Moves this socket into or out of non-blocking mode.
+    ///
+    /// Mio puts all sockets it creates in non-blocking mode and depends on it, a
+    /// blocking call would block the thread polling for events. This allows a
+    /// socket to be used in blocking mode temporarily, e.g. to perform a
+    /// synchronous handshake on a separate thread.
+    ///
+    /// # Notes
+    ///
+    /// While in blocking mode the socket must not be registered with a
+    /// [`Registry`]. Deregister it before switching to blocking mode and only
+    /// register it again after switching back to non-blocking mode.
+    ///
+    /// On Linux [`RecvFlags::DONT_WAIT`] and [`SendFlags::DONT_WAIT`] can be
+    /// used to receive or send without blocking while in blocking mode.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.inner.set_nonblocking(nonblocking)
+    }
+
+    /// Sets or clears the `FD_CLOEXEC` flag on this socket.
+    ///
+    /// Mio sets `FD_CLOEXEC` on all sockets it creates, meaning the socket is
+    /// closed when the process calls `exec`. Clearing the flag allows a child
+    /// process to inherit the socket.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn set_cloexec(&self, cloexec: bool) -> io::Result<()> {
+        sys::set_cloexec(self.as_raw_fd(), cloexec)
+    }
+
+    /// Returns `true` if the `FD_CLOEXEC` flag is set on this socket.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn is_cloexec(&self) -> io::Result<bool> {
+        sys::is_cloexec(self.as_raw_fd())
+    }
+
     /// Execute an I/O operation ensuring that the socket receives more events
     /// if it hits a [`WouldBlock`] error.
     ///
@@ -692,6 +1407,13 @@ impl AsRawSocket for UdpSocket {
     }
 }
 
+#[cfg(windows)]
+impl AsSocket for UdpSocket {
+    fn as_socket(&self) -> BorrowedSocket<'_> {
+        self.inner.as_socket()
+    }
+}
+
 #[cfg(windows)]
 impl FromRawSocket for UdpSocket {
     /// Converts a `RawSocket` to a `UdpSocket`.
@@ -722,3 +1444,30 @@ impl From<UdpSocket> for net::UdpSocket {
         }
     }
 }
+
+/// Returns the index of the network interface named `name`, e.g. `"eth0"`.
+///
+/// This wraps `if_nametoindex(3)`. The index can be passed to
+/// [`UdpSocket::join_multicast_v6_if`] and
+/// [`UdpSocket::leave_multicast_v6_if`].
+///
+/// # Examples
+///
+#[cfg_attr(all(feature = "os-poll", target_os = "linux"), doc = "```")]
+#[cfg_attr(not(all(feature = "os-poll", target_os = "linux")), doc = "```ignore")]
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::{interface_index_by_name, UdpSocket};
+///
+/// let socket = UdpSocket::bind("[::]:0".parse()?)?;
+/// let loopback = interface_index_by_name("lo")?;
+/// socket.join_multicast_v6_if("ff02::1234".parse()?, loopback)?;
+/// #    Ok(())
+/// # }
+/// ```
+#[cfg(unix)]
+#[cfg_attr(docsrs, doc(cfg(unix)))]
+pub fn interface_index_by_name(name: &str) -> io::Result<u32> {
+    sys::udp::interface_index_by_name(name)
+}
@@ -3,8 +3,12 @@ use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, RawFd};
 use std::os::unix::net::{self, SocketAddr};
 use std::path::Path;
 use std::{fmt, io};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::io::{IoSlice, IoSliceMut};
 
 use crate::io_source::IoSource;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::UCred;
 use crate::{event, sys, Interest, Registry, Token};
 
 /// A Unix datagram socket.
@@ -36,6 +40,16 @@ impl UnixDatagram {
         }
     }
 
+    /// Creates a new `UnixDatagram` from a standard `net::UnixDatagram`,
+    /// setting it in non-blocking mode.
+    ///
+    /// This is the same as [`UnixDatagram::from_std`], but doesn't require the
+    /// caller to call `set_nonblocking(true)` first.
+    pub fn from_std_nonblocking(socket: net::UnixDatagram) -> io::Result<UnixDatagram> {
+        socket.set_nonblocking(true)?;
+        Ok(UnixDatagram::from_std(socket))
+    }
+
     /// Connects the socket to the specified address.
     ///
     /// This may return a `WouldBlock` in which case the socket connection
@@ -86,6 +100,26 @@ impl UnixDatagram {
         self.inner.do_io(|inner| inner.recv(buf))
     }
 
+    /// Receives data from the socket, without removing it from the input
+    /// queue.
+    ///
+    /// On success, returns the number of bytes read and the address from
+    /// whence the data came. The same datagram is returned by the next call to
+    /// `recv_from`, `recv` or one of the peek methods.
+    pub fn peek_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::peek_from(inner, buf))
+    }
+
+    /// Receives data from the socket, without removing it from the input
+    /// queue.
+    ///
+    /// On success, returns the number of bytes read.
+    pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::peek(inner, buf))
+    }
+
     /// Sends data on the socket to the specified address.
     ///
     /// On success, returns the number of bytes written.
@@ -103,11 +137,45 @@ impl UnixDatagram {
         self.inner.do_io(|inner| inner.send(buf))
     }
 
-    /// Returns the value of the `SO_ERROR` option.
+    /// Get the value of the `SO_ERROR` option on this socket.
+    ///
+    /// This will retrieve the stored error in the underlying socket, clearing
+    /// the field in the process. A following call returns `Ok(None)`, until
+    /// another error occurs.
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
 
+    /// Moves this socket into or out of non-blocking mode.
+    ///
+    /// Mio puts all sockets it creates in non-blocking mode and depends on it, a
+    /// blocking call would block the thread polling for events. This allows a
+    /// socket to be used in blocking mode temporarily, e.g. to perform a
+    /// synchronous handshake on a separate thread.
+    ///
+    /// # Notes
+    ///
+    /// While in blocking mode the socket must not be registered with a
+    /// [`Registry`]. Deregister it before switching to blocking mode and only
+    /// register it again after switching back to non-blocking mode.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.inner.set_nonblocking(nonblocking)
+    }
+
+    /// Sets or clears the `FD_CLOEXEC` flag on this socket.
+    ///
+    /// Mio sets `FD_CLOEXEC` on all sockets it creates, meaning the socket is
+    /// closed when the process calls `exec`. Clearing the flag allows a child
+    /// process to inherit the socket.
+    pub fn set_cloexec(&self, cloexec: bool) -> io::Result<()> {
+        sys::set_cloexec(self.as_raw_fd(), cloexec)
+    }
+
+    /// Returns `true` if the `FD_CLOEXEC` flag is set on this socket.
+    pub fn is_cloexec(&self) -> io::Result<bool> {
+        sys::is_cloexec(self.as_raw_fd())
+    }
+
     /// Shut down the read, write, or both halves of this connection.
     ///
     /// This function will cause all pending and future I/O calls on the
@@ -117,6 +185,64 @@ impl UnixDatagram {
         self.inner.shutdown(how)
     }
 
+    /// Sets the value of the `SO_PASSCRED` option on this socket.
+    ///
+    /// When enabled the credentials of the sender are received with the data,
+    /// see [`recv_with_creds`].
+    ///
+    /// [`recv_with_creds`]: UnixDatagram::recv_with_creds
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn set_passcred(&self, passcred: bool) -> io::Result<()> {
+        sys::uds::set_passcred(&*self.inner, passcred)
+    }
+
+    /// Gets the value of the `SO_PASSCRED` option on this socket.
+    ///
+    /// For more information about this option, see [`set_passcred`].
+    ///
+    /// [`set_passcred`]: UnixDatagram::set_passcred
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn passcred(&self) -> io::Result<bool> {
+        sys::uds::passcred(&*self.inner)
+    }
+
+    /// Sends data from `bufs` to the connected peer, attaching `creds` in an
+    /// `SCM_CREDENTIALS` control message.
+    ///
+    /// The kernel validates the credentials: a process can only send its own
+    /// process id, user id and group id (see [`UCred::current`]), unless it
+    /// has the `CAP_SYS_ADMIN`, `CAP_SETUID` or `CAP_SETGID` capability
+    /// respectively. Invalid credentials result in a `PermissionDenied`
+    /// error. The peer receives the credentials if it enabled
+    /// [`set_passcred`].
+    ///
+    /// [`UCred::current`]: crate::net::UCred::current
+    /// [`set_passcred`]: UnixDatagram::set_passcred
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn send_with_creds(&self, bufs: &[IoSlice<'_>], creds: &UCred) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::send_with_creds(inner, bufs, creds))
+    }
+
+    /// Receives data into `bufs`, returning the number of bytes read and the
+    /// credentials of the sender, if any.
+    ///
+    /// Credentials are only received if enabled using [`set_passcred`].
+    ///
+    /// [`set_passcred`]: UnixDatagram::set_passcred
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn recv_with_creds(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, Option<UCred>)> {
+        self.inner
+            .do_io(|inner| sys::uds::recv_with_creds(inner, bufs))
+    }
+
     /// Execute an I/O operation ensuring that the socket receives more events
     /// if it hits a [`WouldBlock`] error.
     ///
@@ -36,23 +36,107 @@ impl UnixListener {
         }
     }
 
+    /// Creates a new `UnixListener` from a standard `net::UnixListener`,
+    /// setting it in non-blocking mode.
+    ///
+    /// This is the same as [`UnixListener::from_std`], but doesn't require the
+    /// caller to call `set_nonblocking(true)` first.
+    pub fn from_std_nonblocking(listener: net::UnixListener) -> io::Result<UnixListener> {
+        listener.set_nonblocking(true)?;
+        Ok(UnixListener::from_std(listener))
+    }
+
     /// Accepts a new incoming connection to this listener.
     ///
     /// The call is responsible for ensuring that the listening socket is in
     /// non-blocking mode.
+    ///
+    /// The returned address is the address the peer's socket is bound to,
+    /// which is unnamed if the peer didn't bind its socket. On Android and
+    /// Linux this includes abstract names, on other platforms peers bound to
+    /// an abstract name are not supported.
     pub fn accept(&self) -> io::Result<(UnixStream, SocketAddr)> {
         sys::uds::listener::accept(&self.inner)
     }
 
+    /// Returns an iterator over the connections pending on this listener.
+    ///
+    /// The iterator calls [`accept`] for each item and returns `None` once
+    /// accepting would block, rather than blocking like the standard library's
+    /// version. This makes it suitable to accept all pending connections after
+    /// receiving a readable event. The returned streams are in non-blocking
+    /// mode, like all streams returned by [`accept`].
+    ///
+    /// Note that after returning `None` the iterator may return new
+    /// connections again, it's not fused.
+    ///
+    /// [`accept`]: UnixListener::accept
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UnixListener;
+    ///
+    /// let listener = UnixListener::bind("/tmp/mio.sock")?;
+    ///
+    /// // After receiving a readable event for the listener.
+    /// for stream in listener.incoming() {
+    ///     let stream = stream?;
+    ///     // Handle the connection...
+    /// #   drop(stream);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn incoming(&self) -> UnixIncoming<'_> {
+        UnixIncoming { listener: self }
+    }
+
     /// Returns the local socket address of this listener.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         self.inner.local_addr()
     }
 
-    /// Returns the value of the `SO_ERROR` option.
+    /// Get the value of the `SO_ERROR` option on this socket.
+    ///
+    /// This will retrieve the stored error in the underlying socket, clearing
+    /// the field in the process. A following call returns `Ok(None)`, until
+    /// another error occurs.
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
+
+    /// Moves this listener into or out of non-blocking mode.
+    ///
+    /// Mio puts all listeners it creates in non-blocking mode and depends on it, a
+    /// blocking call would block the thread polling for events. This allows a
+    /// listener to be used in blocking mode temporarily, e.g. to perform a
+    /// synchronous handshake on a separate thread.
+    ///
+    /// # Notes
+    ///
+    /// While in blocking mode the listener must not be registered with a
+    /// [`Registry`]. Deregister it before switching to blocking mode and only
+    /// register it again after switching back to non-blocking mode.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.inner.set_nonblocking(nonblocking)
+    }
+
+    /// Sets or clears the `FD_CLOEXEC` flag on this listener.
+    ///
+    /// Mio sets `FD_CLOEXEC` on all listeners it creates, meaning the listener is
+    /// closed when the process calls `exec`. Clearing the flag allows a child
+    /// process to inherit the listener.
+    pub fn set_cloexec(&self, cloexec: bool) -> io::Result<()> {
+        sys::set_cloexec(self.as_raw_fd(), cloexec)
+    }
+
+    /// Returns `true` if the `FD_CLOEXEC` flag is set on this listener.
+    pub fn is_cloexec(&self) -> io::Result<bool> {
+        sys::is_cloexec(self.as_raw_fd())
+    }
 }
 
 impl event::Source for UnixListener {
@@ -123,3 +207,24 @@ impl AsFd for UnixListener {
         self.inner.as_fd()
     }
 }
+
+/// An iterator over the connections pending on a [`UnixListener`].
+///
+/// This struct is created by [`UnixListener::incoming`], see its documentation
+/// for more.
+#[derive(Debug)]
+pub struct UnixIncoming<'a> {
+    listener: &'a UnixListener,
+}
+
+impl Iterator for UnixIncoming<'_> {
+    type Item = io::Result<UnixStream>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.listener.accept() {
+            Ok((stream, _)) => Some(Ok(stream)),
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
@@ -2,7 +2,12 @@ mod datagram;
 pub use self::datagram::UnixDatagram;
 
 mod listener;
-pub use self::listener::UnixListener;
+pub use self::listener::{UnixIncoming, UnixListener};
 
 mod stream;
 pub use self::stream::UnixStream;
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod ucred;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use self::ucred::UCred;
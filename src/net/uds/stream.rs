@@ -6,6 +6,8 @@ use std::os::unix::net::{self, SocketAddr};
 use std::path::Path;
 
 use crate::io_source::IoSource;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::UCred;
 use crate::{event, sys, Interest, Registry, Token};
 
 /// A non-blocking Unix stream socket.
@@ -49,6 +51,16 @@ impl UnixStream {
         }
     }
 
+    /// Creates a new `UnixStream` from a standard `net::UnixStream`, setting it
+    /// in non-blocking mode.
+    ///
+    /// This is the same as [`UnixStream::from_std`], but doesn't require the
+    /// caller to call `set_nonblocking(true)` first.
+    pub fn from_std_nonblocking(stream: net::UnixStream) -> io::Result<UnixStream> {
+        stream.set_nonblocking(true)?;
+        Ok(UnixStream::from_std(stream))
+    }
+
     /// Creates an unnamed pair of connected sockets.
     ///
     /// Returns two `UnixStream`s which are connected to each other.
@@ -68,11 +80,59 @@ impl UnixStream {
         self.inner.peer_addr()
     }
 
-    /// Returns the value of the `SO_ERROR` option.
+    /// Creates a new independently owned handle to the underlying socket.
+    ///
+    /// The returned `UnixStream` refers to the same connection as `self`, but
+    /// is a separate [`event::Source`] with its own registration, allowing for
+    /// example separate read and write handles.
+    ///
+    /// # Notes
+    ///
+    /// The clone is not registered, even if `self` is. Each handle must be
+    /// registered, and deregistered, independently.
+    pub fn try_clone(&self) -> io::Result<UnixStream> {
+        self.inner.try_clone().map(UnixStream::from_std)
+    }
+
+    /// Get the value of the `SO_ERROR` option on this socket.
+    ///
+    /// This will retrieve the stored error in the underlying socket, clearing
+    /// the field in the process. A following call returns `Ok(None)`, until
+    /// another error occurs.
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
 
+    /// Moves this stream into or out of non-blocking mode.
+    ///
+    /// Mio puts all streams it creates in non-blocking mode and depends on it, a
+    /// blocking call would block the thread polling for events. This allows a
+    /// stream to be used in blocking mode temporarily, e.g. to perform a
+    /// synchronous handshake on a separate thread.
+    ///
+    /// # Notes
+    ///
+    /// While in blocking mode the stream must not be registered with a
+    /// [`Registry`]. Deregister it before switching to blocking mode and only
+    /// register it again after switching back to non-blocking mode.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.inner.set_nonblocking(nonblocking)
+    }
+
+    /// Sets or clears the `FD_CLOEXEC` flag on this stream.
+    ///
+    /// Mio sets `FD_CLOEXEC` on all streams it creates, meaning the stream is
+    /// closed when the process calls `exec`. Clearing the flag allows a child
+    /// process to inherit the stream.
+    pub fn set_cloexec(&self, cloexec: bool) -> io::Result<()> {
+        sys::set_cloexec(self.as_raw_fd(), cloexec)
+    }
+
+    /// Returns `true` if the `FD_CLOEXEC` flag is set on this stream.
+    pub fn is_cloexec(&self) -> io::Result<bool> {
+        sys::is_cloexec(self.as_raw_fd())
+    }
+
     /// Shuts down the read, write, or both halves of this connection.
     ///
     /// This function will cause all pending and future I/O calls on the
@@ -82,6 +142,64 @@ impl UnixStream {
         self.inner.shutdown(how)
     }
 
+    /// Sets the value of the `SO_PASSCRED` option on this socket.
+    ///
+    /// When enabled the credentials of the sender are received with the data,
+    /// see [`recv_with_creds`].
+    ///
+    /// [`recv_with_creds`]: UnixStream::recv_with_creds
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn set_passcred(&self, passcred: bool) -> io::Result<()> {
+        sys::uds::set_passcred(&*self.inner, passcred)
+    }
+
+    /// Gets the value of the `SO_PASSCRED` option on this socket.
+    ///
+    /// For more information about this option, see [`set_passcred`].
+    ///
+    /// [`set_passcred`]: UnixStream::set_passcred
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn passcred(&self) -> io::Result<bool> {
+        sys::uds::passcred(&*self.inner)
+    }
+
+    /// Sends data from `bufs` to the peer, attaching `creds` in an
+    /// `SCM_CREDENTIALS` control message.
+    ///
+    /// The kernel validates the credentials: a process can only send its own
+    /// process id, user id and group id (see [`UCred::current`]), unless it
+    /// has the `CAP_SYS_ADMIN`, `CAP_SETUID` or `CAP_SETGID` capability
+    /// respectively. Invalid credentials result in a `PermissionDenied`
+    /// error. The peer receives the credentials if it enabled
+    /// [`set_passcred`].
+    ///
+    /// [`UCred::current`]: crate::net::UCred::current
+    /// [`set_passcred`]: UnixStream::set_passcred
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn send_with_creds(&self, bufs: &[IoSlice<'_>], creds: &UCred) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::send_with_creds(inner, bufs, creds))
+    }
+
+    /// Receives data into `bufs`, returning the number of bytes read and the
+    /// credentials of the sender, if any.
+    ///
+    /// Credentials are only received if enabled using [`set_passcred`].
+    ///
+    /// [`set_passcred`]: UnixStream::set_passcred
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn recv_with_creds(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, Option<UCred>)> {
+        self.inner
+            .do_io(|inner| sys::uds::recv_with_creds(inner, bufs))
+    }
+
     /// Execute an I/O operation ensuring that the socket receives more events
     /// if it hits a [`WouldBlock`] error.
     ///
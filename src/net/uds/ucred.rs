@@ -0,0 +1,25 @@
+use crate::sys;
+
+/// Credentials of a process, as passed in `SCM_CREDENTIALS` control messages.
+///
+/// See [`UnixStream::send_with_creds`] and [`UnixStream::recv_with_creds`].
+///
+/// [`UnixStream::send_with_creds`]: crate::net::UnixStream::send_with_creds
+/// [`UnixStream::recv_with_creds`]: crate::net::UnixStream::recv_with_creds
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UCred {
+    /// Process id.
+    pub pid: i32,
+    /// User id.
+    pub uid: u32,
+    /// Group id.
+    pub gid: u32,
+}
+
+impl UCred {
+    /// Returns the credentials of the current process, i.e. its process id
+    /// and real user and group ids.
+    pub fn current() -> UCred {
+        sys::uds::current_creds()
+    }
+}
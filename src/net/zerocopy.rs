@@ -0,0 +1,55 @@
+/// Completion notification of one or more `MSG_ZEROCOPY` sends.
+///
+/// Returned by [`TcpStream::poll_zerocopy_completions`], see
+/// [`TcpStream::send_zerocopy`] for more information.
+///
+/// A single notification can cover a range of sends, from [`first`] up to and
+/// including [`last`]. Note that sequence ids wrap around, so `last` can be
+/// smaller than `first`; use [`contains`] to check if a send completed.
+///
+/// [`TcpStream::poll_zerocopy_completions`]: crate::net::TcpStream::poll_zerocopy_completions
+/// [`TcpStream::send_zerocopy`]: crate::net::TcpStream::send_zerocopy
+/// [`first`]: ZcCompletion::first
+/// [`last`]: ZcCompletion::last
+/// [`contains`]: ZcCompletion::contains
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ZcCompletion {
+    first: u32,
+    last: u32,
+    copied: bool,
+}
+
+impl ZcCompletion {
+    #[cfg(feature = "os-poll")]
+    pub(crate) const fn new(first: u32, last: u32, copied: bool) -> ZcCompletion {
+        ZcCompletion {
+            first,
+            last,
+            copied,
+        }
+    }
+
+    /// Sequence id of the first completed send.
+    pub const fn first(&self) -> u32 {
+        self.first
+    }
+
+    /// Sequence id of the last completed send, inclusive.
+    pub const fn last(&self) -> u32 {
+        self.last
+    }
+
+    /// Returns true if the send with sequence id `id` is covered by this
+    /// completion.
+    pub const fn contains(&self, id: u32) -> bool {
+        id.wrapping_sub(self.first) <= self.last.wrapping_sub(self.first)
+    }
+
+    /// Returns true if the kernel fell back to copying the data.
+    ///
+    /// This is always the case for loopback connections for example. If this
+    /// happens often it might be more efficient to not use zerocopy at all.
+    pub const fn copied(&self) -> bool {
+        self.copied
+    }
+}
@@ -1,7 +1,10 @@
+#[cfg(any(unix, windows))]
+use std::num::NonZeroUsize;
 #[cfg(all(
     unix,
     not(mio_unsupported_force_poll_poll),
     not(any(
+        target_os = "aix",
         target_os = "espidf",
         target_os = "haiku",
         target_os = "hermit",
@@ -13,8 +16,8 @@
 use std::os::fd::{AsRawFd, RawFd};
 #[cfg(all(debug_assertions, not(target_os = "wasi")))]
 use std::sync::atomic::{AtomicBool, Ordering};
-#[cfg(all(debug_assertions, not(target_os = "wasi")))]
-use std::sync::Arc;
+#[cfg(not(target_os = "wasi"))]
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 use std::{fmt, io};
 
@@ -241,6 +244,14 @@ use crate::{event, sys, Events, Interest, Token};
 /// | Windows       | [IOCP]    |
 /// | macOS         | [kqueue]  |
 ///
+/// Other Unix platforms without epoll or kqueue, such as AIX, ESP-IDF, Haiku,
+/// Hermit, QNX Neutrino, Solaris and Vita, use [poll(2)]. Its list of file
+/// descriptors grows as sources are registered, so unlike `select(2)`'s
+/// `FD_SETSIZE` there is no limit on the number of sources or the value of
+/// their file descriptors. However, the kernel scans all registered file
+/// descriptors on each call to [`Poll::poll`], so polling is O(n) in the
+/// number of registered sources.
+///
 /// On all supported platforms, socket operations are handled by using the
 /// system selector. Platform specific extensions (e.g. [`SourceFd`]) allow
 /// accessing other features provided by individual system selectors. For
@@ -258,11 +269,25 @@ use crate::{event, sys, Events, Interest, Token};
 /// [epoll]: https://man7.org/linux/man-pages/man7/epoll.7.html
 /// [kqueue]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
 /// [IOCP]: https://docs.microsoft.com/en-us/windows/win32/fileio/i-o-completion-ports
+/// [poll(2)]: https://man7.org/linux/man-pages/man2/poll.2.html
 /// [`signalfd`]: https://man7.org/linux/man-pages/man2/signalfd.2.html
 /// [`SourceFd`]: unix/struct.SourceFd.html
 /// [`Poll::poll`]: struct.Poll.html#method.poll
 pub struct Poll {
     registry: Registry,
+    /// Set by `Poll::set_fair`.
+    fair: bool,
+    /// Number of events to rotate the next returned events by, if `fair`.
+    fair_offset: usize,
+    /// Set by `Poll::set_max_events_per_poll`.
+    #[cfg(any(unix, windows))]
+    max_events: Option<NonZeroUsize>,
+    /// Wake source shared by the `Waker`s returned by `Poll::shutdown_waker`.
+    #[cfg(not(target_os = "wasi"))]
+    shutdown: OnceLock<Arc<sys::Waker>>,
+    /// Selector set by `Poll::with_selector`.
+    #[cfg(feature = "test-util")]
+    selector: Option<Box<dyn crate::test::Selector>>,
 }
 
 /// Registers I/O resources.
@@ -316,22 +341,223 @@ impl Poll {
         /// # }
         /// ```
         pub fn new() -> io::Result<Poll> {
-            sys::Selector::new().map(|selector| Poll {
+            Poll::new_with_capacity(0)
+        }
+
+        /// Return a new `Poll` handle, pre-allocating the selector's internal
+        /// state for `capacity` registered `event::Source`s.
+        ///
+        /// `capacity` is only a hint, more sources can still be registered.
+        /// This is mostly useful on Windows and when the `poll(2)` based
+        /// selector is used, which keep per source state, to avoid
+        /// reallocating while registering sources. epoll and kqueue don't
+        /// need to know the number of sources up front and ignore it.
+        ///
+        /// [`Poll::new`] uses a capacity of zero, allocating as sources are
+        /// registered.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use std::error::Error;
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// use mio::Poll;
+        ///
+        /// let poll = Poll::new_with_capacity(1024)?;
+        /// # drop(poll);
+        /// #     Ok(())
+        /// # }
+        /// ```
+        pub fn new_with_capacity(capacity: usize) -> io::Result<Poll> {
+            sys::Selector::new(capacity).map(|selector| Poll {
                 registry: Registry {
                     selector,
                     #[cfg(all(debug_assertions, not(target_os = "wasi")))]
                     has_waker: Arc::new(AtomicBool::new(false)),
                 },
+                fair: false,
+                fair_offset: 0,
+                #[cfg(any(unix, windows))]
+                max_events: None,
+                #[cfg(not(target_os = "wasi"))]
+                shutdown: OnceLock::new(),
+                #[cfg(feature = "test-util")]
+                selector: None,
             })
         }
     }
 
+    /// Return a new `Poll` handle that gets its events from `selector`,
+    /// rather than from the OS.
+    ///
+    /// This allows testing code that uses `Poll` without relying on the OS,
+    /// see the [`test`] module.
+    ///
+    /// `event::Source`s can still be registered with the returned `Poll`,
+    /// but their readiness events are not returned by [`Poll::poll`].
+    ///
+    /// [`test`]: crate::test
+    #[cfg(feature = "test-util")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+    pub fn with_selector(selector: Box<dyn crate::test::Selector>) -> io::Result<Poll> {
+        Poll::new().map(|poll| Poll {
+            selector: Some(selector),
+            ..poll
+        })
+    }
+
     /// Create a separate `Registry` which can be used to register
     /// `event::Source`s.
     pub fn registry(&self) -> &Registry {
         &self.registry
     }
 
+    /// Create a [`Waker`] used to signal the event loop to shut down.
+    ///
+    /// Waking the returned `Waker` causes the current, or next, call to
+    /// [`poll`] to return an event for which [`Event::is_shutdown`] returns
+    /// true, i.e. an event with [`Token::SHUTDOWN`]. This allows another
+    /// thread to cleanly stop an event loop blocked in [`poll`], without
+    /// having to track whether a wake up means shutdown or new work.
+    ///
+    /// The returned `Waker` doesn't count towards the single [`Waker`] that can
+    /// be active per `Poll` instance, so it can be used next to a `Waker`
+    /// created using [`Waker::new`]. All `Waker`s returned by this method
+    /// share the same wake source, which is owned by the `Poll` instance.
+    /// This means the event is delivered even if the returned `Waker` is
+    /// dropped directly after waking it.
+    ///
+    /// [`Waker`]: crate::Waker
+    /// [`Waker::new`]: crate::Waker::new
+    /// [`poll`]: Poll::poll
+    /// [`Event::is_shutdown`]: crate::event::Event::is_shutdown
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::thread;
+    ///
+    /// use mio::{Events, Poll};
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(8);
+    ///
+    /// let shutdown = poll.shutdown_waker()?;
+    /// let handle = thread::spawn(move || shutdown.wake());
+    ///
+    /// 'event_loop: loop {
+    ///     poll.poll(&mut events, None)?;
+    ///     for event in events.iter() {
+    ///         if event.is_shutdown() {
+    ///             break 'event_loop;
+    ///         }
+    ///         // Handle other events...
+    ///     }
+    /// }
+    /// # handle.join().unwrap()?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(target_os = "wasi"))]
+    pub fn shutdown_waker(&self) -> io::Result<crate::Waker> {
+        let inner = match self.shutdown.get() {
+            Some(inner) => inner,
+            None => {
+                let inner = sys::Waker::new(self.registry.selector(), Token::SHUTDOWN)?;
+                self.shutdown.get_or_init(|| Arc::new(inner))
+            }
+        };
+        Ok(crate::Waker::shared(Arc::clone(inner)))
+    }
+
+    /// Enable or disable fair event ordering.
+    ///
+    /// By default events are returned in the order determined by the OS,
+    /// which may return the same event sources first on every call to
+    /// [`poll`]. When processing events in order this may starve the event
+    /// sources that are consistently returned last.
+    ///
+    /// If enabled, [`poll`] rotates the returned events by a different offset
+    /// on each call, so that no event source is consistently returned first
+    /// or last. The events returned by a single call remain the same, only
+    /// their order changes. Disabled by default.
+    ///
+    /// Note that this does not affect [`poll_array`].
+    ///
+    /// [`poll`]: Poll::poll
+    /// [`poll_array`]: Poll::poll_array
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::Poll;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// poll.set_fair(true);
+    /// assert!(poll.is_fair());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn set_fair(&mut self, fair: bool) {
+        self.fair = fair;
+    }
+
+    /// Returns `true` if fair event ordering is enabled.
+    ///
+    /// See [`Poll::set_fair`].
+    pub fn is_fair(&self) -> bool {
+        self.fair
+    }
+
+    /// Limit the number of events retrieved by a single call to [`poll`].
+    ///
+    /// By default [`poll`] retrieves as many events as fit in the supplied
+    /// [`Events`], up to its capacity. Setting a limit caps the number of
+    /// events requested from the OS, e.g. `epoll_wait(2)` or
+    /// `GetQueuedCompletionStatusEx` on Windows, regardless of the capacity of
+    /// `Events`. This can be used to bound the amount of work done per
+    /// iteration of an event loop, the remaining events are returned by the
+    /// following calls to [`poll`]. `None` removes the limit.
+    ///
+    /// This is not available on WASI, as `poll_oneoff` always returns all
+    /// events.
+    ///
+    /// [`poll`]: Poll::poll
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use mio::Poll;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let max_events = NonZeroUsize::new(16).unwrap();
+    /// poll.set_max_events_per_poll(Some(max_events));
+    /// assert_eq!(poll.max_events_per_poll(), Some(max_events));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(unix, windows))]
+    pub fn set_max_events_per_poll(&mut self, max_events: Option<NonZeroUsize>) {
+        self.max_events = max_events;
+    }
+
+    /// Returns the limit set by [`Poll::set_max_events_per_poll`], if any.
+    #[cfg(any(unix, windows))]
+    pub fn max_events_per_poll(&self) -> Option<NonZeroUsize> {
+        self.max_events
+    }
+
     /// Wait for readiness events
     ///
     /// Blocks the current thread and waits for readiness events for any of the
@@ -430,7 +656,158 @@ impl Poll {
     ///
     /// [struct]: #
     pub fn poll(&mut self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
-        self.registry.selector.select(events.sys(), timeout)
+        #[cfg(feature = "test-util")]
+        if let Some(selector) = self.selector.as_mut() {
+            events.clear();
+            selector.select(events, timeout)?;
+            self.rotate_events(events);
+            return Ok(());
+        }
+        #[cfg(any(unix, windows))]
+        if let Some(max_events) = self.max_events {
+            self.registry
+                .selector
+                .select_max(events.sys(), max_events.get(), timeout)?;
+            self.rotate_events(events);
+            return Ok(());
+        }
+        self.registry.selector.select(events.sys(), timeout)?;
+        self.rotate_events(events);
+        Ok(())
+    }
+
+    /// Rotate `events` if fair event ordering is enabled.
+    fn rotate_events(&mut self, events: &mut Events) {
+        if self.fair && !events.is_empty() {
+            events.rotate_left(self.fair_offset % events.len());
+            self.fair_offset = self.fair_offset.wrapping_add(1);
+        }
+    }
+
+    /// Poll for readiness events without blocking.
+    ///
+    /// This is the same as calling [`poll`] with a zero timeout: it returns
+    /// immediately with the readiness events that are available, if any.
+    ///
+    /// [`poll`]: Poll::poll
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(8);
+    ///
+    /// // Nothing is registered, so there are no events.
+    /// poll.poll_now(&mut events)?;
+    /// assert!(events.is_empty());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn poll_now(&mut self, events: &mut Events) -> io::Result<()> {
+        self.poll(events, Some(Duration::ZERO))
+    }
+
+    /// Poll for readiness events without blocking until no more events are
+    /// returned, calling `f` for each event.
+    ///
+    /// This repeatedly calls [`poll_now`], passing all returned events to `f`,
+    /// until a call returns no events. This allows processing all sources
+    /// that are ready in a single burst. Returns the total number of events
+    /// passed to `f`.
+    ///
+    /// Note that this only returns once no more events are ready. If `f`
+    /// keeps a source ready, e.g. by reregistering a source that remains
+    /// readable, this doesn't return.
+    ///
+    /// [`poll_now`]: Poll::poll_now
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll, Token, Waker};
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(8);
+    /// let waker = Waker::new(poll.registry(), Token(0))?;
+    ///
+    /// waker.wake()?;
+    /// let mut tokens = Vec::new();
+    /// let n = poll.poll_drain(&mut events, |event| tokens.push(event.token()))?;
+    /// assert_eq!(n, 1);
+    /// assert_eq!(tokens, [Token(0)]);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn poll_drain<F>(&mut self, events: &mut Events, mut f: F) -> io::Result<usize>
+    where
+        F: FnMut(&event::Event),
+    {
+        let mut n = 0;
+        loop {
+            self.poll_now(events)?;
+            if events.is_empty() {
+                return Ok(n);
+            }
+            for event in events.iter() {
+                f(event);
+            }
+            n += events.len();
+        }
+    }
+
+    /// Wait for readiness events, filling a stack allocated [`EventsArray`].
+    ///
+    /// This works the same as [`poll`], but returns at most `N` events and
+    /// doesn't allocate. Any remaining events are returned by the next call.
+    ///
+    /// [`EventsArray`]: crate::event::EventsArray
+    /// [`poll`]: Poll::poll
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::event::EventsArray;
+    /// use mio::{Poll, Token, Waker};
+    /// use std::time::Duration;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let waker = Waker::new(poll.registry(), Token(0))?;
+    /// let mut events = EventsArray::<4>::new();
+    ///
+    /// waker.wake()?;
+    /// poll.poll_array(&mut events, Some(Duration::from_millis(100)))?;
+    /// assert_eq!(events.iter().next().unwrap().token(), Token(0));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn poll_array<const N: usize>(
+        &mut self,
+        events: &mut event::EventsArray<N>,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        #[cfg(feature = "test-util")]
+        if self.selector.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "`Poll::poll_array` is not supported with a custom selector",
+            ));
+        }
+        let n_events = self.registry.selector.select_into(events.sys(), timeout)?;
+        // SAFETY: `select_into` ensures that `n_events` are initialised.
+        unsafe { events.set_len(n_events) };
+        Ok(())
     }
 }
 
@@ -438,6 +815,7 @@ impl Poll {
     unix,
     not(mio_unsupported_force_poll_poll),
     not(any(
+        target_os = "aix",
         target_os = "espidf",
         target_os = "haiku",
         target_os = "hermit",
@@ -697,10 +1075,135 @@ impl Registry {
         source.deregister(self)
     }
 
+    /// Deregister multiple event sources with the `Poll` instance.
+    ///
+    /// This calls [`deregister`] for each source in `sources`. If
+    /// deregistering a source fails the remaining sources are still
+    /// deregistered, after which the first error is returned.
+    ///
+    /// [`deregister`]: Registry::deregister
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+    #[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::event::Source;
+    /// use mio::net::UdpSocket;
+    /// use mio::{Interest, Poll, Token};
+    ///
+    /// let poll = Poll::new()?;
+    ///
+    /// let mut socket1 = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// let mut socket2 = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// poll.registry().register(&mut socket1, Token(0), Interest::READABLE)?;
+    /// poll.registry().register(&mut socket2, Token(1), Interest::READABLE)?;
+    ///
+    /// poll.registry().deregister_batch(&mut [&mut socket1 as &mut dyn Source, &mut socket2])?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn deregister_batch(&self, sources: &mut [&mut dyn event::Source]) -> io::Result<()> {
+        let mut result = Ok(());
+        for source in sources.iter_mut() {
+            let res = self.deregister(&mut **source);
+            if result.is_ok() {
+                result = res;
+            }
+        }
+        result
+    }
+
+    /// Sets the read low watermark of a registered event source.
+    ///
+    /// This sets `NOTE_LOWAT` on the `EVFILT_READ` filter of the `source`,
+    /// delaying readable events until at least `lowat` bytes are available to
+    /// read. This can reduce the number of wake ups for protocols which send
+    /// data in frames. This is similar to setting `SO_RCVLOWAT` on a socket,
+    /// but also works for other types of file descriptors, e.g. pipes.
+    ///
+    /// `source` must already be registered with readable interest using
+    /// `token`. Reregistering `source` resets the low watermark, so this needs
+    /// to be called again after calling [`reregister`].
+    ///
+    /// [`reregister`]: Registry::reregister
+    #[cfg(all(
+        not(mio_unsupported_force_poll_poll),
+        any(
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "ios",
+            target_os = "macos",
+            target_os = "netbsd",
+            target_os = "openbsd",
+            target_os = "tvos",
+            target_os = "visionos",
+            target_os = "watchos",
+        ),
+    ))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "ios",
+            target_os = "macos",
+            target_os = "netbsd",
+            target_os = "openbsd",
+            target_os = "tvos",
+            target_os = "visionos",
+            target_os = "watchos",
+        )))
+    )]
+    pub fn set_read_lowat<S>(&self, source: &S, token: Token, lowat: usize) -> io::Result<()>
+    where
+        S: AsRawFd + ?Sized,
+    {
+        self.selector
+            .set_read_lowat(source.as_raw_fd(), token, lowat)
+    }
+
     /// Creates a new independently owned `Registry`.
     ///
     /// Event sources registered with this `Registry` will be registered with
     /// the original `Registry` and `Poll` instance.
+    ///
+    /// The returned `Registry` can be handed to another part of an
+    /// application, e.g. a subsystem that needs to register event sources but
+    /// should not be able to poll for events, which is only possible using
+    /// [`Poll`].
+    ///
+    /// # Lifetime
+    ///
+    /// The cloned `Registry` holds its own handle to the underlying selector
+    /// (a duplicated file descriptor on Unix, a shared reference on Windows),
+    /// so it remains valid after the `Registry` it was cloned from, or the
+    /// owning `Poll`, is dropped. However once the `Poll` instance is dropped
+    /// nothing polls the selector anymore, so while (de)registering event
+    /// sources is still safe, no events will be delivered for them.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+    #[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::TcpListener;
+    /// use mio::{Interest, Poll, Registry, Token};
+    ///
+    /// // A subsystem that can register event sources, but not poll.
+    /// fn start_listener(registry: Registry) -> std::io::Result<TcpListener> {
+    ///     let mut listener = TcpListener::bind("127.0.0.1:0".parse().unwrap())?;
+    ///     registry.register(&mut listener, Token(0), Interest::READABLE)?;
+    ///     Ok(listener)
+    /// }
+    ///
+    /// let poll = Poll::new()?;
+    /// let _listener = start_listener(poll.registry().try_clone()?)?;
+    /// #     Ok(())
+    /// # }
+    /// ```
     pub fn try_clone(&self) -> io::Result<Registry> {
         self.selector.try_clone().map(|selector| Registry {
             selector,
@@ -736,6 +1239,7 @@ impl fmt::Debug for Registry {
     unix,
     not(mio_unsupported_force_poll_poll),
     not(any(
+        target_os = "aix",
         target_os = "espidf",
         target_os = "haiku",
         target_os = "hermit",
@@ -755,6 +1259,7 @@ cfg_os_poll! {
         unix,
         not(mio_unsupported_force_poll_poll),
         not(any(
+            target_os = "aix",
             target_os = "espidf",
             target_os = "hermit",
             target_os = "nto",
@@ -79,5 +79,6 @@ cfg_not_os_poll! {
         mod unix;
         #[cfg(feature = "os-ext")]
         pub use self::unix::SourceFd;
+        pub(crate) use self::unix::{is_cloexec, set_cloexec};
     }
 }
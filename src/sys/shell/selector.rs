@@ -18,6 +18,38 @@ impl Selector {
     pub fn select(&self, _: &mut Events, _: Option<Duration>) -> io::Result<()> {
         os_required!();
     }
+
+    #[cfg(all(
+        not(mio_unsupported_force_poll_poll),
+        any(
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "ios",
+            target_os = "macos",
+            target_os = "netbsd",
+            target_os = "openbsd",
+            target_os = "tvos",
+            target_os = "visionos",
+            target_os = "watchos",
+        ),
+    ))]
+    pub fn set_read_lowat(&self, _: RawFd, _: crate::Token, _: usize) -> io::Result<()> {
+        os_required!();
+    }
+
+    #[cfg(unix)]
+    pub fn select_into(
+        &self,
+        _: &mut [std::mem::MaybeUninit<Event>],
+        _: Option<Duration>,
+    ) -> io::Result<usize> {
+        os_required!();
+    }
+
+    #[cfg(any(unix, windows))]
+    pub fn select_max(&self, _: &mut Events, _: usize, _: Option<Duration>) -> io::Result<()> {
+        os_required!();
+    }
 }
 
 #[cfg(unix)]
@@ -96,6 +128,14 @@ pub mod event {
         os_required!();
     }
 
+    pub fn is_hup(_: &Event) -> bool {
+        os_required!();
+    }
+
+    pub fn is_read_hup(_: &Event) -> bool {
+        os_required!();
+    }
+
     pub fn is_read_closed(_: &Event) -> bool {
         os_required!();
     }
@@ -116,6 +156,10 @@ pub mod event {
         os_required!();
     }
 
+    pub fn read_data(_: &Event) -> Option<usize> {
+        os_required!();
+    }
+
     pub fn debug_details(_: &mut fmt::Formatter<'_>, _: &Event) -> fmt::Result {
         os_required!();
     }
@@ -1,5 +1,22 @@
 use std::io;
 use std::net::{self, SocketAddr};
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+use std::time::Duration;
+
+#[cfg(target_os = "linux")]
+use crate::net::ZcCompletion;
 
 #[cfg(not(target_os = "wasi"))]
 pub(crate) fn new_for_addr(_: SocketAddr) -> io::Result<i32> {
@@ -26,6 +43,204 @@ pub(crate) fn set_reuseaddr(_: &net::TcpListener, _: bool) -> io::Result<()> {
     os_required!();
 }
 
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+pub(crate) fn keepalive_time(_: &net::TcpStream) -> io::Result<Duration> {
+    os_required!();
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+pub(crate) fn keepalive_interval(_: &net::TcpStream) -> io::Result<Duration> {
+    os_required!();
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+pub(crate) fn keepalive_retries(_: &net::TcpStream) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn only_v6<S>(_: &S) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(not(target_os = "wasi"))]
+pub(crate) fn set_only_v6(_: &net::TcpListener, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_freebind(_: &net::TcpListener, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn freebind(_: &net::TcpListener) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_transparent(_: &net::TcpListener, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn transparent(_: &net::TcpListener) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(windows)]
+pub(crate) fn share(_: &net::TcpStream, _: u32) -> io::Result<Vec<u8>> {
+    os_required!();
+}
+
+#[cfg(windows)]
+pub(crate) fn from_shared(_: &[u8]) -> io::Result<net::TcpStream> {
+    os_required!();
+}
+
 pub(crate) fn accept(_: &net::TcpListener) -> io::Result<(net::TcpStream, SocketAddr)> {
     os_required!();
 }
+
+#[cfg(target_os = "linux")]
+pub(crate) fn incoming_cpu(_: &net::TcpStream) -> io::Result<i32> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_busy_poll(_: &net::TcpStream, _: Duration) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn busy_poll(_: &net::TcpStream) -> io::Result<Duration> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_zerocopy(_: &net::TcpStream, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn zerocopy(_: &net::TcpStream) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn send_zerocopy(_: &net::TcpStream, _: &[u8]) -> io::Result<usize> {
+    os_required!();
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn zerocopy_completions(_: &net::TcpStream) -> io::Result<Vec<ZcCompletion>> {
+    os_required!();
+}
+
+#[cfg(any(unix, windows))]
+pub(crate) fn available(_: &net::TcpStream) -> io::Result<usize> {
+    os_required!();
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+pub(crate) fn at_mark(_: &net::TcpStream) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_recv_lowat(_: &net::TcpStream, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_lowat(_: &net::TcpStream) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+pub(crate) fn set_defer_accept(_: &net::TcpListener, _: Duration) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn defer_accept(_: &net::TcpListener) -> io::Result<Duration> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_cork(_: &net::TcpStream, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn cork(_: &net::TcpStream) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+pub(crate) fn send_file(
+    _: &net::TcpStream,
+    _: std::os::fd::RawFd,
+    _: u64,
+    _: usize,
+) -> io::Result<usize> {
+    os_required!();
+}
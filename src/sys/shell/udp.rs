@@ -1,6 +1,17 @@
 #![cfg(not(target_os = "wasi"))]
 use std::io;
+#[cfg(unix)]
+use std::io::{IoSlice, IoSliceMut};
+#[cfg(any(unix, windows))]
+use std::net::Ipv4Addr;
 use std::net::{self, SocketAddr};
+#[cfg(target_os = "linux")]
+use std::os::fd::BorrowedFd;
+#[cfg(target_os = "linux")]
+use std::time::{Duration, Instant};
+
+#[cfg(target_os = "linux")]
+use crate::net::{RecvFlags, RecvFull, RecvMsg, SendFlags, SockFilter};
 
 pub fn bind(_: SocketAddr) -> io::Result<net::UdpSocket> {
     os_required!()
@@ -9,3 +20,207 @@ pub fn bind(_: SocketAddr) -> io::Result<net::UdpSocket> {
 pub(crate) fn only_v6(_: &net::UdpSocket) -> io::Result<bool> {
     os_required!()
 }
+
+#[cfg(windows)]
+pub(crate) fn set_connreset(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(unix, windows))]
+pub(crate) fn set_multicast_if_v4(_: &net::UdpSocket, _: Ipv4Addr) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(unix, windows))]
+pub(crate) fn multicast_if_v4(_: &net::UdpSocket) -> io::Result<Ipv4Addr> {
+    os_required!()
+}
+
+#[cfg(any(unix, windows))]
+pub(crate) fn set_multicast_hops_v6(_: &net::UdpSocket, _: u32) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(unix, windows))]
+pub(crate) fn multicast_hops_v6(_: &net::UdpSocket) -> io::Result<u32> {
+    os_required!()
+}
+
+#[cfg(any(unix, windows))]
+pub(crate) fn set_unicast_hops_v6(_: &net::UdpSocket, _: u32) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(unix, windows))]
+pub(crate) fn unicast_hops_v6(_: &net::UdpSocket) -> io::Result<u32> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn set_dont_route(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn dont_route(_: &net::UdpSocket) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_from_trunc(
+    _: &net::UdpSocket,
+    _: &mut [u8],
+) -> io::Result<(usize, bool, SocketAddr)> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_from_vectored(
+    _: &net::UdpSocket,
+    _: &mut [IoSliceMut<'_>],
+) -> io::Result<(usize, SocketAddr)> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn send_to_vectored(_: &net::UdpSocket, _: &[IoSlice<'_>], _: SocketAddr) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_from_with_flags(_: &net::UdpSocket, _: &mut [u8], _: RecvFlags) -> io::Result<(usize, SocketAddr)> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn send_to_with_flags(_: &net::UdpSocket, _: &[u8], _: SocketAddr, _: SendFlags) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn interface_index_by_name(_: &str) -> io::Result<u32> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_busy_poll(_: &net::UdpSocket, _: Duration) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn busy_poll(_: &net::UdpSocket) -> io::Result<Duration> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn attach_reuseport_cbpf(_: &net::UdpSocket, _: &[SockFilter]) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn attach_filter(_: &net::UdpSocket, _: &[SockFilter]) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn detach_filter(_: &net::UdpSocket) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn attach_reuseport_ebpf(_: &net::UdpSocket, _: BorrowedFd<'_>) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_txtime(_: &net::UdpSocket, _: i32, _: u32) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn send_at(_: &net::UdpSocket, _: &[u8], _: SocketAddr, _: Instant) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_recv_timestamp(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_timestamp(_: &net::UdpSocket) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_recv_hw_timestamp(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_hw_timestamp(_: &net::UdpSocket) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_recv_pktinfo(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_pktinfo(_: &net::UdpSocket) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(any(unix, windows))]
+pub(crate) fn available(_: &net::UdpSocket) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_transparent(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn transparent(_: &net::UdpSocket) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_recv_orig_dst_addr(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_orig_dst_addr(_: &net::UdpSocket) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_recv_ecn(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_ecn(_: &net::UdpSocket) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_full(_: &net::UdpSocket, _: &mut [u8]) -> io::Result<RecvFull> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_mmsg(_: &net::UdpSocket, _: &mut [&mut [u8]]) -> io::Result<Vec<RecvMsg>> {
+    os_required!()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn send_batch_segmented(
+    _: &net::UdpSocket,
+    _: &[(&[u8], u16, SocketAddr)],
+) -> io::Result<usize> {
+    os_required!()
+}
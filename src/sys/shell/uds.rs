@@ -1,3 +1,9 @@
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::io::{self, IoSlice, IoSliceMut};
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::UCred;
+
 pub(crate) mod datagram {
     use std::io;
     use std::os::unix::net::{self, SocketAddr};
@@ -13,6 +19,17 @@ pub(crate) mod datagram {
     pub(crate) fn pair() -> io::Result<(net::UnixDatagram, net::UnixDatagram)> {
         os_required!()
     }
+
+    pub(crate) fn peek(_: &net::UnixDatagram, _: &mut [u8]) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn peek_from(
+        _: &net::UnixDatagram,
+        _: &mut [u8],
+    ) -> io::Result<(usize, SocketAddr)> {
+        os_required!()
+    }
 }
 
 pub(crate) mod listener {
@@ -42,3 +59,31 @@ pub(crate) mod stream {
         os_required!()
     }
 }
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn current_creds() -> UCred {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_passcred<S>(_: &S, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn passcred<S>(_: &S) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_with_creds<S>(_: &S, _: &[IoSlice<'_>], _: &UCred) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_with_creds<S>(
+    _: &S,
+    _: &mut [IoSliceMut<'_>],
+) -> io::Result<(usize, Option<UCred>)> {
+    os_required!()
+}
@@ -0,0 +1,20 @@
+use std::io;
+use std::os::fd::RawFd;
+
+pub(crate) fn set_cloexec(fd: RawFd, cloexec: bool) -> io::Result<()> {
+    let flags = syscall!(fcntl(fd, libc::F_GETFD))?;
+    let new_flags = if cloexec {
+        flags | libc::FD_CLOEXEC
+    } else {
+        flags & !libc::FD_CLOEXEC
+    };
+    if new_flags != flags {
+        syscall!(fcntl(fd, libc::F_SETFD, new_flags))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn is_cloexec(fd: RawFd) -> io::Result<bool> {
+    let flags = syscall!(fcntl(fd, libc::F_GETFD))?;
+    Ok(flags & libc::FD_CLOEXEC != 0)
+}
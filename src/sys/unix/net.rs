@@ -1,5 +1,5 @@
 use std::io;
-use std::mem::size_of;
+use std::mem::{size_of, MaybeUninit};
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 
 pub(crate) fn new_ip_socket(addr: SocketAddr, socket_type: libc::c_int) -> io::Result<libc::c_int> {
@@ -51,6 +51,7 @@ pub(crate) fn new_socket(domain: libc::c_int, socket_type: libc::c_int) -> io::R
 
     // Darwin (and others) doesn't have SOCK_NONBLOCK or SOCK_CLOEXEC.
     #[cfg(any(
+        target_os = "aix",
         target_os = "ios",
         target_os = "macos",
         target_os = "tvos",
@@ -75,6 +76,55 @@ pub(crate) fn new_socket(domain: libc::c_int, socket_type: libc::c_int) -> io::R
     Ok(socket)
 }
 
+/// Set a socket option, see `setsockopt(2)`.
+pub(crate) fn setsockopt<T>(
+    fd: libc::c_int,
+    level: libc::c_int,
+    name: libc::c_int,
+    value: T,
+) -> io::Result<()> {
+    syscall!(setsockopt(
+        fd,
+        level,
+        name,
+        &value as *const T as *const libc::c_void,
+        size_of::<T>() as libc::socklen_t,
+    ))
+    .map(|_| ())
+}
+
+/// Get a socket option, see `getsockopt(2)`.
+///
+/// `T` must be valid when zero filled, as the kernel may write fewer bytes than
+/// `size_of::<T>()`.
+pub(crate) fn getsockopt<T>(
+    fd: libc::c_int,
+    level: libc::c_int,
+    name: libc::c_int,
+) -> io::Result<T> {
+    let mut value: MaybeUninit<T> = MaybeUninit::zeroed();
+    let mut length = size_of::<T>() as libc::socklen_t;
+    syscall!(getsockopt(
+        fd,
+        level,
+        name,
+        value.as_mut_ptr().cast(),
+        &mut length,
+    ))?;
+    // SAFETY: `value` was zero filled and `getsockopt(2)` wrote at most
+    // `size_of::<T>()` bytes into it.
+    Ok(unsafe { value.assume_init() })
+}
+
+/// Returns the number of bytes available to read from `fd`, using the
+/// `FIONREAD` ioctl.
+#[cfg(unix)]
+pub(crate) fn available(fd: libc::c_int) -> io::Result<usize> {
+    let mut available: libc::c_int = 0;
+    syscall!(ioctl(fd, libc::FIONREAD, &mut available))?;
+    Ok(available as usize)
+}
+
 /// A type with the same memory layout as `libc::sockaddr`. Used in converting Rust level
 /// SocketAddr* types into their system representation. The benefit of this specific
 /// type over using `libc::sockaddr_storage` is that this type is exactly as large as it
@@ -248,6 +248,43 @@ impl Sender {
         set_nonblocking(self.inner.as_raw_fd(), nonblocking)
     }
 
+    /// Set the capacity of the pipe's buffer, in bytes.
+    ///
+    /// A larger buffer allows a producer to write more data before the pipe
+    /// is full, reducing the number of wake ups. This uses `F_SETPIPE_SZ`.
+    ///
+    /// The kernel rounds `capacity` up to a power of two number of pages,
+    /// [`capacity`] can be used to retrieve the actual capacity. For
+    /// unprivileged processes the capacity can't exceed the value in
+    /// `/proc/sys/fs/pipe-max-size` (1 MiB by default), trying to do so
+    /// returns a [`PermissionDenied`] error.
+    ///
+    /// [`capacity`]: Sender::capacity
+    /// [`PermissionDenied`]: io::ErrorKind::PermissionDenied
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn set_capacity(&self, capacity: usize) -> io::Result<()> {
+        let capacity = libc::c_int::try_from(capacity)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "capacity too large"))?;
+        syscall!(fcntl(
+            self.inner.as_raw_fd(),
+            libc::F_SETPIPE_SZ,
+            capacity
+        ))
+        .map(|_| ())
+    }
+
+    /// Get the capacity of the pipe's buffer, in bytes.
+    ///
+    /// This uses `F_GETPIPE_SZ`, see [`set_capacity`] for more information.
+    ///
+    /// [`set_capacity`]: Sender::set_capacity
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+    pub fn capacity(&self) -> io::Result<usize> {
+        syscall!(fcntl(self.inner.as_raw_fd(), libc::F_GETPIPE_SZ)).map(|n| n as usize)
+    }
+
     /// Execute an I/O operation ensuring that the socket receives more events
     /// if it hits a [`WouldBlock`] error.
     ///
@@ -578,6 +615,46 @@ impl AsFd for Receiver {
     }
 }
 
+/// Move up to `len` bytes from `from` to `to` without copying them into user
+/// space, using `splice(2)`.
+///
+/// At least one of `from` and `to` must be a pipe, e.g. a [`Receiver`] or
+/// [`Sender`], the other can be a socket, such as a [`TcpStream`]. This makes
+/// it possible to forward data between two sockets inside the kernel, by
+/// splicing through an intermediate pipe. `flags` can contain any of the
+/// `SPLICE_F_*` flags, `SPLICE_F_NONBLOCK` is always set.
+///
+/// Returns the number of bytes moved, where zero means `from` reached the end
+/// of its input (e.g. the peer closed the connection). If no data is
+/// available in `from`, or `to` is full, this returns a [`WouldBlock`] error,
+/// after which the caller should wait for the relevant readiness event.
+///
+/// [`TcpStream`]: crate::net::TcpStream
+/// [`WouldBlock`]: io::ErrorKind::WouldBlock
+///
+/// # Notes
+///
+/// The I/O isn't performed using the sources' `try_io` methods. When using
+/// the poll(2) based selector this means the sources won't receive new events
+/// after a `WouldBlock` error, unless this is called inside `try_io`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[cfg_attr(docsrs, doc(cfg(any(target_os = "android", target_os = "linux"))))]
+pub fn splice<F, T>(from: &F, to: &T, len: usize, flags: u32) -> io::Result<usize>
+where
+    F: AsRawFd + ?Sized,
+    T: AsRawFd + ?Sized,
+{
+    syscall!(splice(
+        from.as_raw_fd(),
+        std::ptr::null_mut(),
+        to.as_raw_fd(),
+        std::ptr::null_mut(),
+        len,
+        flags | libc::SPLICE_F_NONBLOCK,
+    ))
+    .map(|n| n as usize)
+}
+
 #[cfg(not(any(target_os = "illumos", target_os = "solaris", target_os = "vita")))]
 fn set_nonblocking(fd: RawFd, nonblocking: bool) -> io::Result<()> {
     let value = nonblocking as libc::c_int;
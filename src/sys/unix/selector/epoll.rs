@@ -1,10 +1,11 @@
+use std::mem::MaybeUninit;
 use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
 #[cfg(debug_assertions)]
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
-use std::{io, ptr};
+use std::{cmp, io, ptr};
 
-use libc::{EPOLLET, EPOLLIN, EPOLLOUT, EPOLLPRI, EPOLLRDHUP};
+use libc::{EPOLLET, EPOLLIN, EPOLLOUT, EPOLLPRI, EPOLLRDHUP, EPOLLWAKEUP};
 
 use crate::{Interest, Token};
 
@@ -20,7 +21,9 @@ pub struct Selector {
 }
 
 impl Selector {
-    pub fn new() -> io::Result<Selector> {
+    /// epoll doesn't need to know the number of sources up front, so
+    /// `capacity` is ignored.
+    pub fn new(_capacity: usize) -> io::Result<Selector> {
         // SAFETY: `epoll_create1(2)` ensures the fd is valid.
         let ep = unsafe { OwnedFd::from_raw_fd(syscall!(epoll_create1(libc::EPOLL_CLOEXEC))?) };
         Ok(Selector {
@@ -40,6 +43,31 @@ impl Selector {
     }
 
     pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        self.select_max(events, usize::MAX, timeout)
+    }
+
+    /// Same as `select`, but retrieves at most `max_events` events.
+    pub fn select_max(
+        &self,
+        events: &mut Events,
+        max_events: usize,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        events.clear();
+        let spare = events.spare_capacity_mut();
+        let len = cmp::min(spare.len(), max_events);
+        let n_events = self.select_into(&mut spare[..len], timeout)?;
+        // This is safe because `select_into` ensures that `n_events` are
+        // assigned.
+        unsafe { events.set_len(n_events) };
+        Ok(())
+    }
+
+    pub fn select_into(
+        &self,
+        events: &mut [MaybeUninit<Event>],
+        timeout: Option<Duration>,
+    ) -> io::Result<usize> {
         let timeout = timeout
             .map(|to| {
                 // `Duration::as_millis` truncates, so round up. This avoids
@@ -52,18 +80,13 @@ impl Selector {
             })
             .unwrap_or(-1);
 
-        events.clear();
         syscall!(epoll_wait(
             self.ep.as_raw_fd(),
-            events.as_mut_ptr(),
-            events.capacity() as i32,
+            events.as_mut_ptr().cast(),
+            cmp::min(events.len(), libc::c_int::MAX as usize) as libc::c_int,
             timeout,
         ))
-        .map(|n_events| {
-            // This is safe because `epoll_wait` ensures that `n_events` are
-            // assigned.
-            unsafe { events.set_len(n_events as usize) };
-        })
+        .map(|n_events| n_events as usize)
     }
 
     pub fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
@@ -126,6 +149,14 @@ fn interests_to_epoll(interests: Interest) -> u32 {
         kind |= EPOLLPRI;
     }
 
+    if interests.is_wakeup() {
+        kind |= EPOLLWAKEUP;
+    }
+
+    if interests.is_hup() {
+        kind |= EPOLLRDHUP;
+    }
+
     kind as u32
 }
 
@@ -136,6 +167,8 @@ pub mod event {
     use std::fmt;
 
     use crate::sys::Event;
+    #[cfg(feature = "test-util")]
+    use crate::test::Readiness;
     use crate::Token;
 
     pub fn token(event: &Event) -> Token {
@@ -155,12 +188,19 @@ pub mod event {
         (event.events as libc::c_int & libc::EPOLLERR) != 0
     }
 
+    pub fn is_hup(event: &Event) -> bool {
+        (event.events as libc::c_int & libc::EPOLLHUP) != 0
+    }
+
+    pub fn is_read_hup(event: &Event) -> bool {
+        (event.events as libc::c_int & libc::EPOLLRDHUP) != 0
+    }
+
     pub fn is_read_closed(event: &Event) -> bool {
         // Both halves of the socket have closed
         event.events as libc::c_int & libc::EPOLLHUP != 0
             // Socket has received FIN or called shutdown(SHUT_RD)
-            || (event.events as libc::c_int & libc::EPOLLIN != 0
-                && event.events as libc::c_int & libc::EPOLLRDHUP != 0)
+            || event.events as libc::c_int & libc::EPOLLRDHUP != 0
     }
 
     pub fn is_write_closed(event: &Event) -> bool {
@@ -187,6 +227,40 @@ pub mod event {
         false
     }
 
+    pub fn read_data(_: &Event) -> Option<usize> {
+        // Not supported.
+        None
+    }
+
+    #[cfg(feature = "test-util")]
+    pub fn push(events: &mut crate::sys::Events, token: Token, readiness: Readiness) {
+        let mut kind = 0;
+        if readiness.is_readable() {
+            kind |= libc::EPOLLIN;
+        }
+        if readiness.is_writable() {
+            kind |= libc::EPOLLOUT;
+        }
+        if readiness.is_error() {
+            kind |= libc::EPOLLERR;
+        }
+        if readiness.is_read_closed() {
+            kind |= libc::EPOLLIN | libc::EPOLLRDHUP;
+        }
+        if readiness.is_write_closed() {
+            kind |= libc::EPOLLOUT | libc::EPOLLERR;
+        }
+        if readiness.is_priority() {
+            kind |= libc::EPOLLPRI;
+        }
+        events.push(Event {
+            events: kind as u32,
+            u64: usize::from(token) as u64,
+            #[cfg(target_os = "redox")]
+            _pad: 0,
+        });
+    }
+
     pub fn debug_details(f: &mut fmt::Formatter<'_>, event: &Event) -> fmt::Result {
         #[allow(clippy::trivially_copy_pass_by_ref)]
         fn check_events(got: &u32, want: &libc::c_int) -> bool {
@@ -71,7 +71,9 @@ pub struct Selector {
 }
 
 impl Selector {
-    pub fn new() -> io::Result<Selector> {
+    /// kqueue doesn't need to know the number of sources up front, so
+    /// `capacity` is ignored.
+    pub fn new(_capacity: usize) -> io::Result<Selector> {
         // SAFETY: `kqueue(2)` ensures the fd is valid.
         let kq = unsafe { OwnedFd::from_raw_fd(syscall!(kqueue())?) };
         syscall!(fcntl(kq.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC))?;
@@ -92,6 +94,31 @@ impl Selector {
     }
 
     pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        self.select_max(events, usize::MAX, timeout)
+    }
+
+    /// Same as `select`, but retrieves at most `max_events` events.
+    pub fn select_max(
+        &self,
+        events: &mut Events,
+        max_events: usize,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        events.clear();
+        let spare = events.spare_capacity_mut();
+        let len = cmp::min(spare.len(), max_events);
+        let n_events = self.select_into(&mut spare[..len], timeout)?;
+        // This is safe because `select_into` ensures that `n_events` are
+        // assigned.
+        unsafe { events.set_len(n_events) };
+        Ok(())
+    }
+
+    pub fn select_into(
+        &self,
+        events: &mut [MaybeUninit<Event>],
+        timeout: Option<Duration>,
+    ) -> io::Result<usize> {
         let timeout = timeout.map(|to| libc::timespec {
             tv_sec: cmp::min(to.as_secs(), libc::time_t::MAX as u64) as libc::time_t,
             // `Duration::subsec_nanos` is guaranteed to be less than one
@@ -105,20 +132,15 @@ impl Selector {
             .map(|s| s as *const _)
             .unwrap_or(ptr::null_mut());
 
-        events.clear();
         syscall!(kevent(
             self.kq.as_raw_fd(),
             ptr::null(),
             0,
-            events.as_mut_ptr(),
-            events.capacity() as Count,
+            events.as_mut_ptr().cast(),
+            cmp::min(events.len(), Count::MAX as usize) as Count,
             timeout,
         ))
-        .map(|n_events| {
-            // This is safe because `kevent` ensures that `n_events` are
-            // assigned.
-            unsafe { events.set_len(n_events as usize) };
-        })
+        .map(|n_events| n_events as usize)
     }
 
     pub fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
@@ -134,7 +156,9 @@ impl Selector {
             n_changes += 1;
         }
 
-        if interests.is_readable() {
+        // kqueue has no hangup only filter, the closure is reported using
+        // `EV_EOF` on the read filter.
+        if interests.is_readable() || interests.is_hup() {
             let kevent = kevent!(fd, libc::EVFILT_READ, flags, token.0);
             changes[n_changes] = MaybeUninit::new(kevent);
             n_changes += 1;
@@ -167,7 +191,7 @@ impl Selector {
         } else {
             flags | libc::EV_DELETE
         };
-        let read_flags = if interests.is_readable() {
+        let read_flags = if interests.is_readable() || interests.is_hup() {
             flags | libc::EV_ADD
         } else {
             flags | libc::EV_DELETE
@@ -208,8 +232,22 @@ impl Selector {
         kevent_register(self.kq.as_raw_fd(), &mut changes, &[libc::ENOENT as i64])
     }
 
+    pub fn set_read_lowat(&self, fd: RawFd, token: Token, lowat: usize) -> io::Result<()> {
+        let flags = libc::EV_CLEAR | libc::EV_RECEIPT | libc::EV_ADD;
+        let mut kevent = kevent!(fd, libc::EVFILT_READ, flags, token.0);
+        kevent.fflags = libc::NOTE_LOWAT;
+        kevent.data = lowat as _;
+        // For the explanation of ignoring `EPIPE` see `register`.
+        kevent_register(
+            self.kq.as_raw_fd(),
+            &mut [kevent],
+            &[libc::EPIPE as i64],
+        )
+    }
+
     // Used by `Waker`.
     #[cfg(any(
+        target_os = "dragonfly",
         target_os = "freebsd",
         target_os = "ios",
         target_os = "macos",
@@ -218,9 +256,11 @@ impl Selector {
         target_os = "watchos"
     ))]
     pub fn setup_waker(&self, token: Token) -> io::Result<()> {
-        // First attempt to accept user space notifications.
+        // First attempt to accept user space notifications. The token is used
+        // as identifier, so that the waker used by `Poll::shutdown_waker`
+        // doesn't replace the token of another waker.
         let mut kevent = kevent!(
-            0,
+            token.0,
             libc::EVFILT_USER,
             libc::EV_ADD | libc::EV_CLEAR | libc::EV_RECEIPT,
             token.0
@@ -238,6 +278,7 @@ impl Selector {
 
     // Used by `Waker`.
     #[cfg(any(
+        target_os = "dragonfly",
         target_os = "freebsd",
         target_os = "ios",
         target_os = "macos",
@@ -247,7 +288,7 @@ impl Selector {
     ))]
     pub fn wake(&self, token: Token) -> io::Result<()> {
         let mut kevent = kevent!(
-            0,
+            token.0,
             libc::EVFILT_USER,
             libc::EV_ADD | libc::EV_RECEIPT,
             token.0
@@ -357,9 +398,16 @@ unsafe impl Sync for Events {}
 pub mod event {
     use std::fmt;
 
+    #[cfg(feature = "test-util")]
+    use std::mem;
+
     use crate::sys::Event;
+    #[cfg(feature = "test-util")]
+    use crate::test::Readiness;
     use crate::Token;
 
+    #[cfg(feature = "test-util")]
+    use super::UData;
     use super::{Filter, Flags};
 
     pub fn token(event: &Event) -> Token {
@@ -369,6 +417,7 @@ pub mod event {
     pub fn is_readable(event: &Event) -> bool {
         event.filter == libc::EVFILT_READ || {
             #[cfg(any(
+                target_os = "dragonfly",
                 target_os = "freebsd",
                 target_os = "ios",
                 target_os = "macos",
@@ -383,6 +432,7 @@ pub mod event {
                 event.filter == libc::EVFILT_USER
             }
             #[cfg(not(any(
+                target_os = "dragonfly",
                 target_os = "freebsd",
                 target_os = "ios",
                 target_os = "macos",
@@ -407,6 +457,18 @@ pub mod event {
             (event.flags & libc::EV_EOF) != 0 && event.fflags != 0
     }
 
+    pub fn is_hup(_: &Event) -> bool {
+        // kqueue reports the read and write side closing separately, see
+        // `is_read_closed` and `is_write_closed`.
+        false
+    }
+
+    pub fn is_read_hup(event: &Event) -> bool {
+        // kqueue doesn't distinguish the peer shutting down from the read half
+        // being closed otherwise.
+        is_read_closed(event)
+    }
+
     pub fn is_read_closed(event: &Event) -> bool {
         event.filter == libc::EVFILT_READ && event.flags & libc::EV_EOF != 0
     }
@@ -460,6 +522,45 @@ pub mod event {
         }
     }
 
+    pub fn read_data(event: &Event) -> Option<usize> {
+        // For `EVFILT_READ` the `data` field contains the number of bytes
+        // available to read (or the backlog size for listening sockets).
+        if event.filter == libc::EVFILT_READ {
+            Some(event.data as usize)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "test-util")]
+    pub fn push(events: &mut crate::sys::Events, token: Token, readiness: Readiness) {
+        // kqueue uses a separate event per filter, so readable and writable
+        // readiness are returned as two events.
+        let flags = if readiness.is_error() {
+            libc::EV_ERROR
+        } else {
+            0
+        };
+        let read = readiness.is_readable() || readiness.is_read_closed();
+        let write = readiness.is_writable() || readiness.is_write_closed();
+        if read || !write {
+            let eof = if readiness.is_read_closed() {
+                libc::EV_EOF
+            } else {
+                0
+            };
+            events.push(kevent!(0, libc::EVFILT_READ, flags | eof, token.0));
+        }
+        if write {
+            let eof = if readiness.is_write_closed() {
+                libc::EV_EOF
+            } else {
+                0
+            };
+            events.push(kevent!(0, libc::EVFILT_WRITE, flags | eof, token.0));
+        }
+    }
+
     pub fn debug_details(f: &mut fmt::Formatter<'_>, event: &Event) -> fmt::Result {
         debug_detail!(
             FilterDetails(Filter),
@@ -22,6 +22,7 @@ pub(crate) use self::epoll::{event, Event, Events, Selector};
 
 #[cfg(any(
     mio_unsupported_force_poll_poll,
+    target_os = "aix",
     target_os = "espidf",
     target_os = "haiku",
     target_os = "hermit",
@@ -33,6 +34,7 @@ mod poll;
 
 #[cfg(any(
     mio_unsupported_force_poll_poll,
+    target_os = "aix",
     target_os = "espidf",
     target_os = "haiku",
     target_os = "hermit",
@@ -45,6 +47,7 @@ pub(crate) use self::poll::{event, Event, Events, Selector};
 cfg_io_source! {
     #[cfg(any(
         mio_unsupported_force_poll_poll,
+        target_os = "aix",
         target_os = "espidf",
         target_os = "haiku",
         target_os = "hermit",
@@ -5,6 +5,7 @@
 
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
+use std::mem::MaybeUninit;
 #[cfg(not(target_os = "hermit"))]
 use std::os::fd::{AsRawFd, RawFd};
 // TODO: once <https://github.com/rust-lang/rust/issues/126198> is fixed this
@@ -29,8 +30,8 @@ pub struct Selector {
 }
 
 impl Selector {
-    pub fn new() -> io::Result<Selector> {
-        let state = SelectorState::new()?;
+    pub fn new(capacity: usize) -> io::Result<Selector> {
+        let state = SelectorState::new(capacity)?;
 
         Ok(Selector {
             state: Arc::new(state),
@@ -44,7 +45,34 @@ impl Selector {
     }
 
     pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
-        self.state.select(events, timeout)
+        events.clear();
+        self.state
+            .select(usize::MAX, timeout, |event| events.push(event))
+    }
+
+    /// Same as `select`, but retrieves at most `max_events` events.
+    pub fn select_max(
+        &self,
+        events: &mut Events,
+        max_events: usize,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        events.clear();
+        self.state
+            .select(max_events, timeout, |event| events.push(event))
+    }
+
+    pub fn select_into(
+        &self,
+        events: &mut [MaybeUninit<Event>],
+        timeout: Option<Duration>,
+    ) -> io::Result<usize> {
+        let mut n_events = 0;
+        self.state.select(events.len(), timeout, |event| {
+            events[n_events].write(event);
+            n_events += 1;
+        })?;
+        Ok(n_events)
     }
 
     pub fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
@@ -98,6 +126,11 @@ struct SelectorState {
     /// to the list of emitted events.
     pending_wake_token: Mutex<Option<Token>>,
 
+    /// Set when the `Waker` with `Token::SHUTDOWN` (see `Poll::shutdown_waker`)
+    /// asks to wake. Kept separate from `pending_wake_token` so it doesn't
+    /// replace the token of another `Waker`.
+    shutdown: AtomicBool,
+
     /// Data is written to this to wake up the current instance of `wait`, which can occur when the
     /// user notifies it (in which case `notified` would have been set) or when an operation needs
     /// to occur (in which case `waiting_operations` would have been incremented).
@@ -160,20 +193,25 @@ struct FdData {
 }
 
 impl SelectorState {
-    pub fn new() -> io::Result<SelectorState> {
+    pub fn new(capacity: usize) -> io::Result<SelectorState> {
         let notify_waker = WakerInternal::new()?;
 
+        // The first entry is always the notify waker.
+        let mut poll_fds = Vec::with_capacity(capacity.saturating_add(1));
+        poll_fds.push(PollFd(libc::pollfd {
+            fd: notify_waker.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        }));
+
         Ok(Self {
             fds: Mutex::new(Fds {
-                poll_fds: vec![PollFd(libc::pollfd {
-                    fd: notify_waker.as_raw_fd(),
-                    events: libc::POLLIN,
-                    revents: 0,
-                })],
-                fd_data: HashMap::new(),
+                poll_fds,
+                fd_data: HashMap::with_capacity(capacity),
             }),
             pending_removal: Mutex::new(Vec::new()),
             pending_wake_token: Mutex::new(None),
+            shutdown: AtomicBool::new(false),
             notify_waker,
             waiting_operations: AtomicUsize::new(0),
             operations_complete: Condvar::new(),
@@ -182,8 +220,22 @@ impl SelectorState {
         })
     }
 
-    pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
-        events.clear();
+    /// Poll for events, passing at most `max_events` events to `push`.
+    ///
+    /// Events for fds that are not passed to `push` keep their interest, so
+    /// they are returned by the next call.
+    pub fn select<F>(
+        &self,
+        max_events: usize,
+        timeout: Option<Duration>,
+        mut push: F,
+    ) -> io::Result<()>
+    where
+        F: FnMut(Event),
+    {
+        if max_events == 0 {
+            return Ok(());
+        }
 
         let mut fds = self.fds.lock().unwrap();
 
@@ -217,12 +269,17 @@ impl SelectorState {
             let mut num_fd_events = if notified { num_events - 1 } else { num_events };
 
             let pending_wake_token = self.pending_wake_token.lock().unwrap().take();
+            let mut shutdown = false;
 
             if notified {
                 self.notify_waker.ack_and_reset();
                 if pending_wake_token.is_some() {
                     num_fd_events += 1;
                 }
+                shutdown = self.shutdown.swap(false, Ordering::AcqRel);
+                if shutdown {
+                    num_fd_events += 1;
+                }
             }
 
             // We now check whether this poll was performed with descriptors which were pending
@@ -234,15 +291,29 @@ impl SelectorState {
             // Store the events if there were any.
             if num_fd_events > 0 {
                 let fds = &mut *fds;
-
-                events.reserve(num_fd_events);
+                let num_fd_events = num_fd_events.min(max_events);
+                let mut n_events = 0;
 
                 // Add synthetic events we picked up from calls to wake()
-                if let Some(pending_wake_token) = pending_wake_token {
-                    events.push(Event {
-                        token: pending_wake_token,
+                if shutdown {
+                    push(Event {
+                        token: Token::SHUTDOWN,
                         events: waker_events,
                     });
+                    n_events += 1;
+                }
+                if let Some(pending_wake_token) = pending_wake_token {
+                    if n_events < num_fd_events {
+                        push(Event {
+                            token: pending_wake_token,
+                            events: waker_events,
+                        });
+                        n_events += 1;
+                    } else {
+                        // Reached `max_events`, the wake up is returned by the
+                        // next poll.
+                        let _ = self.wake(pending_wake_token);
+                    }
                 }
 
                 for fd_data in fds.fd_data.values_mut() {
@@ -253,12 +324,19 @@ impl SelectorState {
                         continue;
                     }
 
+                    if n_events == num_fd_events {
+                        // Reached `max_events`, the remaining fds keep their
+                        // interest and are returned by the next poll.
+                        break;
+                    }
+
                     if poll_fd.revents != 0 {
                         // Store event
-                        events.push(Event {
+                        push(Event {
                             token: fd_data.token,
                             events: poll_fd.revents,
                         });
+                        n_events += 1;
 
                         if poll_fd.revents & (libc::POLLHUP | libc::POLLERR) != 0 {
                             pending_removal.push(poll_fd.fd);
@@ -273,7 +351,7 @@ impl SelectorState {
                         // Minor optimization to potentially avoid looping n times where n is the
                         // number of input fds (i.e. we might loop between m and n times where m is
                         // the number of fds with revents != 0).
-                        if events.len() == num_fd_events {
+                        if n_events == num_fd_events {
                             break;
                         }
                     }
@@ -434,7 +512,11 @@ impl SelectorState {
     }
 
     pub fn wake(&self, token: Token) -> io::Result<()> {
-        self.pending_wake_token.lock().unwrap().replace(token);
+        if token == Token::SHUTDOWN {
+            self.shutdown.store(true, Ordering::Release);
+        } else {
+            self.pending_wake_token.lock().unwrap().replace(token);
+        }
         self.notify_waker.wake()
     }
 }
@@ -494,6 +576,10 @@ fn interests_to_poll(interest: Interest) -> libc::c_short {
         kind |= PRIORITY_EVENTS;
     }
 
+    if interest.is_hup() {
+        kind |= POLLRDHUP;
+    }
+
     kind
 }
 
@@ -548,6 +634,8 @@ pub type Events = Vec<Event>;
 pub mod event {
     use crate::sys::unix::selector::poll::POLLRDHUP;
     use crate::sys::Event;
+    #[cfg(feature = "test-util")]
+    use crate::test::Readiness;
     use crate::Token;
     use std::fmt;
 
@@ -567,6 +655,15 @@ pub mod event {
         (event.events & libc::POLLERR) != 0
     }
 
+    pub fn is_hup(event: &Event) -> bool {
+        (event.events & libc::POLLHUP) != 0
+    }
+
+    pub fn is_read_hup(event: &Event) -> bool {
+        // Always false if `POLLRDHUP` isn't supported.
+        (event.events & POLLRDHUP) != 0
+    }
+
     pub fn is_read_closed(event: &Event) -> bool {
         // Both halves of the socket have closed
         (event.events & libc::POLLHUP) != 0
@@ -597,6 +694,39 @@ pub mod event {
         false
     }
 
+    pub fn read_data(_: &Event) -> Option<usize> {
+        // Not supported.
+        None
+    }
+
+    #[cfg(feature = "test-util")]
+    pub fn push(events: &mut crate::sys::Events, token: Token, readiness: Readiness) {
+        let mut kind = 0;
+        if readiness.is_readable() {
+            kind |= libc::POLLIN;
+        }
+        if readiness.is_writable() {
+            kind |= libc::POLLOUT;
+        }
+        if readiness.is_error() {
+            kind |= libc::POLLERR;
+        }
+        if readiness.is_read_closed() {
+            // `POLLRDHUP` isn't supported on all platforms.
+            kind |= libc::POLLIN | if POLLRDHUP != 0 { POLLRDHUP } else { libc::POLLHUP };
+        }
+        if readiness.is_write_closed() {
+            kind |= libc::POLLOUT | libc::POLLERR;
+        }
+        if readiness.is_priority() {
+            kind |= libc::POLLPRI;
+        }
+        events.push(Event {
+            token,
+            events: kind,
+        });
+    }
+
     pub fn debug_details(f: &mut fmt::Formatter<'_>, event: &Event) -> fmt::Result {
         #[allow(clippy::trivially_copy_pass_by_ref)]
         fn check_events(got: &libc::c_short, want: &libc::c_short) -> bool {
@@ -1,4 +1,6 @@
 use std::io;
+#[cfg(all(feature = "os-ext", not(target_os = "hermit")))]
+use std::os::fd::{AsRawFd, OwnedFd};
 #[cfg(not(target_os = "hermit"))]
 use std::os::fd::RawFd;
 // TODO: once <https://github.com/rust-lang/rust/issues/126198> is fixed this
@@ -96,6 +98,26 @@ use crate::{event, Interest, Registry, Token};
 #[derive(Debug)]
 pub struct SourceFd<'a>(pub &'a RawFd);
 
+#[cfg(feature = "os-ext")]
+impl<'a> SourceFd<'a> {
+    /// Sets or clears the `FD_CLOEXEC` flag on the file descriptor.
+    ///
+    /// Clearing the flag allows a child process to inherit the file descriptor
+    /// across `exec`.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn set_cloexec(&self, cloexec: bool) -> io::Result<()> {
+        crate::sys::set_cloexec(*self.0, cloexec)
+    }
+
+    /// Returns `true` if the `FD_CLOEXEC` flag is set on the file descriptor.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn is_cloexec(&self) -> io::Result<bool> {
+        crate::sys::is_cloexec(*self.0)
+    }
+}
+
 impl<'a> event::Source for SourceFd<'a> {
     fn register(
         &mut self,
@@ -119,3 +141,40 @@ impl<'a> event::Source for SourceFd<'a> {
         registry.selector().deregister(*self.0)
     }
 }
+
+/// Registers an owned file descriptor, e.g. one returned by a library, with
+/// [`Poll`] directly.
+///
+/// Unlike [`SourceFd`] the lifetime of the FD is tied to the source, the FD
+/// is closed when the `OwnedFd` is dropped.
+///
+/// [`Poll`]: crate::Poll
+#[cfg(all(feature = "os-ext", not(target_os = "hermit")))]
+#[cfg_attr(docsrs, doc(cfg(all(unix, feature = "os-ext"))))]
+impl event::Source for OwnedFd {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        registry
+            .selector()
+            .register(self.as_raw_fd(), token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        registry
+            .selector()
+            .reregister(self.as_raw_fd(), token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        registry.selector().deregister(self.as_raw_fd())
+    }
+}
@@ -1,15 +1,45 @@
 use std::convert::TryInto;
 use std::io;
+#[cfg(any(target_os = "freebsd", target_os = "linux"))]
+use std::mem;
 use std::mem::{size_of, MaybeUninit};
 use std::net::{self, SocketAddr};
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+use std::time::Duration;
 #[cfg(not(target_os = "hermit"))]
 use std::os::fd::{AsRawFd, FromRawFd};
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+use std::os::fd::RawFd;
 // TODO: once <https://github.com/rust-lang/rust/issues/126198> is fixed this
 // can use `std::os::fd` and be merged with the above.
 #[cfg(target_os = "hermit")]
 use std::os::hermit::io::{AsRawFd, FromRawFd};
 
-use crate::sys::unix::net::{new_socket, socket_addr, to_socket_addr};
+#[cfg(target_os = "linux")]
+use crate::net::ZcCompletion;
+use crate::sys::unix::net::{getsockopt, new_socket, setsockopt, socket_addr, to_socket_addr};
 
 pub(crate) fn new_for_addr(address: SocketAddr) -> io::Result<libc::c_int> {
     let domain = match address {
@@ -46,14 +76,411 @@ pub(crate) fn listen(socket: &net::TcpListener, backlog: u32) -> io::Result<()>
 
 pub(crate) fn set_reuseaddr(socket: &net::TcpListener, reuseaddr: bool) -> io::Result<()> {
     let val: libc::c_int = i32::from(reuseaddr);
-    syscall!(setsockopt(
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_REUSEADDR, val)
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+pub(crate) fn keepalive_time(socket: &net::TcpStream) -> io::Result<Duration> {
+    #[cfg(not(any(
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    )))]
+    const KEEPALIVE_TIME: libc::c_int = libc::TCP_KEEPIDLE;
+    #[cfg(any(
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    const KEEPALIVE_TIME: libc::c_int = libc::TCP_KEEPALIVE;
+    keepalive_secs(socket, KEEPALIVE_TIME)
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+pub(crate) fn keepalive_interval(socket: &net::TcpStream) -> io::Result<Duration> {
+    keepalive_secs(socket, libc::TCP_KEEPINTVL)
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+pub(crate) fn keepalive_retries(socket: &net::TcpStream) -> io::Result<u32> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_KEEPCNT)
+        .map(|retries| retries as u32)
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+fn keepalive_secs(socket: &net::TcpStream, opt: libc::c_int) -> io::Result<Duration> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::IPPROTO_TCP, opt)
+        .map(|secs| Duration::from_secs(secs as u64))
+}
+
+pub(crate) fn only_v6<S: AsRawFd>(socket: &S) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::IPPROTO_IPV6, libc::IPV6_V6ONLY)
+        .map(|only_v6| only_v6 != 0)
+}
+
+pub(crate) fn set_only_v6(socket: &net::TcpListener, only_v6: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(only_v6);
+    setsockopt(socket.as_raw_fd(), libc::IPPROTO_IPV6, libc::IPV6_V6ONLY, val)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_freebind(socket: &net::TcpListener, freebind: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(freebind);
+    setsockopt(socket.as_raw_fd(), libc::IPPROTO_IP, libc::IP_FREEBIND, val)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn freebind(socket: &net::TcpListener) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::IPPROTO_IP, libc::IP_FREEBIND)
+        .map(|freebind| freebind != 0)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_transparent(socket: &net::TcpListener, transparent: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(transparent);
+    setsockopt(socket.as_raw_fd(), libc::IPPROTO_IP, libc::IP_TRANSPARENT, val)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn transparent(socket: &net::TcpListener) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::IPPROTO_IP, libc::IP_TRANSPARENT)
+        .map(|transparent| transparent != 0)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn incoming_cpu(socket: &net::TcpStream) -> io::Result<i32> {
+    getsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_INCOMING_CPU)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_busy_poll(socket: &net::TcpStream, timeout: Duration) -> io::Result<()> {
+    let micros = timeout.as_micros().try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_BUSY_POLL, micros)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn busy_poll(socket: &net::TcpStream) -> io::Result<Duration> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_BUSY_POLL)
+        .map(|micros| Duration::from_micros(micros as u64))
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_zerocopy(socket: &net::TcpStream, zerocopy: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(zerocopy);
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_ZEROCOPY, val)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn zerocopy(socket: &net::TcpStream) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_ZEROCOPY)
+        .map(|zerocopy| zerocopy != 0)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn send_zerocopy(socket: &net::TcpStream, buf: &[u8]) -> io::Result<usize> {
+    // Mimick `libstd` and use `MSG_NOSIGNAL` to not raise `SIGPIPE`.
+    let flags = libc::MSG_ZEROCOPY | libc::MSG_NOSIGNAL;
+    syscall!(send(socket.as_raw_fd(), buf.as_ptr().cast(), buf.len(), flags)).map(|n| n as usize)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn zerocopy_completions(socket: &net::TcpStream) -> io::Result<Vec<ZcCompletion>> {
+    // Not (yet) defined in libc, see `linux/errqueue.h`.
+    const SO_EE_ORIGIN_ZEROCOPY: u8 = 5;
+    const SO_EE_CODE_ZEROCOPY_COPIED: u8 = 1;
+
+    let mut completions = Vec::new();
+    loop {
+        // Large enough for a single `sock_extended_err` with the offender's
+        // address, `u64` ensures the buffer is properly aligned.
+        let mut control = [0u64; 16];
+        // SAFETY: all zero is a valid `msghdr`.
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+        msg.msg_control = control.as_mut_ptr().cast();
+        msg.msg_controllen = mem::size_of_val(&control) as _;
+
+        match syscall!(recvmsg(socket.as_raw_fd(), &mut msg, libc::MSG_ERRQUEUE)) {
+            Ok(_) => {}
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => return Ok(completions),
+            Err(err) => return Err(err),
+        }
+
+        // SAFETY: `recvmsg` initialised the control messages in `msg`.
+        let mut cmsg = unsafe { libc::CMSG_FIRSTHDR(&msg) };
+        while !cmsg.is_null() {
+            // SAFETY: checked `cmsg` is not null above.
+            let (level, kind) = unsafe { ((*cmsg).cmsg_level, (*cmsg).cmsg_type) };
+            if (level == libc::SOL_IP && kind == libc::IP_RECVERR)
+                || (level == libc::SOL_IPV6 && kind == libc::IPV6_RECVERR)
+            {
+                // SAFETY: the kernel always passes a `sock_extended_err` for
+                // `IP_RECVERR` and `IPV6_RECVERR` control messages.
+                let err: libc::sock_extended_err =
+                    unsafe { libc::CMSG_DATA(cmsg).cast::<libc::sock_extended_err>().read_unaligned() };
+                if err.ee_origin == SO_EE_ORIGIN_ZEROCOPY && err.ee_errno == 0 {
+                    let copied = err.ee_code & SO_EE_CODE_ZEROCOPY_COPIED != 0;
+                    completions.push(ZcCompletion::new(err.ee_info, err.ee_data, copied));
+                }
+            }
+            // SAFETY: `cmsg` is a valid control message in `msg`.
+            cmsg = unsafe { libc::CMSG_NXTHDR(&msg, cmsg) };
+        }
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn available(socket: &net::TcpStream) -> io::Result<usize> {
+    crate::sys::unix::net::available(socket.as_raw_fd())
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[cfg(not(any(target_arch = "mips", target_arch = "mips64")))]
+const SIOCATMARK: libc::c_ulong = 0x8905;
+/// `_IOR('s', 7, int)`.
+#[cfg(any(
+    all(
+        any(target_os = "android", target_os = "linux"),
+        any(target_arch = "mips", target_arch = "mips64"),
+    ),
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+const SIOCATMARK: libc::c_ulong = 0x4004_7307;
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+pub(crate) fn at_mark(socket: &net::TcpStream) -> io::Result<bool> {
+    let mut at_mark: libc::c_int = 0;
+    syscall!(ioctl(socket.as_raw_fd(), SIOCATMARK as _, &mut at_mark))?;
+    Ok(at_mark != 0)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_recv_lowat(socket: &net::TcpStream, lowat: u32) -> io::Result<()> {
+    let lowat: libc::c_int = lowat.try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RCVLOWAT, lowat)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_lowat(socket: &net::TcpStream) -> io::Result<u32> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RCVLOWAT)
+        .map(|lowat| lowat as u32)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_defer_accept(socket: &net::TcpListener, timeout: Duration) -> io::Result<()> {
+    // Round up, a non-zero timeout below a second must not disable the option.
+    let secs = timeout.as_secs() + u64::from(timeout.subsec_nanos() != 0);
+    let secs: libc::c_int = secs.try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_DEFER_ACCEPT,
+        secs,
+    )
+}
+
+#[cfg(target_os = "freebsd")]
+pub(crate) fn set_defer_accept(socket: &net::TcpListener, timeout: Duration) -> io::Result<()> {
+    if timeout.is_zero() {
+        // Passing a null pointer removes the accept filter.
+        return syscall!(setsockopt(
+            socket.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_ACCEPTFILTER,
+            std::ptr::null(),
+            0,
+        ))
+        .map(|_| ());
+    }
+
+    // SAFETY: all zeroes is a valid `accept_filter_arg`.
+    let mut arg: libc::accept_filter_arg = unsafe { mem::zeroed() };
+    for (dst, src) in arg.af_name.iter_mut().zip(b"dataready") {
+        *dst = *src as libc::c_char;
+    }
+    setsockopt(
         socket.as_raw_fd(),
         libc::SOL_SOCKET,
-        libc::SO_REUSEADDR,
-        &val as *const libc::c_int as *const libc::c_void,
-        size_of::<libc::c_int>() as libc::socklen_t,
-    ))?;
-    Ok(())
+        libc::SO_ACCEPTFILTER,
+        arg,
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn defer_accept(socket: &net::TcpListener) -> io::Result<Duration> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_DEFER_ACCEPT)
+        .map(|secs| Duration::from_secs(secs as u64))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_cork(socket: &net::TcpStream, cork: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(cork);
+    setsockopt(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_CORK, val)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn cork(socket: &net::TcpStream) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_CORK)
+        .map(|cork| cork != 0)
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+pub(crate) fn send_file(
+    socket: &net::TcpStream,
+    file: RawFd,
+    offset: u64,
+    count: usize,
+) -> io::Result<usize> {
+    // On BSD-like systems a count of zero means sending until the end of the
+    // file, make it consistent with Linux instead.
+    if count == 0 {
+        return Ok(0);
+    }
+
+    let offset: libc::off_t = offset
+        .try_into()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "offset too large"))?;
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    {
+        let mut offset = offset;
+        syscall!(sendfile(socket.as_raw_fd(), file, &mut offset, count)).map(|n| n as usize)
+    }
+
+    // On BSD-like systems `sendfile` can partially send the data and still
+    // return `EAGAIN`, in which case the number of bytes sent is still set.
+    #[cfg(any(
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    {
+        let mut sent: libc::off_t = count.try_into().unwrap_or(libc::off_t::MAX);
+        let res = syscall!(sendfile(
+            file,
+            socket.as_raw_fd(),
+            offset,
+            &mut sent,
+            std::ptr::null_mut(),
+            0,
+        ));
+        partial_send(res, sent)
+    }
+
+    #[cfg(any(target_os = "dragonfly", target_os = "freebsd"))]
+    {
+        let mut sent: libc::off_t = 0;
+        let res = syscall!(sendfile(
+            file,
+            socket.as_raw_fd(),
+            offset,
+            count,
+            std::ptr::null_mut(),
+            &mut sent,
+            0,
+        ));
+        partial_send(res, sent)
+    }
+}
+
+#[cfg(any(
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+fn partial_send(res: io::Result<libc::c_int>, sent: libc::off_t) -> io::Result<usize> {
+    match res {
+        Ok(_) => Ok(sent as usize),
+        Err(ref err) if sent > 0 && err.kind() == io::ErrorKind::WouldBlock => Ok(sent as usize),
+        Err(err) => Err(err),
+    }
 }
 
 pub(crate) fn accept(listener: &net::TcpListener) -> io::Result<(net::TcpStream, SocketAddr)> {
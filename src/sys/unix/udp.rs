@@ -1,6 +1,21 @@
+#[cfg(unix)]
+use std::ffi::CString;
 use std::io;
-use std::mem;
+#[cfg(unix)]
+use std::io::{IoSlice, IoSliceMut};
+#[cfg(unix)]
+use std::net::Ipv4Addr;
 use std::net::{self, SocketAddr};
+#[cfg(target_os = "linux")]
+use std::net::{IpAddr, Ipv6Addr};
+#[cfg(target_os = "linux")]
+use std::time::{Duration, Instant, SystemTime};
+#[cfg(unix)]
+use std::mem;
+#[cfg(target_os = "linux")]
+use std::ptr;
+#[cfg(target_os = "linux")]
+use std::os::fd::{BorrowedFd, RawFd};
 #[cfg(not(target_os = "hermit"))]
 use std::os::fd::{AsRawFd, FromRawFd};
 // TODO: once <https://github.com/rust-lang/rust/issues/126198> is fixed this
@@ -8,7 +23,11 @@ use std::os::fd::{AsRawFd, FromRawFd};
 #[cfg(target_os = "hermit")]
 use std::os::hermit::io::{AsRawFd, FromRawFd};
 
-use crate::sys::unix::net::{new_ip_socket, socket_addr};
+#[cfg(target_os = "linux")]
+use crate::net::{HwTimestamp, RecvFlags, RecvFull, RecvMsg, SendFlags, SockFilter};
+#[cfg(unix)]
+use crate::sys::unix::net::{setsockopt, to_socket_addr};
+use crate::sys::unix::net::{getsockopt, new_ip_socket, socket_addr};
 
 pub fn bind(addr: SocketAddr) -> io::Result<net::UdpSocket> {
     let fd = new_ip_socket(addr, libc::SOCK_DGRAM)?;
@@ -21,16 +40,715 @@ pub fn bind(addr: SocketAddr) -> io::Result<net::UdpSocket> {
 }
 
 pub(crate) fn only_v6(socket: &net::UdpSocket) -> io::Result<bool> {
-    let mut optval: libc::c_int = 0;
-    let mut optlen = mem::size_of::<libc::c_int>() as libc::socklen_t;
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::IPPROTO_IPV6, libc::IPV6_V6ONLY)
+        .map(|only_v6| only_v6 != 0)
+}
+
+#[cfg(unix)]
+pub(crate) fn set_multicast_if_v4(socket: &net::UdpSocket, interface: Ipv4Addr) -> io::Result<()> {
+    let interface = libc::in_addr {
+        s_addr: u32::from_ne_bytes(interface.octets()),
+    };
+    setsockopt(
+        socket.as_raw_fd(),
+        libc::IPPROTO_IP,
+        libc::IP_MULTICAST_IF,
+        interface,
+    )
+}
+
+#[cfg(unix)]
+pub(crate) fn multicast_if_v4(socket: &net::UdpSocket) -> io::Result<Ipv4Addr> {
+    getsockopt::<libc::in_addr>(socket.as_raw_fd(), libc::IPPROTO_IP, libc::IP_MULTICAST_IF)
+        .map(|interface| Ipv4Addr::from(interface.s_addr.to_ne_bytes()))
+}
+
+#[cfg(unix)]
+pub(crate) fn set_multicast_hops_v6(socket: &net::UdpSocket, hops: u32) -> io::Result<()> {
+    let hops = hops as libc::c_int;
+    setsockopt(
+        socket.as_raw_fd(),
+        libc::IPPROTO_IPV6,
+        libc::IPV6_MULTICAST_HOPS,
+        hops,
+    )
+}
+
+#[cfg(unix)]
+pub(crate) fn multicast_hops_v6(socket: &net::UdpSocket) -> io::Result<u32> {
+    getsockopt::<libc::c_int>(
+        socket.as_raw_fd(),
+        libc::IPPROTO_IPV6,
+        libc::IPV6_MULTICAST_HOPS,
+    )
+    .map(|hops| hops as u32)
+}
 
-    syscall!(getsockopt(
+#[cfg(unix)]
+pub(crate) fn set_unicast_hops_v6(socket: &net::UdpSocket, hops: u32) -> io::Result<()> {
+    let hops = hops as libc::c_int;
+    setsockopt(
         socket.as_raw_fd(),
         libc::IPPROTO_IPV6,
-        libc::IPV6_V6ONLY,
-        &mut optval as *mut _ as *mut _,
-        &mut optlen,
+        libc::IPV6_UNICAST_HOPS,
+        hops,
+    )
+}
+
+#[cfg(unix)]
+pub(crate) fn unicast_hops_v6(socket: &net::UdpSocket) -> io::Result<u32> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::IPPROTO_IPV6, libc::IPV6_UNICAST_HOPS)
+        .map(|hops| hops as u32)
+}
+
+#[cfg(unix)]
+pub(crate) fn set_dont_route(socket: &net::UdpSocket, dont_route: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(dont_route);
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_DONTROUTE, val)
+}
+
+#[cfg(unix)]
+pub(crate) fn dont_route(socket: &net::UdpSocket) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_DONTROUTE)
+        .map(|dont_route| dont_route != 0)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_from_with_flags(
+    socket: &net::UdpSocket,
+    buf: &mut [u8],
+    flags: RecvFlags,
+) -> io::Result<(usize, SocketAddr)> {
+    let mut raw_flags = 0;
+    if flags.is_dont_wait() {
+        raw_flags |= libc::MSG_DONTWAIT;
+    }
+    recvfrom(socket, buf, raw_flags)
+}
+
+#[cfg(target_os = "linux")]
+fn recvfrom(
+    socket: &net::UdpSocket,
+    buf: &mut [u8],
+    flags: libc::c_int,
+) -> io::Result<(usize, SocketAddr)> {
+    // SAFETY: all zero is a valid `sockaddr_storage`.
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let mut length = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    let n = syscall!(recvfrom(
+        socket.as_raw_fd(),
+        buf.as_mut_ptr().cast(),
+        buf.len(),
+        flags,
+        (&mut storage as *mut libc::sockaddr_storage).cast(),
+        &mut length,
+    ))?;
+    // SAFETY: `recvfrom` initialised the address.
+    let addr = unsafe { to_socket_addr(&storage)? };
+    Ok((n as usize, addr))
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_from_trunc(
+    socket: &net::UdpSocket,
+    buf: &mut [u8],
+) -> io::Result<(usize, bool, SocketAddr)> {
+    let (n, flags, addr) = recvmsg_from(socket, &mut [IoSliceMut::new(buf)])?;
+    Ok((n, flags & libc::MSG_TRUNC != 0, addr))
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_from_vectored(
+    socket: &net::UdpSocket,
+    bufs: &mut [IoSliceMut<'_>],
+) -> io::Result<(usize, SocketAddr)> {
+    recvmsg_from(socket, bufs).map(|(n, _, addr)| (n, addr))
+}
+
+/// Calls `recvmsg(2)` reading into `bufs`, returning the number of bytes
+/// read, the message flags and the source address.
+#[cfg(unix)]
+fn recvmsg_from(
+    socket: &net::UdpSocket,
+    bufs: &mut [IoSliceMut<'_>],
+) -> io::Result<(usize, libc::c_int, SocketAddr)> {
+    // SAFETY: all zero is a valid `sockaddr_storage` and `msghdr`.
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_name = (&mut storage as *mut libc::sockaddr_storage).cast();
+    msg.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    // `IoSliceMut` is guaranteed to be ABI compatible with `iovec` on Unix.
+    msg.msg_iov = bufs.as_mut_ptr().cast();
+    msg.msg_iovlen = bufs.len().min(libc::c_int::MAX as usize) as _;
+
+    let n = syscall!(recvmsg(socket.as_raw_fd(), &mut msg, 0))?;
+    // SAFETY: `recvmsg` initialised the address.
+    let addr = unsafe { to_socket_addr(&storage)? };
+    Ok((n as usize, msg.msg_flags, addr))
+}
+
+#[cfg(unix)]
+pub(crate) fn send_to_vectored(
+    socket: &net::UdpSocket,
+    bufs: &[IoSlice<'_>],
+    target: SocketAddr,
+) -> io::Result<usize> {
+    let (raw_addr, raw_addr_length) = socket_addr(&target);
+    // SAFETY: all zero is a valid `msghdr`.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_name = raw_addr.as_ptr() as *mut libc::c_void;
+    msg.msg_namelen = raw_addr_length;
+    // `IoSlice` is guaranteed to be ABI compatible with `iovec` on Unix.
+    msg.msg_iov = bufs.as_ptr() as *mut libc::iovec;
+    msg.msg_iovlen = bufs.len().min(libc::c_int::MAX as usize) as _;
+
+    syscall!(sendmsg(socket.as_raw_fd(), &msg, 0)).map(|n| n as usize)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn send_to_with_flags(
+    socket: &net::UdpSocket,
+    buf: &[u8],
+    target: SocketAddr,
+    flags: SendFlags,
+) -> io::Result<usize> {
+    // Mimick `libstd` and use `MSG_NOSIGNAL` to not raise `SIGPIPE`.
+    let mut raw_flags = libc::MSG_NOSIGNAL;
+    if flags.is_confirm() {
+        raw_flags |= libc::MSG_CONFIRM;
+    }
+    if flags.is_dont_wait() {
+        raw_flags |= libc::MSG_DONTWAIT;
+    }
+    let (raw_addr, raw_addr_length) = socket_addr(&target);
+    syscall!(sendto(
+        socket.as_raw_fd(),
+        buf.as_ptr().cast(),
+        buf.len(),
+        raw_flags,
+        raw_addr.as_ptr(),
+        raw_addr_length,
+    ))
+    .map(|n| n as usize)
+}
+
+#[cfg(unix)]
+pub(crate) fn interface_index_by_name(name: &str) -> io::Result<u32> {
+    let name = CString::new(name).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "interface name contains a nul byte",
+        )
+    })?;
+    match unsafe { libc::if_nametoindex(name.as_ptr()) } {
+        0 => Err(io::Error::last_os_error()),
+        index => Ok(index),
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_busy_poll(socket: &net::UdpSocket, timeout: Duration) -> io::Result<()> {
+    let micros = timeout.as_micros().try_into().unwrap_or(libc::c_int::MAX);
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_BUSY_POLL, micros)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn busy_poll(socket: &net::UdpSocket) -> io::Result<Duration> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_BUSY_POLL)
+        .map(|micros| Duration::from_micros(micros as u64))
+}
+
+#[cfg(target_os = "linux")]
+fn sock_fprog(program: &[SockFilter]) -> io::Result<libc::sock_fprog> {
+    let len = program.len().try_into().map_err(|_| {
+        io::Error::new(io::ErrorKind::InvalidInput, "BPF program too long")
+    })?;
+    Ok(libc::sock_fprog {
+        len,
+        // `SockFilter` has the same layout as `sock_filter` and the kernel
+        // doesn't modify the program.
+        filter: program.as_ptr() as *mut libc::sock_filter,
+    })
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn attach_reuseport_cbpf(
+    socket: &net::UdpSocket,
+    program: &[SockFilter],
+) -> io::Result<()> {
+    setsockopt(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_ATTACH_REUSEPORT_CBPF,
+        sock_fprog(program)?,
+    )
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn attach_filter(socket: &net::UdpSocket, program: &[SockFilter]) -> io::Result<()> {
+    setsockopt(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_ATTACH_FILTER,
+        sock_fprog(program)?,
+    )
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn detach_filter(socket: &net::UdpSocket) -> io::Result<()> {
+    // The value is ignored by the kernel, but it must be present.
+    let value: libc::c_int = 0;
+    setsockopt(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_DETACH_FILTER,
+        value,
+    )
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn attach_reuseport_ebpf(
+    socket: &net::UdpSocket,
+    program: BorrowedFd<'_>,
+) -> io::Result<()> {
+    let program: RawFd = program.as_raw_fd();
+    setsockopt(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_ATTACH_REUSEPORT_EBPF,
+        program,
+    )
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_txtime(socket: &net::UdpSocket, clockid: i32, flags: u32) -> io::Result<()> {
+    let txtime = libc::sock_txtime { clockid, flags };
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_TXTIME, txtime)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn send_at(
+    socket: &net::UdpSocket,
+    buf: &[u8],
+    target: SocketAddr,
+    when: Instant,
+) -> io::Result<usize> {
+    // The transmit time must be in the clock used by the socket.
+    let txtime: libc::sock_txtime =
+        getsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_TXTIME)?;
+    let delay = when.saturating_duration_since(Instant::now());
+    let mut now = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    syscall!(clock_gettime(txtime.clockid, &mut now))?;
+    let txtime = (now.tv_sec as u64 * 1_000_000_000 + now.tv_nsec as u64)
+        .saturating_add(delay.as_nanos().try_into().unwrap_or(u64::MAX));
+
+    let (raw_addr, raw_addr_length) = socket_addr(&target);
+    let mut iov = libc::iovec {
+        iov_base: buf.as_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+    // Large enough for a single `u64` control message, `u64` ensures the
+    // buffer is properly aligned.
+    let mut control = [0u64; 4];
+    let control_len = unsafe { libc::CMSG_SPACE(mem::size_of::<u64>() as libc::c_uint) };
+    // SAFETY: all zero is a valid `msghdr`.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_name = raw_addr.as_ptr() as *mut libc::c_void;
+    msg.msg_namelen = raw_addr_length;
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = control_len as _;
+
+    // SAFETY: `msg_control` points to a buffer large enough for a single
+    // control message.
+    unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_TXTIME;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(mem::size_of::<u64>() as libc::c_uint) as _;
+        ptr::write_unaligned(libc::CMSG_DATA(cmsg).cast::<u64>(), txtime);
+    }
+
+    syscall!(sendmsg(socket.as_raw_fd(), &msg, 0)).map(|n| n as usize)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_recv_timestamp(socket: &net::UdpSocket, timestamp: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(timestamp);
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_TIMESTAMPNS, val)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_timestamp(socket: &net::UdpSocket) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_TIMESTAMPNS)
+        .map(|timestamp| timestamp != 0)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_recv_hw_timestamp(socket: &net::UdpSocket, timestamp: bool) -> io::Result<()> {
+    let val = if timestamp {
+        (libc::SOF_TIMESTAMPING_RX_HARDWARE | libc::SOF_TIMESTAMPING_RAW_HARDWARE) as libc::c_int
+    } else {
+        0
+    };
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_TIMESTAMPING, val)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_hw_timestamp(socket: &net::UdpSocket) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_TIMESTAMPING)
+        .map(|flags| flags as libc::c_uint & libc::SOF_TIMESTAMPING_RAW_HARDWARE != 0)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_recv_pktinfo(socket: &net::UdpSocket, pktinfo: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(pktinfo);
+    if socket.local_addr()?.is_ipv6() {
+        setsockopt(
+            socket.as_raw_fd(),
+            libc::IPPROTO_IPV6,
+            libc::IPV6_RECVPKTINFO,
+            val,
+        )
+    } else {
+        setsockopt(socket.as_raw_fd(), libc::IPPROTO_IP, libc::IP_PKTINFO, val)
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_pktinfo(socket: &net::UdpSocket) -> io::Result<bool> {
+    if socket.local_addr()?.is_ipv6() {
+        getsockopt::<libc::c_int>(
+            socket.as_raw_fd(),
+            libc::IPPROTO_IPV6,
+            libc::IPV6_RECVPKTINFO,
+        )
+    } else {
+        getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::IPPROTO_IP, libc::IP_PKTINFO)
+    }
+    .map(|pktinfo| pktinfo != 0)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_recv_ecn(socket: &net::UdpSocket, ecn: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(ecn);
+    if socket.local_addr()?.is_ipv6() {
+        setsockopt(
+            socket.as_raw_fd(),
+            libc::IPPROTO_IPV6,
+            libc::IPV6_RECVTCLASS,
+            val,
+        )
+    } else {
+        setsockopt(socket.as_raw_fd(), libc::IPPROTO_IP, libc::IP_RECVTOS, val)
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_ecn(socket: &net::UdpSocket) -> io::Result<bool> {
+    if socket.local_addr()?.is_ipv6() {
+        getsockopt::<libc::c_int>(
+            socket.as_raw_fd(),
+            libc::IPPROTO_IPV6,
+            libc::IPV6_RECVTCLASS,
+        )
+    } else {
+        getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::IPPROTO_IP, libc::IP_RECVTOS)
+    }
+    .map(|ecn| ecn != 0)
+}
+
+#[cfg(unix)]
+pub(crate) fn available(socket: &net::UdpSocket) -> io::Result<usize> {
+    crate::sys::unix::net::available(socket.as_raw_fd())
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_transparent(socket: &net::UdpSocket, transparent: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(transparent);
+    setsockopt(socket.as_raw_fd(), libc::IPPROTO_IP, libc::IP_TRANSPARENT, val)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn transparent(socket: &net::UdpSocket) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::IPPROTO_IP, libc::IP_TRANSPARENT)
+        .map(|transparent| transparent != 0)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_recv_orig_dst_addr(socket: &net::UdpSocket, orig_dst_addr: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(orig_dst_addr);
+    if socket.local_addr()?.is_ipv6() {
+        setsockopt(
+            socket.as_raw_fd(),
+            libc::IPPROTO_IPV6,
+            libc::IPV6_RECVORIGDSTADDR,
+            val,
+        )
+    } else {
+        setsockopt(
+            socket.as_raw_fd(),
+            libc::IPPROTO_IP,
+            libc::IP_RECVORIGDSTADDR,
+            val,
+        )
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_orig_dst_addr(socket: &net::UdpSocket) -> io::Result<bool> {
+    if socket.local_addr()?.is_ipv6() {
+        getsockopt::<libc::c_int>(
+            socket.as_raw_fd(),
+            libc::IPPROTO_IPV6,
+            libc::IPV6_RECVORIGDSTADDR,
+        )
+    } else {
+        getsockopt::<libc::c_int>(
+            socket.as_raw_fd(),
+            libc::IPPROTO_IP,
+            libc::IP_RECVORIGDSTADDR,
+        )
+    }
+    .map(|orig_dst_addr| orig_dst_addr != 0)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_full(socket: &net::UdpSocket, buf: &mut [u8]) -> io::Result<RecvFull> {
+    // Large enough for `SCM_TIMESTAMPING` (three `timespec`s),
+    // `IPV6_PKTINFO`, `IPV6_TCLASS` and `IPV6_ORIGDSTADDR` control messages,
+    // `u64` ensures the buffer is properly aligned.
+    type Control = [u64; 32];
+
+    // SAFETY: all zero is a valid `sockaddr_storage` and `msghdr`.
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let mut control: Control = [0; 32];
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr().cast(),
+        iov_len: buf.len(),
+    };
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_name = (&mut storage as *mut libc::sockaddr_storage).cast();
+    msg.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = mem::size_of::<Control>() as _;
+
+    let n = syscall!(recvmsg(socket.as_raw_fd(), &mut msg, 0))?;
+    // SAFETY: `recvmsg` initialised the address and control messages.
+    let addr = unsafe { to_socket_addr(&storage)? };
+    let mut recv = RecvFull {
+        len: n as usize,
+        addr,
+        dst_addr: None,
+        orig_dst_addr: None,
+        interface: None,
+        ecn: None,
+        timestamp: unsafe { recv_timestamp_cmsg(&msg) },
+        hw_timestamp: None,
+    };
+
+    // SAFETY: `recvmsg` initialised the control messages.
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+        while !cmsg.is_null() {
+            let data = libc::CMSG_DATA(cmsg);
+            match ((*cmsg).cmsg_level, (*cmsg).cmsg_type) {
+                (libc::IPPROTO_IP, libc::IP_PKTINFO) => {
+                    let info = data.cast::<libc::in_pktinfo>().read_unaligned();
+                    recv.dst_addr = Some(IpAddr::V4(Ipv4Addr::from(
+                        info.ipi_addr.s_addr.to_ne_bytes(),
+                    )));
+                    recv.interface = Some(info.ipi_ifindex as u32);
+                }
+                (libc::IPPROTO_IPV6, libc::IPV6_PKTINFO) => {
+                    let info = data.cast::<libc::in6_pktinfo>().read_unaligned();
+                    recv.dst_addr = Some(IpAddr::V6(Ipv6Addr::from(info.ipi6_addr.s6_addr)));
+                    recv.interface = Some(info.ipi6_ifindex);
+                }
+                // A `sockaddr_in` or `sockaddr_in6`.
+                (libc::IPPROTO_IP, libc::IP_ORIGDSTADDR)
+                | (libc::IPPROTO_IPV6, libc::IPV6_ORIGDSTADDR) => {
+                    let mut orig: libc::sockaddr_storage = mem::zeroed();
+                    let len = ((*cmsg).cmsg_len as usize - libc::CMSG_LEN(0) as usize)
+                        .min(mem::size_of::<libc::sockaddr_storage>());
+                    ptr::copy_nonoverlapping(data, (&mut orig as *mut libc::sockaddr_storage).cast(), len);
+                    recv.orig_dst_addr = to_socket_addr(&orig).ok();
+                }
+                // The TOS is passed as a single byte.
+                (libc::IPPROTO_IP, libc::IP_TOS) => recv.ecn = Some(data.read() & 0b11),
+                (libc::IPPROTO_IPV6, libc::IPV6_TCLASS) => {
+                    let tclass = data.cast::<libc::c_int>().read_unaligned();
+                    recv.ecn = Some(tclass as u8 & 0b11);
+                }
+                // Three `timespec`s: software, deprecated hardware converted to
+                // system time and raw hardware timestamp.
+                (libc::SOL_SOCKET, libc::SCM_TIMESTAMPING) => {
+                    let ts = data.cast::<[libc::timespec; 3]>().read_unaligned();
+                    let to_duration =
+                        |ts: libc::timespec| Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32);
+                    let raw = to_duration(ts[2]);
+                    if !raw.is_zero() {
+                        let system = Some(to_duration(ts[1]))
+                            .filter(|time| !time.is_zero())
+                            .map(|time| SystemTime::UNIX_EPOCH + time);
+                        recv.hw_timestamp = Some(HwTimestamp { raw, system });
+                    }
+                }
+                _ => {}
+            }
+            cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+        }
+    }
+    Ok(recv)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn recv_mmsg(socket: &net::UdpSocket, bufs: &mut [&mut [u8]]) -> io::Result<Vec<RecvMsg>> {
+    // Large enough for a single `SCM_TIMESTAMPING` control message (three
+    // `timespec`s), `u64` ensures the buffer is properly aligned.
+    type Control = [u64; 8];
+
+    let vlen = bufs.len().min(libc::c_uint::MAX as usize);
+    // SAFETY: all zero is a valid `sockaddr_storage`.
+    let mut storages: Vec<libc::sockaddr_storage> = vec![unsafe { mem::zeroed() }; vlen];
+    let mut controls: Vec<Control> = vec![[0; 8]; vlen];
+    let mut iovs: Vec<libc::iovec> = bufs[..vlen]
+        .iter_mut()
+        .map(|buf| libc::iovec {
+            iov_base: buf.as_mut_ptr().cast(),
+            iov_len: buf.len(),
+        })
+        .collect();
+    let mut msgs: Vec<libc::mmsghdr> = (0..vlen)
+        .map(|i| {
+            // SAFETY: all zero is a valid `mmsghdr`.
+            let mut msg: libc::mmsghdr = unsafe { mem::zeroed() };
+            msg.msg_hdr.msg_name = (&mut storages[i] as *mut libc::sockaddr_storage).cast();
+            msg.msg_hdr.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+            msg.msg_hdr.msg_iov = &mut iovs[i];
+            msg.msg_hdr.msg_iovlen = 1;
+            msg.msg_hdr.msg_control = controls[i].as_mut_ptr().cast();
+            msg.msg_hdr.msg_controllen = mem::size_of::<Control>() as _;
+            msg
+        })
+        .collect();
+
+    let n = syscall!(recvmmsg(
+        socket.as_raw_fd(),
+        msgs.as_mut_ptr(),
+        vlen as libc::c_uint,
+        0,
+        ptr::null_mut(),
     ))?;
 
-    Ok(optval != 0)
+    msgs[..n as usize]
+        .iter()
+        .zip(storages.iter())
+        .map(|(msg, storage)| {
+            // SAFETY: `recvmmsg` initialised the address and control messages.
+            let addr = unsafe { to_socket_addr(storage)? };
+            let timestamp = unsafe { recv_timestamp_cmsg(&msg.msg_hdr) };
+            Ok(RecvMsg::new(msg.msg_len as usize, addr, timestamp))
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn send_batch_segmented(
+    socket: &net::UdpSocket,
+    msgs: &[(&[u8], u16, SocketAddr)],
+) -> io::Result<usize> {
+    // Large enough for a single `UDP_SEGMENT` control message (an `u16`),
+    // `u64` ensures the buffer is properly aligned.
+    type Control = [u64; 4];
+
+    let vlen = msgs.len().min(libc::c_uint::MAX as usize);
+    let addrs: Vec<_> = msgs[..vlen]
+        .iter()
+        .map(|(_, _, addr)| socket_addr(addr))
+        .collect();
+    let mut controls: Vec<Control> = vec![[0; 4]; vlen];
+    let mut iovs: Vec<libc::iovec> = msgs[..vlen]
+        .iter()
+        .map(|(buf, _, _)| libc::iovec {
+            iov_base: buf.as_ptr() as *mut libc::c_void,
+            iov_len: buf.len(),
+        })
+        .collect();
+    let mut hdrs: Vec<libc::mmsghdr> = (0..vlen)
+        .map(|i| {
+            // SAFETY: all zero is a valid `mmsghdr`.
+            let mut hdr: libc::mmsghdr = unsafe { mem::zeroed() };
+            hdr.msg_hdr.msg_name = addrs[i].0.as_ptr() as *mut libc::c_void;
+            hdr.msg_hdr.msg_namelen = addrs[i].1;
+            hdr.msg_hdr.msg_iov = &mut iovs[i];
+            hdr.msg_hdr.msg_iovlen = 1;
+            hdr.msg_hdr.msg_control = controls[i].as_mut_ptr().cast();
+            // SAFETY: `CMSG_SPACE` is always safe to call.
+            hdr.msg_hdr.msg_controllen =
+                unsafe { libc::CMSG_SPACE(mem::size_of::<u16>() as _) } as _;
+            // SAFETY: the control buffer is large enough for a single
+            // `UDP_SEGMENT` control message.
+            unsafe {
+                let cmsg = libc::CMSG_FIRSTHDR(&hdr.msg_hdr);
+                (*cmsg).cmsg_level = libc::SOL_UDP;
+                (*cmsg).cmsg_type = libc::UDP_SEGMENT;
+                (*cmsg).cmsg_len = libc::CMSG_LEN(mem::size_of::<u16>() as _) as _;
+                libc::CMSG_DATA(cmsg).cast::<u16>().write_unaligned(msgs[i].1);
+            }
+            hdr
+        })
+        .collect();
+
+    // Mimick `libstd` and use `MSG_NOSIGNAL` to not raise `SIGPIPE`.
+    syscall!(sendmmsg(
+        socket.as_raw_fd(),
+        hdrs.as_mut_ptr(),
+        vlen as libc::c_uint,
+        libc::MSG_NOSIGNAL,
+    ))
+    .map(|n| n as usize)
+}
+
+/// Returns the software receive timestamp from the control messages in `msg`.
+///
+/// # Safety
+///
+/// The control messages in `msg` must be initialised.
+#[cfg(target_os = "linux")]
+unsafe fn recv_timestamp_cmsg(msg: &libc::msghdr) -> Option<SystemTime> {
+    // Not defined in libc, but the same as `SO_TIMESTAMP`.
+    const SCM_TIMESTAMP: libc::c_int = libc::SO_TIMESTAMP;
+
+    let mut cmsg = libc::CMSG_FIRSTHDR(msg);
+    while !cmsg.is_null() {
+        if (*cmsg).cmsg_level == libc::SOL_SOCKET {
+            let data = libc::CMSG_DATA(cmsg);
+            let time = match (*cmsg).cmsg_type {
+                libc::SCM_TIMESTAMPNS | libc::SCM_TIMESTAMPING => {
+                    // `SCM_TIMESTAMPING` passes three `timespec`s, the first
+                    // of which is the software timestamp.
+                    let ts = data.cast::<libc::timespec>().read_unaligned();
+                    Some(Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32))
+                }
+                SCM_TIMESTAMP => {
+                    let tv = data.cast::<libc::timeval>().read_unaligned();
+                    Some(Duration::new(tv.tv_sec as u64, tv.tv_usec as u32 * 1000))
+                }
+                _ => None,
+            };
+            // `SCM_TIMESTAMPING` uses all zeros for timestamps that aren't set.
+            if let Some(time) = time.filter(|time| !time.is_zero()) {
+                return Some(SystemTime::UNIX_EPOCH + time);
+            }
+        }
+        cmsg = libc::CMSG_NXTHDR(msg, cmsg);
+    }
+    None
 }
@@ -1,9 +1,9 @@
-use std::io;
 use std::os::fd::{AsRawFd, FromRawFd};
 use std::os::unix::net::{self, SocketAddr};
+use std::{io, mem};
 
 use crate::sys::unix::net::new_socket;
-use crate::sys::unix::uds::unix_addr;
+use crate::sys::unix::uds::{socket_addr, unix_addr};
 
 pub(crate) fn bind_addr(address: &SocketAddr) -> io::Result<net::UnixDatagram> {
     let socket = unbound()?;
@@ -23,3 +23,33 @@ pub(crate) fn unbound() -> io::Result<net::UnixDatagram> {
 pub(crate) fn pair() -> io::Result<(net::UnixDatagram, net::UnixDatagram)> {
     super::pair(libc::SOCK_DGRAM)
 }
+
+pub(crate) fn peek(socket: &net::UnixDatagram, buf: &mut [u8]) -> io::Result<usize> {
+    syscall!(recv(
+        socket.as_raw_fd(),
+        buf.as_mut_ptr().cast(),
+        buf.len(),
+        libc::MSG_PEEK,
+    ))
+    .map(|n| n as usize)
+}
+
+pub(crate) fn peek_from(
+    socket: &net::UnixDatagram,
+    buf: &mut [u8],
+) -> io::Result<(usize, SocketAddr)> {
+    // SAFETY: `libc::sockaddr_un` zero filled is properly initialized, see
+    // `listener::accept`.
+    let mut sockaddr = unsafe { mem::zeroed::<libc::sockaddr_un>() };
+    let mut socklen = mem::size_of_val(&sockaddr) as libc::socklen_t;
+    let n = syscall!(recvfrom(
+        socket.as_raw_fd(),
+        buf.as_mut_ptr().cast(),
+        buf.len(),
+        libc::MSG_PEEK,
+        &mut sockaddr as *mut libc::sockaddr_un as *mut libc::sockaddr,
+        &mut socklen,
+    ))?;
+    let address = socket_addr(&sockaddr, socklen)?;
+    Ok((n as usize, address))
+}
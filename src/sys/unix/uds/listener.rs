@@ -1,13 +1,10 @@
-use std::ffi::OsStr;
 use std::os::fd::{AsRawFd, FromRawFd};
-use std::os::unix::ffi::OsStrExt;
 use std::os::unix::net::{self, SocketAddr};
-use std::path::Path;
 use std::{io, mem};
 
 use crate::net::UnixStream;
 use crate::sys::unix::net::new_socket;
-use crate::sys::unix::uds::{path_offset, unix_addr};
+use crate::sys::unix::uds::{socket_addr, unix_addr};
 
 pub(crate) fn bind_addr(address: &SocketAddr) -> io::Result<net::UnixListener> {
     let fd = new_socket(libc::AF_UNIX, libc::SOCK_STREAM)?;
@@ -101,17 +98,6 @@ pub(crate) fn accept(listener: &net::UnixListener) -> io::Result<(UnixStream, So
     });
 
     let socket = socket.map(UnixStream::from_std)?;
-
-    #[allow(unused_mut)] // See below.
-    let mut path_len = socklen as usize - path_offset(&sockaddr);
-    // On FreeBSD and Darwin, it returns a length of 14/16, but an unnamed (all
-    // zero) address. Map that to a length of 0 to match other OS.
-    if sockaddr.sun_path[0] == 0 {
-        path_len = 0;
-    }
-    let address = SocketAddr::from_pathname(Path::new(OsStr::from_bytes(unsafe {
-        // SAFETY: going from i8 to u8 is fine in this context.
-        &*(&sockaddr.sun_path[..path_len] as *const [libc::c_char] as *const [u8])
-    })))?;
+    let address = socket_addr(&sockaddr, socklen)?;
     Ok((socket, address))
 }
@@ -2,11 +2,22 @@
 use std::os::android::net::SocketAddrExt;
 #[cfg(target_os = "linux")]
 use std::os::linux::net::SocketAddrExt;
+use std::ffi::OsStr;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::io::{IoSlice, IoSliceMut};
 use std::os::unix::ffi::OsStrExt;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::os::unix::io::AsRawFd;
 use std::os::unix::io::FromRawFd;
 use std::os::unix::net::SocketAddr;
+use std::path::Path;
 use std::{io, mem, ptr};
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::UCred;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::sys::unix::net::{getsockopt, setsockopt};
+
 pub(crate) mod datagram;
 pub(crate) mod listener;
 pub(crate) mod stream;
@@ -81,6 +92,31 @@ fn unix_addr(address: &SocketAddr) -> (libc::sockaddr_un, libc::socklen_t) {
     (sockaddr, addrlen as _)
 }
 
+/// Converts the system representation, as returned by e.g. `accept(2)`, into a
+/// Rust `SocketAddr`.
+fn socket_addr(sockaddr: &libc::sockaddr_un, socklen: libc::socklen_t) -> io::Result<SocketAddr> {
+    // Some OSs return a length of zero for unnamed addresses.
+    #[allow(unused_mut)] // See below.
+    let mut path_len = (socklen as usize).saturating_sub(path_offset(sockaddr));
+    if sockaddr.sun_path[0] == 0 {
+        // Abstract names start with a null byte, the remainder is the name.
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        if path_len > 0 {
+            return SocketAddr::from_abstract_name(unsafe {
+                // SAFETY: going from i8 to u8 is fine in this context.
+                &*(&sockaddr.sun_path[1..path_len] as *const [libc::c_char] as *const [u8])
+            });
+        }
+        // On FreeBSD and Darwin, it returns a length of 14/16, but an unnamed
+        // (all zero) address. Map that to a length of 0 to match other OS.
+        path_len = 0;
+    }
+    SocketAddr::from_pathname(Path::new(OsStr::from_bytes(unsafe {
+        // SAFETY: going from i8 to u8 is fine in this context.
+        &*(&sockaddr.sun_path[..path_len] as *const [libc::c_char] as *const [u8])
+    })))
+}
+
 fn pair<T>(flags: libc::c_int) -> io::Result<(T, T)>
 where
     T: FromRawFd,
@@ -133,6 +169,114 @@ where
     Ok(pair)
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn current_creds() -> UCred {
+    // SAFETY: these functions are always successful.
+    unsafe {
+        UCred {
+            pid: libc::getpid(),
+            uid: libc::getuid(),
+            gid: libc::getgid(),
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_passcred<S: AsRawFd>(socket: &S, passcred: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(passcred);
+    setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_PASSCRED, val)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn passcred<S: AsRawFd>(socket: &S) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_PASSCRED)
+        .map(|passcred| passcred != 0)
+}
+
+/// Large enough for a single `SCM_CREDENTIALS` control message, `u64`
+/// ensures the buffer is properly aligned.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+type CredsControl = [u64; 4];
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_with_creds<S: AsRawFd>(
+    socket: &S,
+    bufs: &[IoSlice<'_>],
+    creds: &UCred,
+) -> io::Result<usize> {
+    let mut control: CredsControl = [0; 4];
+    // SAFETY: all zero is a valid `msghdr`.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    // `IoSlice` is guaranteed to be ABI compatible with `iovec` on Unix.
+    msg.msg_iov = bufs.as_ptr() as *mut libc::iovec;
+    msg.msg_iovlen = bufs.len().min(libc::c_int::MAX as usize) as _;
+    msg.msg_control = control.as_mut_ptr().cast();
+    // SAFETY: `CMSG_SPACE` is always safe to call.
+    msg.msg_controllen =
+        unsafe { libc::CMSG_SPACE(mem::size_of::<libc::ucred>() as libc::c_uint) } as _;
+
+    let ucred = libc::ucred {
+        pid: creds.pid,
+        uid: creds.uid,
+        gid: creds.gid,
+    };
+    // SAFETY: the control buffer is large enough for a single control message
+    // holding a `ucred`.
+    unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_CREDENTIALS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(mem::size_of::<libc::ucred>() as libc::c_uint) as _;
+        ptr::write_unaligned(libc::CMSG_DATA(cmsg).cast::<libc::ucred>(), ucred);
+    }
+
+    // Mimick `libstd` and use `MSG_NOSIGNAL` to not raise `SIGPIPE`.
+    syscall!(sendmsg(
+        socket.as_raw_fd(),
+        &msg,
+        libc::MSG_NOSIGNAL
+    ))
+    .map(|n| n as usize)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_with_creds<S: AsRawFd>(
+    socket: &S,
+    bufs: &mut [IoSliceMut<'_>],
+) -> io::Result<(usize, Option<UCred>)> {
+    let mut control: CredsControl = [0; 4];
+    // SAFETY: all zero is a valid `msghdr`.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    // `IoSliceMut` is guaranteed to be ABI compatible with `iovec` on Unix.
+    msg.msg_iov = bufs.as_mut_ptr().cast();
+    msg.msg_iovlen = bufs.len().min(libc::c_int::MAX as usize) as _;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = mem::size_of::<CredsControl>() as _;
+
+    let n = syscall!(recvmsg(socket.as_raw_fd(), &mut msg, 0))?;
+
+    let mut creds = None;
+    // SAFETY: `recvmsg` initialised the control messages.
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+        while !cmsg.is_null() {
+            if (*cmsg).cmsg_level == libc::SOL_SOCKET && (*cmsg).cmsg_type == libc::SCM_CREDENTIALS
+            {
+                let ucred = libc::CMSG_DATA(cmsg)
+                    .cast::<libc::ucred>()
+                    .read_unaligned();
+                creds = Some(UCred {
+                    pid: ucred.pid,
+                    uid: ucred.uid,
+                    gid: ucred.gid,
+                });
+            }
+            cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+        }
+    }
+    Ok((n as usize, creds))
+}
+
 #[cfg(test)]
 mod tests {
     use std::os::unix::net::SocketAddr;
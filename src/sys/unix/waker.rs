@@ -3,6 +3,7 @@
     not(all(
         not(mio_unsupported_force_waker_pipe),
         any(
+            target_os = "dragonfly",
             target_os = "freebsd",
             target_os = "ios",
             target_os = "macos",
@@ -12,6 +13,7 @@
         )
     )),
     not(any(
+        target_os = "aix",
         target_os = "espidf",
         target_os = "haiku",
         target_os = "hermit",
@@ -31,8 +33,6 @@ mod fdbased {
     use crate::sys::unix::waker::eventfd::WakerInternal;
     #[cfg(any(
         mio_unsupported_force_waker_pipe,
-        target_os = "aix",
-        target_os = "dragonfly",
         target_os = "illumos",
         target_os = "netbsd",
         target_os = "openbsd",
@@ -65,6 +65,7 @@ mod fdbased {
     not(all(
         not(mio_unsupported_force_waker_pipe),
         any(
+            target_os = "dragonfly",
             target_os = "freebsd",
             target_os = "ios",
             target_os = "macos",
@@ -74,6 +75,7 @@ mod fdbased {
         )
     )),
     not(any(
+        target_os = "aix",
         target_os = "espidf",
         target_os = "haiku",
         target_os = "hermit",
@@ -185,6 +187,7 @@ pub(crate) use self::eventfd::WakerInternal;
 #[cfg(all(
     not(mio_unsupported_force_waker_pipe),
     any(
+        target_os = "dragonfly",
         target_os = "freebsd",
         target_os = "ios",
         target_os = "macos",
@@ -227,6 +230,7 @@ mod kqueue {
 #[cfg(all(
     not(mio_unsupported_force_waker_pipe),
     any(
+        target_os = "dragonfly",
         target_os = "freebsd",
         target_os = "ios",
         target_os = "macos",
@@ -240,7 +244,6 @@ pub use self::kqueue::Waker;
 #[cfg(any(
     mio_unsupported_force_waker_pipe,
     target_os = "aix",
-    target_os = "dragonfly",
     target_os = "haiku",
     target_os = "illumos",
     target_os = "netbsd",
@@ -296,6 +299,7 @@ mod pipe {
 
         #[cfg(any(
             mio_unsupported_force_poll_poll,
+            target_os = "aix",
             target_os = "espidf",
             target_os = "haiku",
             target_os = "nto",
@@ -331,14 +335,13 @@ mod pipe {
         mio_unsupported_force_poll_poll,
         any(
             mio_unsupported_force_waker_pipe,
-            target_os = "aix",
-            target_os = "dragonfly",
             target_os = "illumos",
             target_os = "netbsd",
             target_os = "openbsd",
             target_os = "redox",
         )
     ),
+    target_os = "aix",
     target_os = "haiku",
     target_os = "nto",
     target_os = "solaris",
@@ -348,6 +351,7 @@ pub(crate) use self::pipe::WakerInternal;
 
 #[cfg(any(
     mio_unsupported_force_poll_poll,
+    target_os = "aix",
     target_os = "espidf",
     target_os = "haiku",
     target_os = "hermit",
@@ -382,6 +386,7 @@ mod poll {
 
 #[cfg(any(
     mio_unsupported_force_poll_poll,
+    target_os = "aix",
     target_os = "espidf",
     target_os = "haiku",
     target_os = "hermit",
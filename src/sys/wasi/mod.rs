@@ -48,11 +48,11 @@ pub(crate) struct Selector {
 }
 
 impl Selector {
-    pub(crate) fn new() -> io::Result<Selector> {
+    pub(crate) fn new(capacity: usize) -> io::Result<Selector> {
         Ok(Selector {
             #[cfg(all(debug_assertions, feature = "net"))]
             id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
-            subscriptions: Arc::new(Mutex::new(Vec::new())),
+            subscriptions: Arc::new(Mutex::new(Vec::with_capacity(capacity))),
         })
     }
 
@@ -261,6 +261,8 @@ pub(crate) mod event {
     use std::fmt;
 
     use crate::sys::Event;
+    #[cfg(feature = "test-util")]
+    use crate::test::Readiness;
     use crate::Token;
 
     pub(crate) fn token(event: &Event) -> Token {
@@ -282,6 +284,16 @@ pub(crate) mod event {
         false
     }
 
+    pub(crate) fn is_hup(_: &Event) -> bool {
+        // Not supported, hangups are reported per read or write subscription.
+        false
+    }
+
+    pub(crate) fn is_read_hup(event: &Event) -> bool {
+        // Not distinguished from the read half being closed.
+        is_read_closed(event)
+    }
+
     pub(crate) fn is_read_closed(event: &Event) -> bool {
         event.type_ == wasi::EVENTTYPE_FD_READ
             // Safety: checked the type of the union above.
@@ -309,6 +321,39 @@ pub(crate) mod event {
         false
     }
 
+    pub(crate) fn read_data(event: &Event) -> Option<usize> {
+        if event.type_ == wasi::EVENTTYPE_FD_READ {
+            Some(event.fd_readwrite.nbytes as usize)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "test-util")]
+    pub(crate) fn push(events: &mut crate::sys::Events, token: Token, readiness: Readiness) {
+        // WASI uses a separate event per type, so readable and writable
+        // readiness are returned as two events.
+        let event = |type_, hangup| Event {
+            userdata: token.0 as wasi::Userdata,
+            error: wasi::ERRNO_SUCCESS,
+            type_,
+            fd_readwrite: wasi::EventFdReadwrite {
+                nbytes: 0,
+                flags: if hangup {
+                    wasi::EVENTRWFLAGS_FD_READWRITE_HANGUP
+                } else {
+                    0
+                },
+            },
+        };
+        if readiness.is_readable() || readiness.is_read_closed() {
+            events.push(event(wasi::EVENTTYPE_FD_READ, readiness.is_read_closed()));
+        }
+        if readiness.is_writable() || readiness.is_write_closed() {
+            events.push(event(wasi::EVENTTYPE_FD_WRITE, readiness.is_write_closed()));
+        }
+    }
+
     pub(crate) fn debug_details(f: &mut fmt::Formatter<'_>, event: &Event) -> fmt::Result {
         debug_detail!(
             TypeDetails(wasi::Eventtype),
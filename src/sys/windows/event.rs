@@ -2,6 +2,8 @@ use std::fmt;
 
 use super::afd;
 use super::iocp::CompletionStatus;
+#[cfg(feature = "test-util")]
+use crate::test::Readiness;
 use crate::Token;
 
 #[derive(Clone)]
@@ -74,6 +76,14 @@ pub fn is_error(event: &Event) -> bool {
     event.flags & ERROR_FLAGS != 0
 }
 
+pub fn is_hup(event: &Event) -> bool {
+    event.flags & afd::POLL_ABORT != 0
+}
+
+pub fn is_read_hup(event: &Event) -> bool {
+    event.flags & afd::POLL_DISCONNECT != 0
+}
+
 pub fn is_read_closed(event: &Event) -> bool {
     event.flags & READ_CLOSED_FLAGS != 0
 }
@@ -96,6 +106,35 @@ pub fn is_lio(_: &Event) -> bool {
     false
 }
 
+pub fn read_data(_: &Event) -> Option<usize> {
+    // Not supported.
+    None
+}
+
+#[cfg(feature = "test-util")]
+pub fn push(events: &mut Events, token: Token, readiness: Readiness) {
+    let mut event = Event::new(token);
+    if readiness.is_readable() {
+        event.flags |= afd::POLL_RECEIVE;
+    }
+    if readiness.is_writable() {
+        event.flags |= afd::POLL_SEND;
+    }
+    if readiness.is_error() {
+        event.flags |= afd::POLL_CONNECT_FAIL;
+    }
+    if readiness.is_read_closed() {
+        event.flags |= afd::POLL_DISCONNECT;
+    }
+    if readiness.is_write_closed() {
+        event.flags |= afd::POLL_ABORT;
+    }
+    if readiness.is_priority() {
+        event.flags |= afd::POLL_RECEIVE_EXPEDITED;
+    }
+    events.events.push(event);
+}
+
 pub fn debug_details(f: &mut fmt::Formatter<'_>, event: &Event) -> fmt::Result {
     #[allow(clippy::trivially_copy_pass_by_ref)]
     fn check_flags(got: &u32, want: &u32) -> bool {
@@ -144,6 +183,14 @@ impl Events {
         }
     }
 
+    pub fn reserve(&mut self, additional: usize) {
+        self.events.reserve(additional);
+        let cap = self.events.capacity();
+        if self.statuses.len() < cap {
+            self.statuses = vec![CompletionStatus::zero(); cap].into_boxed_slice();
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.events.is_empty()
     }
@@ -160,6 +207,17 @@ impl Events {
         self.events.get(idx)
     }
 
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&Event) -> bool,
+    {
+        self.events.retain(f);
+    }
+
+    pub fn rotate_left(&mut self, mid: usize) {
+        self.events.rotate_left(mid);
+    }
+
     pub fn clear(&mut self) {
         self.events.clear();
         for status in self.statuses.iter_mut() {
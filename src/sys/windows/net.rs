@@ -4,9 +4,9 @@ use std::net::SocketAddr;
 use std::sync::Once;
 
 use windows_sys::Win32::Networking::WinSock::{
-    closesocket, ioctlsocket, socket, AF_INET, AF_INET6, FIONBIO, IN6_ADDR, IN6_ADDR_0,
-    INVALID_SOCKET, IN_ADDR, IN_ADDR_0, SOCKADDR, SOCKADDR_IN, SOCKADDR_IN6, SOCKADDR_IN6_0,
-    SOCKET,
+    closesocket, ioctlsocket, socket, AF_INET, AF_INET6, FIONBIO, FIONREAD, IN6_ADDR,
+    IN6_ADDR_0, INVALID_SOCKET, IN_ADDR, IN_ADDR_0, SOCKADDR, SOCKADDR_IN, SOCKADDR_IN6,
+    SOCKADDR_IN6_0, SOCKET,
 };
 
 /// Initialise the network stack for Windows.
@@ -47,6 +47,18 @@ pub(crate) fn new_socket(domain: u32, socket_type: i32) -> io::Result<SOCKET> {
     Ok(socket as SOCKET)
 }
 
+/// Returns the number of bytes available to read from `socket`, using the
+/// `FIONREAD` ioctl.
+pub(crate) fn available(socket: SOCKET) -> io::Result<usize> {
+    let mut available: u32 = 0;
+    syscall!(
+        ioctlsocket(socket, FIONREAD, &mut available),
+        PartialEq::ne,
+        0
+    )?;
+    Ok(available as usize)
+}
+
 /// A type with the same memory layout as `SOCKADDR`. Used in converting Rust level
 /// SocketAddr* types into their system representation. The benefit of this specific
 /// type over using `SOCKADDR_STORAGE` is that this type is exactly as large as it
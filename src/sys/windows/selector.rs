@@ -11,6 +11,7 @@ cfg_net! {
 }
 
 use super::iocp::{CompletionPort, CompletionStatus};
+use std::cmp;
 use std::collections::VecDeque;
 use std::ffi::c_void;
 use std::io;
@@ -331,8 +332,8 @@ pub struct Selector {
 }
 
 impl Selector {
-    pub fn new() -> io::Result<Selector> {
-        SelectorInner::new().map(|inner| {
+    pub fn new(capacity: usize) -> io::Result<Selector> {
+        SelectorInner::new(capacity).map(|inner| {
             #[cfg(debug_assertions)]
             let id = NEXT_ID.fetch_add(1, Ordering::Relaxed) + 1;
             Selector {
@@ -356,7 +357,18 @@ impl Selector {
     /// This requires a mutable reference to self because only a single thread
     /// can poll IOCP at a time.
     pub fn select(&mut self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
-        self.inner.select(events, timeout)
+        self.inner.select(events, usize::MAX, timeout)
+    }
+
+    /// Same as `select`, but retrieves at most `max_events` completion
+    /// statuses.
+    pub fn select_max(
+        &mut self,
+        events: &mut Events,
+        max_events: usize,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        self.inner.select(events, max_events, timeout)
     }
 
     pub(super) fn clone_port(&self) -> Arc<CompletionPort> {
@@ -411,14 +423,14 @@ pub struct SelectorInner {
 unsafe impl Sync for SelectorInner {}
 
 impl SelectorInner {
-    pub fn new() -> io::Result<SelectorInner> {
+    pub fn new(capacity: usize) -> io::Result<SelectorInner> {
         CompletionPort::new(0).map(|cp| {
             let cp = Arc::new(cp);
             let cp_afd = Arc::clone(&cp);
 
             SelectorInner {
                 cp,
-                update_queue: Mutex::new(VecDeque::new()),
+                update_queue: Mutex::new(VecDeque::with_capacity(capacity)),
                 afd_group: AfdGroup::new(cp_afd),
                 is_polling: AtomicBool::new(false),
             }
@@ -428,19 +440,26 @@ impl SelectorInner {
     /// # Safety
     ///
     /// May only be calling via `Selector::select`.
-    pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+    pub fn select(
+        &self,
+        events: &mut Events,
+        max_events: usize,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
         events.clear();
+        let len = cmp::min(events.statuses.len(), max_events);
+        let statuses = &mut events.statuses[..len];
 
         if timeout.is_none() {
             loop {
-                let len = self.select2(&mut events.statuses, &mut events.events, None)?;
+                let len = self.select2(statuses, &mut events.events, None)?;
                 if len == 0 {
                     continue;
                 }
                 break Ok(());
             }
         } else {
-            self.select2(&mut events.statuses, &mut events.events, timeout)?;
+            self.select2(statuses, &mut events.events, timeout)?;
             Ok(())
         }
     }
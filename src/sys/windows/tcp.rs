@@ -59,8 +59,126 @@ pub(crate) fn listen(socket: &net::TcpListener, backlog: u32) -> io::Result<()>
     Ok(())
 }
 
+pub(crate) fn only_v6<S: AsRawSocket>(socket: &S) -> io::Result<bool> {
+    use std::mem::{self, MaybeUninit};
+    use WinSock::{getsockopt, IPPROTO_IPV6, IPV6_V6ONLY};
+
+    let mut optval: MaybeUninit<i32> = MaybeUninit::uninit();
+    let mut optlen = mem::size_of::<i32>() as i32;
+
+    syscall!(
+        getsockopt(
+            socket.as_raw_socket() as _,
+            IPPROTO_IPV6,
+            IPV6_V6ONLY,
+            optval.as_mut_ptr().cast(),
+            &mut optlen,
+        ),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )?;
+
+    debug_assert_eq!(optlen as usize, mem::size_of::<i32>());
+    // Safety: `getsockopt` initialised `optval` for us.
+    let optval = unsafe { optval.assume_init() };
+    Ok(optval != 0)
+}
+
+pub(crate) fn set_only_v6(socket: &net::TcpListener, only_v6: bool) -> io::Result<()> {
+    use WinSock::{setsockopt, IPPROTO_IPV6, IPV6_V6ONLY};
+
+    let val: i32 = i32::from(only_v6);
+    syscall!(
+        setsockopt(
+            socket.as_raw_socket() as _,
+            IPPROTO_IPV6,
+            IPV6_V6ONLY,
+            (&val as *const i32).cast(),
+            std::mem::size_of::<i32>() as i32,
+        ),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )?;
+    Ok(())
+}
+
+pub(crate) fn share(socket: &net::TcpStream, target_pid: u32) -> io::Result<Vec<u8>> {
+    use std::mem::{self, MaybeUninit};
+    use std::slice;
+    use WinSock::{WSADuplicateSocketW, WSAPROTOCOL_INFOW};
+
+    let mut info: MaybeUninit<WSAPROTOCOL_INFOW> = MaybeUninit::uninit();
+    syscall!(
+        WSADuplicateSocketW(socket.as_raw_socket() as _, target_pid, info.as_mut_ptr()),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )?;
+
+    // Safety: `WSADuplicateSocketW` initialised `info` for us.
+    let bytes = unsafe {
+        slice::from_raw_parts(
+            info.as_ptr().cast::<u8>(),
+            mem::size_of::<WSAPROTOCOL_INFOW>(),
+        )
+    };
+    Ok(bytes.to_vec())
+}
+
+pub(crate) fn from_shared(info: &[u8]) -> io::Result<net::TcpStream> {
+    use std::mem::{self, MaybeUninit};
+    use std::os::windows::io::FromRawSocket;
+    use std::ptr;
+    use WinSock::{
+        closesocket, ioctlsocket, WSASocketW, FIONBIO, FROM_PROTOCOL_INFO, INVALID_SOCKET,
+        WSAPROTOCOL_INFOW, WSA_FLAG_NO_HANDLE_INHERIT, WSA_FLAG_OVERLAPPED,
+    };
+
+    if info.len() != mem::size_of::<WSAPROTOCOL_INFOW>() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid WSAPROTOCOL_INFOW size",
+        ));
+    }
+
+    let mut protocol_info: MaybeUninit<WSAPROTOCOL_INFOW> = MaybeUninit::uninit();
+    // Safety: checked the length above, `info` may not be aligned so we copy
+    // it byte by byte.
+    let protocol_info = unsafe {
+        ptr::copy_nonoverlapping(
+            info.as_ptr(),
+            protocol_info.as_mut_ptr().cast::<u8>(),
+            info.len(),
+        );
+        protocol_info.assume_init()
+    };
+
+    let socket = syscall!(
+        WSASocketW(
+            FROM_PROTOCOL_INFO,
+            FROM_PROTOCOL_INFO,
+            FROM_PROTOCOL_INFO,
+            &protocol_info,
+            0,
+            WSA_FLAG_OVERLAPPED | WSA_FLAG_NO_HANDLE_INHERIT,
+        ),
+        PartialEq::eq,
+        INVALID_SOCKET
+    )?;
+
+    if let Err(err) = syscall!(ioctlsocket(socket, FIONBIO, &mut 1), PartialEq::ne, 0) {
+        let _ = unsafe { closesocket(socket) };
+        return Err(err);
+    }
+
+    Ok(unsafe { net::TcpStream::from_raw_socket(socket as _) })
+}
+
 pub(crate) fn accept(listener: &net::TcpListener) -> io::Result<(net::TcpStream, SocketAddr)> {
     // The non-blocking state of `listener` is inherited. See
     // https://docs.microsoft.com/en-us/windows/win32/api/winsock2/nf-winsock2-accept#remarks.
     listener.accept()
 }
+
+pub(crate) fn available(socket: &net::TcpStream) -> io::Result<usize> {
+    crate::sys::windows::net::available(socket.as_raw_socket() as _)
+}
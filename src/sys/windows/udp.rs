@@ -1,12 +1,15 @@
 use std::io;
 use std::mem::{self, MaybeUninit};
-use std::net::{self, SocketAddr};
+use std::net::{self, Ipv4Addr, SocketAddr};
 use std::os::windows::io::{AsRawSocket, FromRawSocket};
 use std::os::windows::raw::SOCKET as StdSocket; // windows-sys uses usize, stdlib uses u32/u64.
+use std::ptr;
 
 use crate::sys::windows::net::{new_ip_socket, socket_addr};
 use windows_sys::Win32::Networking::WinSock::{
-    bind as win_bind, getsockopt, IPPROTO_IPV6, IPV6_V6ONLY, SOCKET_ERROR, SOCK_DGRAM,
+    bind as win_bind, getsockopt, setsockopt, WSAIoctl, IPPROTO_IP, IPPROTO_IPV6,
+    IPV6_MULTICAST_HOPS, IPV6_UNICAST_HOPS, IPV6_V6ONLY, IP_MULTICAST_IF, SIO_UDP_CONNRESET,
+    SOCKET_ERROR, SOCK_DGRAM,
 };
 
 pub fn bind(addr: SocketAddr) -> io::Result<net::UdpSocket> {
@@ -20,9 +23,34 @@ pub fn bind(addr: SocketAddr) -> io::Result<net::UdpSocket> {
         SOCKET_ERROR
     )?;
 
+    // Match the Unix behaviour of not reporting ICMP port unreachable
+    // messages as `WSAECONNRESET` errors in `recv_from`.
+    set_connreset(&socket, false)?;
+
     Ok(socket)
 }
 
+pub(crate) fn set_connreset(socket: &net::UdpSocket, connreset: bool) -> io::Result<()> {
+    let enable: u32 = u32::from(connreset);
+    let mut bytes_returned: u32 = 0;
+    syscall!(
+        WSAIoctl(
+            socket.as_raw_socket() as usize,
+            SIO_UDP_CONNRESET,
+            (&enable as *const u32).cast(),
+            mem::size_of::<u32>() as u32,
+            ptr::null_mut(),
+            0,
+            &mut bytes_returned,
+            ptr::null_mut(),
+            None,
+        ),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )?;
+    Ok(())
+}
+
 pub(crate) fn only_v6(socket: &net::UdpSocket) -> io::Result<bool> {
     let mut optval: MaybeUninit<i32> = MaybeUninit::uninit();
     let mut optlen = mem::size_of::<i32>() as i32;
@@ -44,3 +72,94 @@ pub(crate) fn only_v6(socket: &net::UdpSocket) -> io::Result<bool> {
     let optval = unsafe { optval.assume_init() };
     Ok(optval != 0)
 }
+
+pub(crate) fn set_multicast_if_v4(socket: &net::UdpSocket, interface: Ipv4Addr) -> io::Result<()> {
+    // The address must be in network byte order.
+    let interface: u32 = u32::from_ne_bytes(interface.octets());
+    syscall!(
+        setsockopt(
+            socket.as_raw_socket() as usize,
+            IPPROTO_IP,
+            IP_MULTICAST_IF,
+            (&interface as *const u32).cast(),
+            mem::size_of::<u32>() as i32,
+        ),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )?;
+    Ok(())
+}
+
+pub(crate) fn multicast_if_v4(socket: &net::UdpSocket) -> io::Result<Ipv4Addr> {
+    let mut optval: u32 = 0;
+    let mut optlen = mem::size_of::<u32>() as i32;
+
+    syscall!(
+        getsockopt(
+            socket.as_raw_socket() as usize,
+            IPPROTO_IP,
+            IP_MULTICAST_IF,
+            (&mut optval as *mut u32).cast(),
+            &mut optlen,
+        ),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )?;
+
+    Ok(Ipv4Addr::from(optval.to_ne_bytes()))
+}
+
+pub(crate) fn set_multicast_hops_v6(socket: &net::UdpSocket, hops: u32) -> io::Result<()> {
+    set_hops_v6(socket, IPV6_MULTICAST_HOPS, hops)
+}
+
+pub(crate) fn multicast_hops_v6(socket: &net::UdpSocket) -> io::Result<u32> {
+    hops_v6(socket, IPV6_MULTICAST_HOPS)
+}
+
+pub(crate) fn set_unicast_hops_v6(socket: &net::UdpSocket, hops: u32) -> io::Result<()> {
+    set_hops_v6(socket, IPV6_UNICAST_HOPS, hops)
+}
+
+pub(crate) fn unicast_hops_v6(socket: &net::UdpSocket) -> io::Result<u32> {
+    hops_v6(socket, IPV6_UNICAST_HOPS)
+}
+
+fn set_hops_v6(socket: &net::UdpSocket, opt: i32, hops: u32) -> io::Result<()> {
+    let hops = hops as i32;
+    syscall!(
+        setsockopt(
+            socket.as_raw_socket() as usize,
+            IPPROTO_IPV6,
+            opt,
+            (&hops as *const i32).cast(),
+            mem::size_of::<i32>() as i32,
+        ),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )?;
+    Ok(())
+}
+
+fn hops_v6(socket: &net::UdpSocket, opt: i32) -> io::Result<u32> {
+    let mut optval: i32 = 0;
+    let mut optlen = mem::size_of::<i32>() as i32;
+
+    syscall!(
+        getsockopt(
+            socket.as_raw_socket() as usize,
+            IPPROTO_IPV6,
+            opt,
+            (&mut optval as *mut i32).cast(),
+            &mut optlen,
+        ),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )?;
+
+    Ok(optval as u32)
+}
+
+pub(crate) fn available(socket: &net::UdpSocket) -> io::Result<usize> {
+    crate::sys::windows::net::available(socket.as_raw_socket() as usize)
+}
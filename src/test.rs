@@ -0,0 +1,405 @@
+//! Utilities for testing code that uses [`Poll`].
+//!
+//! [`Poll`] normally gets its events from the OS, which makes code using it
+//! hard to test without real (and often flaky) sockets. The [`Selector`] trait
+//! and [`Poll::with_selector`] allow a test to drive arbitrary readiness
+//! events without the OS.
+//!
+//! [`DeterministicPoll`] is a ready to use `Selector` that returns events
+//! enqueued by the test, using a virtual clock for timeouts.
+//!
+//! [`Poll`]: crate::Poll
+//! [`Poll::with_selector`]: crate::Poll::with_selector
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+use std::{cmp, fmt, io, ops};
+
+use crate::{Events, Poll, Token};
+
+/// Source of readiness events for a [`Poll`] created using
+/// [`Poll::with_selector`].
+///
+/// [`Poll`]: crate::Poll
+/// [`Poll::with_selector`]: crate::Poll::with_selector
+///
+/// # Examples
+///
+/// A selector that returns a scripted sequence of events, one batch per call
+/// to [`Poll::poll`].
+///
+/// [`Poll::poll`]: crate::Poll::poll
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::collections::VecDeque;
+/// use std::io;
+/// use std::time::Duration;
+///
+/// use mio::test::{Readiness, Selector};
+/// use mio::{Events, Poll, Token};
+///
+/// struct Script(VecDeque<Vec<(Token, Readiness)>>);
+///
+/// impl Selector for Script {
+///     fn select(&mut self, events: &mut Events, _: Option<Duration>) -> io::Result<()> {
+///         for (token, readiness) in self.0.pop_front().unwrap_or_default() {
+///             events.push(token, readiness);
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let script = Script(VecDeque::from(vec![
+///     vec![(Token(0), Readiness::READABLE)],
+///     vec![(Token(1), Readiness::WRITABLE | Readiness::WRITE_CLOSED)],
+/// ]));
+/// let mut poll = Poll::with_selector(Box::new(script))?;
+/// let mut events = Events::with_capacity(8);
+///
+/// poll.poll(&mut events, None)?;
+/// let event = events.iter().next().unwrap();
+/// assert_eq!(event.token(), Token(0));
+/// assert!(event.is_readable());
+///
+/// poll.poll(&mut events, None)?;
+/// let event = events.iter().next().unwrap();
+/// assert_eq!(event.token(), Token(1));
+/// assert!(event.is_write_closed());
+/// #     Ok(())
+/// # }
+/// ```
+pub trait Selector: Send + Sync {
+    /// Wait for readiness events, see [`Poll::poll`].
+    ///
+    /// `events` is cleared before this is called. Implementations should add
+    /// events using [`Events::push`] and shouldn't add more than
+    /// [`Events::capacity`] events.
+    ///
+    /// [`Poll::poll`]: crate::Poll::poll
+    fn select(&mut self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()>;
+}
+
+/// Readiness of an injected event, see [`Events::push`].
+///
+/// The readiness is translated into the event representation of the OS
+/// selector, so the resulting [`Event`] can report readiness implied by it
+/// the same way events from the OS do. For example on most platforms an
+/// event with `READ_CLOSED` readiness is also readable.
+///
+/// [`Event`]: crate::event::Event
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Readiness(u8);
+
+const READABLE: u8 = 0b00_0001;
+const WRITABLE: u8 = 0b00_0010;
+const ERROR: u8 = 0b00_0100;
+const READ_CLOSED: u8 = 0b00_1000;
+const WRITE_CLOSED: u8 = 0b01_0000;
+const PRIORITY: u8 = 0b10_0000;
+
+impl Readiness {
+    /// See [`Event::is_readable`](crate::event::Event::is_readable).
+    pub const READABLE: Readiness = Readiness(READABLE);
+    /// See [`Event::is_writable`](crate::event::Event::is_writable).
+    pub const WRITABLE: Readiness = Readiness(WRITABLE);
+    /// See [`Event::is_error`](crate::event::Event::is_error).
+    pub const ERROR: Readiness = Readiness(ERROR);
+    /// See [`Event::is_read_closed`](crate::event::Event::is_read_closed).
+    pub const READ_CLOSED: Readiness = Readiness(READ_CLOSED);
+    /// See [`Event::is_write_closed`](crate::event::Event::is_write_closed).
+    pub const WRITE_CLOSED: Readiness = Readiness(WRITE_CLOSED);
+    /// See [`Event::is_priority`](crate::event::Event::is_priority).
+    pub const PRIORITY: Readiness = Readiness(PRIORITY);
+
+    /// Add together two `Readiness`.
+    ///
+    /// This does the same thing as the `BitOr` implementation, but is a
+    /// constant function.
+    #[allow(clippy::should_implement_trait)]
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub const fn add(self, other: Readiness) -> Readiness {
+        Readiness(self.0 | other.0)
+    }
+
+    /// Returns true if the value includes readable readiness.
+    #[must_use]
+    pub const fn is_readable(self) -> bool {
+        (self.0 & READABLE) != 0
+    }
+
+    /// Returns true if the value includes writable readiness.
+    #[must_use]
+    pub const fn is_writable(self) -> bool {
+        (self.0 & WRITABLE) != 0
+    }
+
+    /// Returns true if the value includes error readiness.
+    #[must_use]
+    pub const fn is_error(self) -> bool {
+        (self.0 & ERROR) != 0
+    }
+
+    /// Returns true if the value includes read closed readiness.
+    #[must_use]
+    pub const fn is_read_closed(self) -> bool {
+        (self.0 & READ_CLOSED) != 0
+    }
+
+    /// Returns true if the value includes write closed readiness.
+    #[must_use]
+    pub const fn is_write_closed(self) -> bool {
+        (self.0 & WRITE_CLOSED) != 0
+    }
+
+    /// Returns true if the value includes priority readiness.
+    #[must_use]
+    pub const fn is_priority(self) -> bool {
+        (self.0 & PRIORITY) != 0
+    }
+}
+
+impl ops::BitOr for Readiness {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        self.add(other)
+    }
+}
+
+impl ops::BitOrAssign for Readiness {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl fmt::Debug for Readiness {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut one = false;
+        for (is, name) in [
+            (self.is_readable(), "READABLE"),
+            (self.is_writable(), "WRITABLE"),
+            (self.is_error(), "ERROR"),
+            (self.is_read_closed(), "READ_CLOSED"),
+            (self.is_write_closed(), "WRITE_CLOSED"),
+            (self.is_priority(), "PRIORITY"),
+        ] {
+            if is {
+                if one {
+                    write!(fmt, " | ")?
+                }
+                write!(fmt, "{}", name)?;
+                one = true
+            }
+        }
+        if !one {
+            fmt.write_str("(empty)")?;
+        }
+        Ok(())
+    }
+}
+
+/// In-memory [`Selector`] that returns events enqueued by the test.
+///
+/// Events are returned by [`Poll::poll`] in the order they become ready, in
+/// the order they were enqueued for events that become ready at the same
+/// time.
+///
+/// Timeouts use a virtual clock, which only advances when polling (or calling
+/// [`advance`]): if no events are ready `poll` advances the clock to the time
+/// the next event becomes ready, or to the end of the timeout if that is
+/// sooner, without actually waiting. Only when there are no (future) events at
+/// all and the timeout is `None` does `poll` block, until an event is enqueued
+/// by another thread, e.g. using [`wake`].
+///
+/// Note that [`Waker`] and registered `event::Source`s are not connected to
+/// the deterministic poll, [`wake`] can be used instead of `Waker`.
+///
+/// [`Poll::poll`]: crate::Poll::poll
+/// [`advance`]: DeterministicPoll::advance
+/// [`wake`]: DeterministicPoll::wake
+/// [`Waker`]: crate::Waker
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::time::Duration;
+///
+/// use mio::test::{DeterministicPoll, Readiness};
+/// use mio::{Events, Token};
+///
+/// let deterministic = DeterministicPoll::new();
+/// let mut poll = deterministic.new_poll()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// deterministic.enqueue_after(Duration::from_secs(10), Token(0), Readiness::READABLE);
+///
+/// // The event isn't ready yet, so this times out, without actually waiting
+/// // for a second.
+/// poll.poll(&mut events, Some(Duration::from_secs(1)))?;
+/// assert!(events.is_empty());
+/// assert_eq!(deterministic.now(), Duration::from_secs(1));
+///
+/// // Without a timeout the clock is advanced to the next event.
+/// poll.poll(&mut events, None)?;
+/// assert_eq!(events.iter().next().unwrap().token(), Token(0));
+/// assert_eq!(deterministic.now(), Duration::from_secs(10));
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DeterministicPoll {
+    shared: Arc<Shared>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    /// Notified when an event is enqueued.
+    enqueued: Condvar,
+}
+
+#[derive(Debug)]
+struct State {
+    /// Current virtual time.
+    now: Duration,
+    /// Enqueued events, sorted by `ready_at`.
+    queue: Vec<Enqueued>,
+}
+
+#[derive(Debug)]
+struct Enqueued {
+    ready_at: Duration,
+    token: Token,
+    readiness: Readiness,
+    /// Added by `DeterministicPoll::wake`.
+    wake: bool,
+}
+
+impl DeterministicPoll {
+    /// Create a new `DeterministicPoll` with the virtual clock at zero.
+    pub fn new() -> DeterministicPoll {
+        DeterministicPoll {
+            shared: Arc::new(Shared {
+                state: Mutex::new(State {
+                    now: Duration::ZERO,
+                    queue: Vec::new(),
+                }),
+                enqueued: Condvar::new(),
+            }),
+        }
+    }
+
+    /// Create a new [`Poll`] that returns the events of this
+    /// `DeterministicPoll`.
+    pub fn new_poll(&self) -> io::Result<Poll> {
+        Poll::with_selector(Box::new(self.clone()))
+    }
+
+    /// Enqueue an event for `token` with `readiness`, ready immediately.
+    pub fn enqueue(&self, token: Token, readiness: Readiness) {
+        self.enqueue_after(Duration::ZERO, token, readiness)
+    }
+
+    /// Enqueue an event for `token` with `readiness`, ready once the virtual
+    /// clock advanced by `delay`.
+    pub fn enqueue_after(&self, delay: Duration, token: Token, readiness: Readiness) {
+        let mut state = self.shared.state.lock().unwrap();
+        let ready_at = state.now.saturating_add(delay);
+        state.insert(Enqueued {
+            ready_at,
+            token,
+            readiness,
+            wake: false,
+        });
+        drop(state);
+        self.shared.enqueued.notify_all();
+    }
+
+    /// Enqueue a readable event for `token`, like [`Waker::wake`].
+    ///
+    /// Like `Waker` multiple calls are coalesced into a single event, if the
+    /// previous event isn't returned yet.
+    ///
+    /// [`Waker::wake`]: crate::Waker::wake
+    pub fn wake(&self, token: Token) {
+        let mut state = self.shared.state.lock().unwrap();
+        let woken = state
+            .queue
+            .iter()
+            .any(|event| event.wake && event.token == token);
+        if !woken {
+            let ready_at = state.now;
+            state.insert(Enqueued {
+                ready_at,
+                token,
+                readiness: Readiness::READABLE,
+                wake: true,
+            });
+        }
+        drop(state);
+        self.shared.enqueued.notify_all();
+    }
+
+    /// Returns the virtual time elapsed since the creation of this
+    /// `DeterministicPoll`.
+    pub fn now(&self) -> Duration {
+        self.shared.state.lock().unwrap().now
+    }
+
+    /// Advance the virtual clock by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.now = state.now.saturating_add(duration);
+    }
+}
+
+impl Default for DeterministicPoll {
+    fn default() -> DeterministicPoll {
+        DeterministicPoll::new()
+    }
+}
+
+impl State {
+    fn insert(&mut self, event: Enqueued) {
+        let index = self.queue.partition_point(|e| e.ready_at <= event.ready_at);
+        self.queue.insert(index, event);
+    }
+}
+
+impl Selector for DeterministicPoll {
+    fn select(&mut self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        let mut state = self.shared.state.lock().unwrap();
+        // A deadline that overflows is treated as no deadline.
+        let deadline = timeout.and_then(|timeout| state.now.checked_add(timeout));
+        loop {
+            match state.queue.first() {
+                Some(next) if deadline.map_or(true, |deadline| next.ready_at <= deadline) => {
+                    state.now = cmp::max(state.now, next.ready_at);
+                    break;
+                }
+                _ => match deadline {
+                    Some(deadline) => {
+                        // Timed out.
+                        state.now = cmp::max(state.now, deadline);
+                        return Ok(());
+                    }
+                    None => state = self.shared.enqueued.wait(state).unwrap(),
+                },
+            }
+        }
+
+        let now = state.now;
+        let ready = state.queue.partition_point(|e| e.ready_at <= now);
+        let n = cmp::min(ready, events.capacity());
+        for event in state.queue.drain(..n) {
+            events.push(event.token, event.readiness);
+        }
+        Ok(())
+    }
+}
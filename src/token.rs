@@ -1,3 +1,5 @@
+use crate::Events;
+
 /// Associates readiness events with [`event::Source`]s.
 ///
 /// `Token` is a wrapper around `usize` and is used as an argument to
@@ -129,10 +131,194 @@
 /// # }
 /// ```
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Token(pub usize);
 
+impl Token {
+    /// Token used by the [`Waker`] returned by [`Poll::shutdown_waker`].
+    ///
+    /// Events with this token signal that the event loop should shut down,
+    /// see [`Event::is_shutdown`]. It should not be used to register any other
+    /// event source.
+    ///
+    /// [`Waker`]: crate::Waker
+    /// [`Poll::shutdown_waker`]: crate::Poll::shutdown_waker
+    /// [`Event::is_shutdown`]: crate::event::Event::is_shutdown
+    pub const SHUTDOWN: Token = Token(usize::MAX);
+}
+
 impl From<Token> for usize {
     fn from(val: Token) -> usize {
         val.0
     }
 }
+
+/// Number of bits of a [`Token`] created by [`TokenAllocator`] used for the
+/// slot index, the remaining (high) bits hold the generation.
+const INDEX_BITS: u32 = usize::BITS / 4 * 3;
+const INDEX_MASK: usize = (1 << INDEX_BITS) - 1;
+const GENERATION_MASK: usize = usize::MAX >> INDEX_BITS;
+
+/// Hands out unique [`Token`]s, reusing the ones that are freed.
+///
+/// Each `Token` consists of a slot index, which is reused once the token is
+/// freed, and a generation counter, which is incremented each time the slot is
+/// reused. This means an event for a source that was already deregistered (a
+/// stale event) can be detected by checking [`is_allocated`], even if the slot
+/// is now used by another source.
+///
+/// The slot index can be retrieved using [`TokenAllocator::index`], which is
+/// suitable for indexing into a `Vec` or [`slab`].
+///
+/// The low three quarters of the bits of a `Token` hold the slot index, the
+/// remaining bits the generation. On 64 bit platforms that means 2^48 slots
+/// with a 16 bit generation, on 32 bit platforms 2^24 slots with only an 8 bit
+/// generation. The generation wraps around once a slot is reused that many
+/// times (e.g. after 256 reuses on 32 bit platforms), after which a stale
+/// token of the same slot is considered allocated again.
+///
+/// [`is_allocated`]: TokenAllocator::is_allocated
+/// [`slab`]: https://crates.io/crates/slab
+///
+/// # Examples
+///
+/// ```
+/// use mio::TokenAllocator;
+///
+/// let mut tokens = TokenAllocator::new();
+///
+/// let token = tokens.alloc();
+/// assert!(tokens.is_allocated(token));
+///
+/// assert!(tokens.free(token));
+/// assert!(!tokens.is_allocated(token));
+///
+/// // The slot is reused, but with a new generation.
+/// let new_token = tokens.alloc();
+/// assert_ne!(token, new_token);
+/// assert_eq!(TokenAllocator::index(token), TokenAllocator::index(new_token));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TokenAllocator {
+    /// Current generation of each slot.
+    generations: Vec<usize>,
+    /// Whether or not the slot with the same index is in use.
+    allocated: Vec<bool>,
+    /// Indices of slots that are free to reuse.
+    free: Vec<usize>,
+}
+
+impl TokenAllocator {
+    /// Create a new, empty, `TokenAllocator`.
+    pub const fn new() -> TokenAllocator {
+        TokenAllocator {
+            generations: Vec::new(),
+            allocated: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    /// Allocate a new unique `Token`.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if all slots are in use, i.e. `2^48 - 1` tokens are
+    /// allocated on 64 bit platforms. The last slot is reserved so that
+    /// [`Token::SHUTDOWN`] is never returned.
+    pub fn alloc(&mut self) -> Token {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                let index = self.generations.len();
+                assert!(index < INDEX_MASK, "TokenAllocator: out of tokens");
+                self.generations.push(0);
+                self.allocated.push(false);
+                index
+            }
+        };
+        self.allocated[index] = true;
+        Token(index | (self.generations[index] << INDEX_BITS))
+    }
+
+    /// Free `token`, allowing its slot to be reused.
+    ///
+    /// Returns `false` if `token` was not allocated, for example because it
+    /// was already freed.
+    pub fn free(&mut self, token: Token) -> bool {
+        if !self.is_allocated(token) {
+            return false;
+        }
+        let index = TokenAllocator::index(token);
+        self.allocated[index] = false;
+        self.generations[index] = (self.generations[index] + 1) & GENERATION_MASK;
+        self.free.push(index);
+        true
+    }
+
+    /// Returns `true` if `token` is currently allocated, `false` if it was
+    /// freed or not created by this allocator.
+    pub fn is_allocated(&self, token: Token) -> bool {
+        let index = TokenAllocator::index(token);
+        let generation = token.0 >> INDEX_BITS;
+        self.allocated.get(index).copied().unwrap_or(false) && self.generations[index] == generation
+    }
+
+    /// Removes all events from `events` with a token that is not allocated
+    /// (anymore), i.e. stale events.
+    ///
+    /// Call this after [`Poll::poll`] to ensure that an event for a source
+    /// that was already deregistered, and whose token was freed, isn't
+    /// mistaken for an event of a new source that reuses the same slot.
+    ///
+    /// Events with tokens not created by this allocator are removed as well,
+    /// except for [`Token::SHUTDOWN`].
+    ///
+    /// [`Poll::poll`]: crate::Poll::poll
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::time::Duration;
+    ///
+    /// use mio::{Events, Poll, TokenAllocator};
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(128);
+    /// let tokens = TokenAllocator::new();
+    ///
+    /// // Register sources using tokens from `tokens`...
+    ///
+    /// poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+    /// tokens.remove_stale(&mut events);
+    /// for event in events.iter() {
+    ///     // Only events of currently registered sources.
+    ///     assert!(tokens.is_allocated(event.token()));
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn remove_stale(&self, events: &mut Events) {
+        events.retain(|event| {
+            let token = event.token();
+            token == Token::SHUTDOWN || self.is_allocated(token)
+        });
+    }
+
+    /// Returns the number of allocated tokens.
+    pub fn len(&self) -> usize {
+        self.generations.len() - self.free.len()
+    }
+
+    /// Returns `true` if no tokens are allocated.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the slot index of `token`, ignoring its generation.
+    pub const fn index(token: Token) -> usize {
+        token.0 & INDEX_MASK
+    }
+}
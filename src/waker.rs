@@ -1,6 +1,7 @@
 use crate::{sys, Registry, Token};
 
 use std::io;
+use std::sync::Arc;
 
 /// Waker allows cross-thread waking of [`Poll`].
 ///
@@ -19,13 +20,18 @@ use std::io;
 /// Only a single `Waker` can be active per [`Poll`], if multiple threads need
 /// access to the `Waker` it can be shared via for example an `Arc`. What
 /// happens if multiple `Waker`s are registered with the same `Poll` is
-/// unspecified.
+/// unspecified. The `Waker`s returned by [`Poll::shutdown_waker`] don't count
+/// towards this limit.
+///
+/// [`Poll::shutdown_waker`]: crate::Poll::shutdown_waker
 ///
 /// # Implementation notes
 ///
-/// On platforms that support kqueue this will use the `EVFILT_USER` event
-/// filter, see [implementation notes of `Poll`] to see what platforms support
-/// kqueue. On Linux it uses [eventfd].
+/// On DragonFly BSD, FreeBSD and Apple platforms this will use the kqueue
+/// `EVFILT_USER` event filter, which doesn't require any additional file
+/// descriptors besides a duplicate of the kqueue. On Linux it uses [eventfd].
+/// Other platforms use a Unix pipe, see [implementation notes of `Poll`] for
+/// the selector used on each platform.
 ///
 /// [implementation notes of `Poll`]: struct.Poll.html#implementation-notes
 /// [eventfd]: https://man7.org/linux/man-pages/man2/eventfd.2.html
@@ -76,7 +82,7 @@ use std::io;
 /// ```
 #[derive(Debug)]
 pub struct Waker {
-    inner: sys::Waker,
+    inner: Arc<sys::Waker>,
 }
 
 impl Waker {
@@ -84,7 +90,15 @@ impl Waker {
     pub fn new(registry: &Registry, token: Token) -> io::Result<Waker> {
         #[cfg(debug_assertions)]
         registry.register_waker();
-        sys::Waker::new(registry.selector(), token).map(|inner| Waker { inner })
+        sys::Waker::new(registry.selector(), token).map(|inner| Waker {
+            inner: Arc::new(inner),
+        })
+    }
+
+    /// Create a `Waker` that shares `inner` with the other `Waker`s returned
+    /// by `Poll::shutdown_waker`.
+    pub(crate) fn shared(inner: Arc<sys::Waker>) -> Waker {
+        Waker { inner }
     }
 
     /// Wake up the [`Poll`] associated with this `Waker`.
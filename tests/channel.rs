@@ -0,0 +1,99 @@
+#![cfg(all(unix, feature = "os-poll", feature = "os-ext", feature = "net"))]
+
+use std::sync::mpsc::TryRecvError;
+use std::thread;
+use std::time::Duration;
+
+use mio::{channel, Interest, Token};
+
+mod util;
+use util::{expect_events, expect_no_events, init_with_poll, ExpectEvent, Readiness};
+
+const CHANNEL: Token = Token(0);
+
+#[test]
+fn channel_send_from_thread() {
+    const N: usize = 100;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let (sender, mut receiver) = channel().unwrap();
+    poll.registry()
+        .register(&mut receiver, CHANNEL, Interest::READABLE)
+        .unwrap();
+    assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+
+    let handle = thread::spawn(move || {
+        for i in 0..N {
+            sender.send(i).unwrap();
+            if i % 10 == 0 {
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+    });
+
+    let mut received = Vec::new();
+    let mut disconnected = false;
+    while !disconnected {
+        poll.poll(&mut events, Some(Duration::from_secs(5)))
+            .unwrap();
+        assert!(!events.is_empty(), "no events");
+        for event in events.iter() {
+            assert_eq!(event.token(), CHANNEL);
+            loop {
+                match receiver.try_recv() {
+                    Ok(value) => received.push(value),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        disconnected = true;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    handle.join().unwrap();
+
+    assert_eq!(received, (0..N).collect::<Vec<_>>());
+}
+
+#[test]
+fn channel_readiness() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (sender, mut receiver) = channel().unwrap();
+    poll.registry()
+        .register(&mut receiver, CHANNEL, Interest::READABLE)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    sender.send(1).unwrap();
+    sender.clone().send(2).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(CHANNEL, Interest::READABLE)],
+    );
+    assert_eq!(receiver.try_recv(), Ok(1));
+    assert_eq!(receiver.try_recv(), Ok(2));
+    assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+    expect_no_events(&mut poll, &mut events);
+
+    // Sending after the channel is emptied triggers a new event.
+    sender.send(3).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(CHANNEL, Interest::READABLE)],
+    );
+    assert_eq!(receiver.try_recv(), Ok(3));
+    assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+
+    drop(sender);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(CHANNEL, Readiness::READ_CLOSED)],
+    );
+    assert_eq!(receiver.try_recv(), Err(TryRecvError::Disconnected));
+}
@@ -32,6 +32,8 @@ fn events_all() {
         .unwrap();
 
     assert!(!events.is_empty());
+    assert_eq!(events.len(), 1);
+    assert_eq!(events.iter().count(), events.len());
 
     for event in events.iter() {
         assert_eq!(event.token(), WAKE_TOKEN);
@@ -41,3 +43,182 @@ fn events_all() {
     events.clear();
     assert!(events.is_empty());
 }
+
+// The poll(2) based selector grows the buffer to fit all events.
+#[cfg(not(any(
+    mio_unsupported_force_poll_poll,
+    target_os = "espidf",
+    target_os = "haiku",
+    target_os = "hermit",
+    target_os = "nto",
+    target_os = "solaris",
+    target_os = "vita"
+)))]
+#[test]
+fn events_set_capacity() {
+    use mio::net::UdpSocket;
+    use mio::{Events, Interest};
+    use util::any_local_address;
+
+    const N: usize = 8;
+
+    let (mut poll, _) = init_with_poll();
+    let mut events = Events::with_capacity(2);
+    assert_eq!(events.capacity(), 2);
+
+    let mut sockets = (0..N)
+        .map(|_| UdpSocket::bind(any_local_address()).unwrap())
+        .collect::<Vec<_>>();
+    for (i, socket) in sockets.iter_mut().enumerate() {
+        poll.registry()
+            .register(socket, Token(i), Interest::WRITABLE)
+            .unwrap();
+    }
+
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert_eq!(events.iter().count(), 2);
+
+    // The remaining events are still pending, a bigger buffer picks all of
+    // them up in a single poll.
+    events.set_capacity(N);
+    assert!(events.capacity() >= N);
+    assert!(events.is_empty());
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert_eq!(events.iter().count(), N - 2);
+
+    events.reserve(N);
+    assert!(events.capacity() >= N);
+}
+
+#[cfg(unix)]
+#[test]
+fn events_array() {
+    use mio::event::EventsArray;
+    use mio::net::UdpSocket;
+    use mio::Interest;
+    use util::any_local_address;
+
+    const N: usize = 6;
+
+    let (mut poll, _) = init_with_poll();
+    let mut events = EventsArray::<4>::new();
+    assert_eq!(events.capacity(), 4);
+    assert!(events.is_empty());
+
+    let mut sockets = (0..N)
+        .map(|_| UdpSocket::bind(any_local_address()).unwrap())
+        .collect::<Vec<_>>();
+    for (i, socket) in sockets.iter_mut().enumerate() {
+        poll.registry()
+            .register(socket, Token(i), Interest::WRITABLE)
+            .unwrap();
+    }
+
+    let mut seen = Vec::new();
+    poll.poll_array(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert_eq!(events.iter().count(), 4);
+    seen.extend(events.iter().map(|event| {
+        assert!(event.is_writable());
+        event.token().0
+    }));
+
+    // The remaining events are returned by the next poll.
+    poll.poll_array(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert_eq!(events.iter().count(), N - 4);
+    seen.extend((&events).into_iter().map(|event| event.token().0));
+
+    seen.sort_unstable();
+    assert_eq!(seen, (0..N).collect::<Vec<_>>());
+
+    events.clear();
+    assert!(events.is_empty());
+}
+
+// On Windows the capacity of `Events` is the number of completion statuses
+// retrieved in a single call to `GetQueuedCompletionStatusEx`.
+#[cfg(windows)]
+#[test]
+fn events_large_batch() {
+    use mio::net::UdpSocket;
+    use mio::{Events, Interest};
+    use util::any_local_address;
+
+    const N: usize = 256;
+
+    let (mut poll, _) = init_with_poll();
+    let mut events = Events::with_capacity(N);
+
+    let mut sockets = (0..N)
+        .map(|_| UdpSocket::bind(any_local_address()).unwrap())
+        .collect::<Vec<_>>();
+    for (i, socket) in sockets.iter_mut().enumerate() {
+        poll.registry()
+            .register(socket, Token(i), Interest::WRITABLE)
+            .unwrap();
+    }
+
+    // The first poll submits the AFD poll operations, give them some time to
+    // complete so that they're all retrieved by the second poll.
+    poll.poll(&mut events, Some(Duration::ZERO)).unwrap();
+    let first = events.iter().count();
+    std::thread::sleep(Duration::from_millis(100));
+    poll.poll(&mut events, Some(Duration::from_millis(500)))
+        .unwrap();
+    assert_eq!(first + events.iter().count(), N);
+}
+
+#[cfg(any(unix, windows))]
+#[test]
+fn max_events_per_poll() {
+    use std::num::NonZeroUsize;
+
+    use mio::net::UdpSocket;
+    use mio::{Events, Interest};
+    use util::any_local_address;
+
+    const N: usize = 8;
+    const MAX: usize = 3;
+
+    let (mut poll, _) = init_with_poll();
+    let mut events = Events::with_capacity(N * 2);
+    assert_eq!(poll.max_events_per_poll(), None);
+    let max_events = NonZeroUsize::new(MAX).unwrap();
+    poll.set_max_events_per_poll(Some(max_events));
+    assert_eq!(poll.max_events_per_poll(), Some(max_events));
+
+    let mut sockets = (0..N)
+        .map(|_| UdpSocket::bind(any_local_address()).unwrap())
+        .collect::<Vec<_>>();
+    for (i, socket) in sockets.iter_mut().enumerate() {
+        poll.registry()
+            .register(socket, Token(i), Interest::WRITABLE)
+            .unwrap();
+    }
+
+    let mut seen = Vec::new();
+    while seen.len() < N {
+        poll.poll(&mut events, Some(Duration::from_millis(100)))
+            .unwrap();
+        assert!(!events.is_empty());
+        assert_eq!(events.len(), MAX.min(N - seen.len()));
+        seen.extend(events.iter().map(|event| event.token().0));
+    }
+
+    seen.sort_unstable();
+    assert_eq!(seen, (0..N).collect::<Vec<_>>());
+
+    // Without a limit the entire buffer is used again.
+    poll.set_max_events_per_poll(None);
+    for (i, socket) in sockets.iter_mut().enumerate() {
+        poll.registry()
+            .reregister(socket, Token(i), Interest::WRITABLE)
+            .unwrap();
+    }
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert_eq!(events.len(), N);
+}
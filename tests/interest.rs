@@ -41,6 +41,32 @@ fn fmt_debug() {
     {
         assert_eq!(format!("{:?}", Interest::LIO), "LIO");
     }
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        assert_eq!(
+            format!("{:?}", Interest::READABLE | Interest::WAKEUP),
+            "READABLE | WAKEUP"
+        );
+    }
+}
+
+#[test]
+fn fmt_display() {
+    assert_eq!(Interest::READABLE.to_string(), "READABLE");
+    assert_eq!(
+        (Interest::WRITABLE | Interest::READABLE).to_string(),
+        "READABLE | WRITABLE"
+    );
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        let interests = Interest::READABLE | Interest::WRITABLE | Interest::PRIORITY;
+        assert_eq!(interests.to_string(), "READABLE | WRITABLE | PRIORITY");
+        assert_eq!(format!("{interests:?}"), interests.to_string());
+        assert_eq!(
+            (Interest::READABLE | Interest::HUP).to_string(),
+            "READABLE | HUP"
+        );
+    }
 }
 
 #[test]
@@ -50,3 +76,39 @@ fn add() {
     assert!(interest.is_readable());
     assert!(interest.is_writable());
 }
+
+#[test]
+fn remove() {
+    let interest = (Interest::READABLE | Interest::WRITABLE)
+        .remove(Interest::WRITABLE)
+        .unwrap();
+    assert!(interest.is_readable());
+    assert!(!interest.is_writable());
+
+    assert_eq!(Interest::READABLE.remove(Interest::READABLE), None);
+    assert_eq!(
+        Interest::READABLE.remove(Interest::WRITABLE),
+        Some(Interest::READABLE)
+    );
+}
+
+#[test]
+fn contains() {
+    let interests = Interest::READABLE | Interest::WRITABLE;
+    assert!(interests.contains(Interest::READABLE));
+    assert!(interests.contains(Interest::WRITABLE));
+    assert!(interests.contains(interests));
+    assert!(!Interest::READABLE.contains(interests));
+    assert!(!Interest::READABLE.contains(Interest::WRITABLE));
+}
+
+#[test]
+fn intersection() {
+    let interests = Interest::READABLE | Interest::WRITABLE;
+    assert_eq!(
+        interests.intersection(Interest::WRITABLE),
+        Some(Interest::WRITABLE)
+    );
+    assert_eq!(interests.intersection(interests), Some(interests));
+    assert_eq!(Interest::READABLE.intersection(Interest::WRITABLE), None);
+}
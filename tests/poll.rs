@@ -4,7 +4,7 @@
 use std::net;
 use std::sync::{Arc, Barrier};
 use std::thread::{self, sleep};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{fmt, io};
 
 use mio::event::Source;
@@ -42,6 +42,37 @@ fn run_once_with_nothing() {
         .unwrap();
 }
 
+#[test]
+fn new_with_capacity() {
+    init();
+
+    const N: usize = 256;
+    let mut poll = Poll::new_with_capacity(N).unwrap();
+    let mut events = Events::with_capacity(N);
+
+    let address = any_local_address();
+    let mut sockets = (0..N)
+        .map(|_| UdpSocket::bind(address).unwrap())
+        .collect::<Vec<_>>();
+    for (n, socket) in sockets.iter_mut().enumerate() {
+        poll.registry()
+            .register(socket, Token(n), Interest::WRITABLE)
+            .unwrap();
+    }
+
+    let mut seen = vec![false; N];
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while seen.contains(&false) && Instant::now() < deadline {
+        poll.poll(&mut events, Some(Duration::from_millis(100)))
+            .unwrap();
+        for event in events.iter() {
+            assert!(event.is_writable());
+            seen[event.token().0] = true;
+        }
+    }
+    assert!(!seen.contains(&false), "missing writable events");
+}
+
 #[test]
 fn add_then_drop() {
     init();
@@ -360,6 +391,96 @@ fn register_during_poll() {
     handle1.join().unwrap();
 }
 
+#[test]
+fn fair_event_ordering() {
+    const N: usize = 8;
+    const POLLS: usize = N * 10;
+
+    let (mut poll, mut events) = init_with_poll();
+    poll.set_fair(true);
+    assert!(poll.is_fair());
+
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+    let mut sockets: Vec<UdpSocket> = (0..N)
+        .map(|_| UdpSocket::bind(any_local_address()).unwrap())
+        .collect();
+    for (i, socket) in sockets.iter_mut().enumerate() {
+        // Never read the datagram so the socket is always readable.
+        sender
+            .send_to(b"hello", socket.local_addr().unwrap())
+            .unwrap();
+        poll.registry()
+            .register(socket, Token(i), Interest::READABLE)
+            .unwrap();
+    }
+
+    let mut first_counts = [0; N];
+    let mut polls = 0;
+    while polls < POLLS {
+        poll.poll(&mut events, Some(Duration::from_millis(500)))
+            .unwrap();
+        if events.len() != N {
+            // Not all datagrams have arrived yet.
+            continue;
+        }
+        first_counts[events.iter().next().unwrap().token().0] += 1;
+        polls += 1;
+
+        // Reregister to receive the (edge-triggered) events again.
+        for (i, socket) in sockets.iter_mut().enumerate() {
+            poll.registry()
+                .reregister(socket, Token(i), Interest::READABLE)
+                .unwrap();
+        }
+    }
+
+    for (token, count) in first_counts.iter().enumerate() {
+        assert!(
+            *count >= POLLS / N / 2,
+            "token {token} was first only {count} times: {first_counts:?}"
+        );
+    }
+}
+
+#[test]
+fn register_using_clone_of_dropped_clone() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let registry1 = poll.registry().try_clone().unwrap();
+    let registry2 = registry1.try_clone().unwrap();
+    drop(registry1);
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    registry2
+        .register(&mut socket, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    registry2.deregister(&mut socket).unwrap();
+}
+
+#[test]
+fn register_using_clone_after_poll_drop() {
+    init();
+    let poll = Poll::new().unwrap();
+    let registry = poll.registry().try_clone().unwrap();
+    drop(poll);
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    registry
+        .register(&mut socket, ID1, Interest::WRITABLE)
+        .unwrap();
+    registry
+        .reregister(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+    registry.deregister(&mut socket).unwrap();
+}
+
 // This test checks the following reregister constraints:
 // - `reregister` arguments fully override the previous values. In other
 // words, if a socket is registered with `READABLE` interest and the call
@@ -528,6 +649,28 @@ fn deregister_without_register() {
     assert_error(poll.registry().deregister(&mut listener), "not registered");
 }
 
+#[test]
+#[cfg(debug_assertions)] // Check is only present when debug assertions are enabled.
+fn deregister_batch_continues_after_error() {
+    let poll = Poll::new().expect("unable to create Poll instance");
+
+    let mut listener1 = TcpListener::bind(any_local_address()).unwrap();
+    let mut listener2 = TcpListener::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut listener2, ID2, Interest::READABLE)
+        .unwrap();
+
+    assert_error(
+        poll.registry()
+            .deregister_batch(&mut [&mut listener1, &mut listener2]),
+        "not registered",
+    );
+    // `listener2` was deregistered, so registering it again must succeed.
+    poll.registry()
+        .register(&mut listener2, ID2, Interest::READABLE)
+        .unwrap();
+}
+
 struct TestEventSource {
     registrations: Vec<(Token, Interest)>,
     reregistrations: Vec<(Token, Interest)>,
@@ -605,6 +748,58 @@ fn poll_registration() {
     assert_eq!(source.deregister_count, 1);
 }
 
+#[test]
+fn deregister_batch() {
+    init();
+    let poll = Poll::new().unwrap();
+    let registry = poll.registry();
+
+    let mut source1 = TestEventSource::new();
+    let mut source2 = TestEventSource::new();
+    registry
+        .register(&mut source1, ID1, Interest::READABLE)
+        .unwrap();
+    registry
+        .register(&mut source2, ID2, Interest::READABLE)
+        .unwrap();
+
+    registry
+        .deregister_batch(&mut [&mut source1, &mut source2])
+        .unwrap();
+    assert_eq!(source1.deregister_count, 1);
+    assert_eq!(source2.deregister_count, 1);
+}
+
+#[test]
+fn deregister_batch_no_events() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut socket2, ID2, Interest::READABLE)
+        .unwrap();
+
+    poll.registry()
+        .deregister_batch(&mut [&mut socket1 as &mut dyn Source, &mut socket2])
+        .unwrap();
+
+    sender
+        .send_to(b"hello", socket1.local_addr().unwrap())
+        .unwrap();
+    sender
+        .send_to(b"hello", socket2.local_addr().unwrap())
+        .unwrap();
+
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert!(events.is_empty());
+}
+
 struct ErroneousTestEventSource;
 
 impl event::Source for ErroneousTestEventSource {
@@ -661,3 +856,163 @@ pub fn assert_error<T, E: fmt::Display>(result: Result<T, E>, expected_msg: &str
         ),
     }
 }
+
+#[test]
+#[cfg(unix)]
+fn register_more_than_1024_fds() {
+    use std::mem;
+
+    const N: usize = 2000;
+
+    init();
+
+    // Make sure we're allowed to open enough file descriptors.
+    unsafe {
+        let mut limit: libc::rlimit = mem::zeroed();
+        assert_eq!(libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit), 0);
+        let needed = (N + 64) as libc::rlim_t;
+        if limit.rlim_cur < needed {
+            if limit.rlim_max < needed {
+                // Can't open enough file descriptors.
+                return;
+            }
+            limit.rlim_cur = needed;
+            assert_eq!(libc::setrlimit(libc::RLIMIT_NOFILE, &limit), 0);
+        }
+    }
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+
+    let mut sockets = (0..N)
+        .map(|_| UdpSocket::bind(any_local_address()).unwrap())
+        .collect::<Vec<_>>();
+    for (n, socket) in sockets.iter_mut().enumerate() {
+        poll.registry()
+            .register(socket, Token(n), Interest::READABLE)
+            .unwrap();
+    }
+
+    let sender = net::UdpSocket::bind(any_local_address()).unwrap();
+    let ready = [0, 1023, 1024, 1500, N - 1];
+    for n in ready {
+        sender
+            .send_to(b"Hello", sockets[n].local_addr().unwrap())
+            .unwrap();
+    }
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        ready
+            .iter()
+            .map(|n| ExpectEvent::new(Token(*n), Interest::READABLE))
+            .collect(),
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn poll_interrupted_by_signal() {
+    use std::mem;
+
+    extern "C" fn noop_handler(_: libc::c_int) {}
+
+    init();
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    // Install a signal handler without `SA_RESTART`, the default action of
+    // `SIGUSR1` would terminate the process.
+    unsafe {
+        let mut action: libc::sigaction = mem::zeroed();
+        action.sa_sigaction = noop_handler as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        libc::sigemptyset(&mut action.sa_mask);
+        assert_eq!(
+            libc::sigaction(libc::SIGUSR1, &action, std::ptr::null_mut()),
+            0
+        );
+    }
+
+    // `pthread_t` isn't `Send` on all platforms.
+    let thread = unsafe { libc::pthread_self() } as usize;
+    let handle = thread::spawn(move || {
+        sleep(Duration::from_millis(100));
+        assert_eq!(
+            unsafe { libc::pthread_kill(thread as libc::pthread_t, libc::SIGUSR1) },
+            0
+        );
+    });
+
+    // `poll` doesn't retry if it's interrupted, allowing the caller to react
+    // to the signal.
+    let err = poll
+        .poll(&mut events, Some(Duration::from_secs(10)))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+    handle.join().unwrap();
+}
+
+#[test]
+fn poll_now() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    let start = Instant::now();
+    poll.poll_now(&mut events).unwrap();
+    assert!(events.is_empty());
+    assert!(start.elapsed() < Duration::from_millis(100));
+
+    // Events that are ready are still returned.
+    let _stream = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let start = Instant::now();
+    loop {
+        poll.poll_now(&mut events).unwrap();
+        if !events.is_empty() {
+            break;
+        }
+        assert!(start.elapsed() < Duration::from_secs(5), "no events");
+        sleep(Duration::from_millis(10));
+    }
+    let event = events.iter().next().unwrap();
+    assert_eq!(event.token(), ID1);
+    assert!(event.is_readable());
+}
+
+#[test]
+fn poll_drain() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    // Smaller than the number of sources, forcing multiple polls.
+    let mut events = Events::with_capacity(2);
+
+    // UDP sockets are always writable.
+    let mut sockets: Vec<UdpSocket> = (0..5)
+        .map(|_| UdpSocket::bind(any_local_address()).unwrap())
+        .collect();
+    for (n, socket) in sockets.iter_mut().enumerate() {
+        poll.registry()
+            .register(socket, Token(n), Interest::WRITABLE)
+            .unwrap();
+    }
+
+    let mut seen = Vec::new();
+    let n = poll
+        .poll_drain(&mut events, |event| {
+            assert!(event.is_writable());
+            seen.push(event.token());
+        })
+        .unwrap();
+    assert_eq!(n, seen.len());
+    seen.sort();
+    seen.dedup();
+    assert_eq!(seen, (0..5).map(Token).collect::<Vec<_>>());
+
+    // Nothing is ready anymore.
+    assert_eq!(poll.poll_drain(&mut events, |_| {}).unwrap(), 0);
+}
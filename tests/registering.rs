@@ -244,3 +244,30 @@ fn registering_after_deregistering() {
         .unwrap();
     assert!(events.is_empty());
 }
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn register_wakeup() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    match poll
+        .registry()
+        .register(&mut listener, SERVER, Interest::READABLE | Interest::WAKEUP)
+    {
+        Ok(()) => {}
+        // Older kernels might not allow `EPOLLWAKEUP` without
+        // `CAP_BLOCK_SUSPEND`.
+        Err(ref err) if err.kind() == io::ErrorKind::PermissionDenied => return,
+        Err(err) => panic!("unexpected error registering: {err}"),
+    }
+
+    let mut events = Events::with_capacity(8);
+    let _stream = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(500)))
+        .unwrap();
+    assert!(events
+        .iter()
+        .any(|event| event.token() == SERVER && event.is_readable()));
+}
@@ -4,11 +4,12 @@
 use mio::net::TcpListener;
 use mio::{Interest, Token};
 use std::io::{self, Read};
-use std::net::{self, SocketAddr};
+use std::net::{self, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 #[cfg(unix)]
 use std::os::fd::{AsRawFd, FromRawFd, IntoRawFd};
 use std::sync::{Arc, Barrier};
 use std::thread;
+use std::time::Duration;
 
 mod util;
 use util::{
@@ -132,6 +133,101 @@ fn raw_fd() {
     assert_eq!(listener.local_addr().unwrap(), address);
 }
 
+#[cfg(unix)]
+#[test]
+fn set_get_cloexec() {
+    init();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    assert!(listener.is_cloexec().unwrap());
+
+    listener.set_cloexec(false).unwrap();
+    assert!(!listener.is_cloexec().unwrap());
+    let flags = unsafe { libc::fcntl(listener.as_raw_fd(), libc::F_GETFD) };
+    assert_eq!(flags & libc::FD_CLOEXEC, 0, "CLOEXEC still set");
+
+    listener.set_cloexec(true).unwrap();
+    assert!(listener.is_cloexec().unwrap());
+    assert_socket_close_on_exec(&listener);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_get_defer_accept() {
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    assert_eq!(listener.defer_accept().unwrap(), Duration::ZERO);
+    listener.set_defer_accept(Duration::from_secs(5)).unwrap();
+    assert!(listener.defer_accept().unwrap() >= Duration::from_secs(5));
+    listener.set_defer_accept(Duration::ZERO).unwrap();
+    assert_eq!(listener.defer_accept().unwrap(), Duration::ZERO);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn defer_accept() {
+    use std::io::Write;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    listener.set_defer_accept(Duration::from_secs(10)).unwrap();
+    let address = listener.local_addr().unwrap();
+
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    // The connection is established, but no data is sent yet.
+    let mut stream = net::TcpStream::connect(address).unwrap();
+    expect_no_events(&mut poll, &mut events);
+    assert_would_block(listener.accept());
+
+    stream.write_all(b"hello").unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let (mut accepted, _) = listener.accept().unwrap();
+    let mut buf = [0; 5];
+    accepted.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hello");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn bind_freebind() {
+    // Address from TEST-NET-1, which isn't assigned to any local interface.
+    let addr = "192.0.2.1:0".parse().unwrap();
+    assert!(TcpListener::bind(addr).is_err());
+
+    let listener = match TcpListener::bind_freebind(addr) {
+        Ok(listener) => listener,
+        // Not allowed in some sandboxed environments.
+        Err(ref err) if err.kind() == io::ErrorKind::PermissionDenied => return,
+        Err(err) => panic!("unexpected error: {err}"),
+    };
+    assert!(listener.freebind().unwrap());
+    assert_eq!(listener.local_addr().unwrap().ip(), addr.ip());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn bind_transparent() {
+    let addr = "192.0.2.1:0".parse().unwrap();
+    let listener = match TcpListener::bind_transparent(addr) {
+        Ok(listener) => listener,
+        // Requires the `CAP_NET_ADMIN` capability.
+        Err(ref err) if err.kind() == io::ErrorKind::PermissionDenied => return,
+        Err(err) => panic!("unexpected error: {err}"),
+    };
+    assert!(listener.transparent().unwrap());
+    assert_eq!(listener.local_addr().unwrap().ip(), addr.ip());
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    assert!(!listener.transparent().unwrap());
+}
+
 #[test]
 fn registering() {
     let (mut poll, mut events) = init_with_poll();
@@ -147,6 +243,41 @@ fn registering() {
     // NOTE: more tests are done in the smoke tests above.
 }
 
+#[test]
+fn incoming() {
+    const N: usize = 5;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    let clients: Vec<net::TcpStream> = (0..N)
+        .map(|_| net::TcpStream::connect(address).unwrap())
+        .collect();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let accepted = listener.incoming().collect::<io::Result<Vec<_>>>().unwrap();
+    assert_eq!(accepted.len(), N);
+    for (stream, peer_address) in accepted.iter() {
+        assert_socket_non_blocking(stream);
+        assert_eq!(stream.peer_addr().unwrap(), *peer_address);
+        assert!(clients
+            .iter()
+            .any(|client| client.local_addr().unwrap() == *peer_address));
+    }
+
+    assert!(listener.incoming().next().is_none());
+}
+
 #[test]
 fn reregister() {
     let (mut poll, mut events) = init_with_poll();
@@ -268,6 +399,96 @@ fn tcp_listener_two_streams() {
     thread_handle2.join().expect("unable to join thread");
 }
 
+#[test]
+fn bind_dualstack() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listeners = mio::net::bind_dualstack(0).unwrap();
+    assert!(!listeners.is_empty() && listeners.len() <= 2);
+    let port = listeners[0].local_addr().unwrap().port();
+    for (n, listener) in listeners.iter_mut().enumerate() {
+        assert_eq!(listener.local_addr().unwrap().port(), port);
+        poll.registry()
+            .register(listener, Token(n), Interest::READABLE)
+            .unwrap();
+    }
+
+    // Without IPv6 support only a single IPv4 listener is returned.
+    let ipv6 = net::TcpListener::bind((Ipv6Addr::LOCALHOST, 0)).is_ok();
+    if !ipv6 {
+        assert_eq!(listeners.len(), 1);
+        assert!(listeners[0].local_addr().unwrap().is_ipv4());
+    }
+
+    let v4_client = net::TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+    let v6_client = ipv6.then(|| net::TcpStream::connect((Ipv6Addr::LOCALHOST, port)).unwrap());
+
+    let mut peers = Vec::new();
+    while peers.len() < 1 + usize::from(ipv6) {
+        poll.poll(&mut events, Some(Duration::from_secs(1)))
+            .unwrap();
+        assert!(!events.is_empty(), "no connections accepted");
+        for event in events.iter() {
+            let listener = &listeners[event.token().0];
+            loop {
+                match listener.accept() {
+                    Ok((_, peer)) => peers.push(peer),
+                    Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                    Err(err) => panic!("unexpected error accepting: {err}"),
+                }
+            }
+        }
+    }
+
+    // IPv4 connections accepted on an IPv6 socket use IPv4-mapped addresses.
+    let v4_peer = v4_client.local_addr().unwrap();
+    assert!(peers.iter().any(|peer| peer.port() == v4_peer.port()
+        && match peer.ip() {
+            IpAddr::V4(ip) => ip == Ipv4Addr::LOCALHOST,
+            IpAddr::V6(ip) => ip.to_ipv4_mapped() == Some(Ipv4Addr::LOCALHOST),
+        }));
+    if let Some(v6_client) = v6_client {
+        assert!(peers.contains(&v6_client.local_addr().unwrap()));
+    }
+}
+
+#[test]
+#[cfg(not(target_os = "openbsd"))]
+fn bind_with_only_v6() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let addr = SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), 0);
+    let mut listener = TcpListener::bind_with_only_v6(addr, false).unwrap();
+    assert!(!listener.only_v6().unwrap());
+    let port = listener.local_addr().unwrap().port();
+
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    let client = net::TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let (_, peer) = listener.accept().unwrap();
+    let client_addr = client.local_addr().unwrap();
+    let expected = SocketAddr::new(
+        Ipv4Addr::LOCALHOST.to_ipv6_mapped().into(),
+        client_addr.port(),
+    );
+    assert_eq!(peer, expected);
+
+    let listener = TcpListener::bind_with_only_v6(addr, true).unwrap();
+    assert!(listener.only_v6().unwrap());
+
+    let err = TcpListener::bind_with_only_v6(any_local_address(), false).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
 /// Start `n_connections` connections to `address`. If a `barrier` is provided
 /// it will wait on it after each connection is made before it is dropped.
 fn start_connections(
@@ -58,6 +58,15 @@ fn tcp_stream_std() {
     });
 }
 
+#[test]
+fn tcp_stream_std_nonblocking() {
+    smoke_test_tcp_stream(any_local_address(), |addr| {
+        // Blocking stream, `from_std_nonblocking` should change that.
+        let stream = net::TcpStream::connect(addr).unwrap();
+        TcpStream::from_std_nonblocking(stream)
+    });
+}
+
 fn smoke_test_tcp_stream<F>(addr: SocketAddr, make_stream: F)
 where
     F: FnOnce(SocketAddr) -> io::Result<TcpStream>,
@@ -196,6 +205,74 @@ fn get_ttl_without_previous_set() {
     thread_handle.join().expect("unable to join thread");
 }
 
+#[test]
+fn get_only_v6() {
+    let (thread_handle, address) = echo_listener(any_local_ipv6_address(), 1);
+    let stream = TcpStream::connect(address).unwrap();
+    // The default depends on the system configuration.
+    stream
+        .only_v6()
+        .expect("unable to get IPV6_V6ONLY for TCP stream");
+    drop(stream);
+    thread_handle.join().expect("unable to join thread");
+
+    let (thread_handle, address) = echo_listener(any_local_address(), 1);
+    let stream = TcpStream::connect(address).unwrap();
+    assert!(stream.only_v6().is_err(), "IPv4 stream has IPV6_V6ONLY");
+    drop(stream);
+    thread_handle.join().expect("unable to join thread");
+}
+
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+fn get_keepalive() {
+    let (thread_handle, address) = echo_listener(any_local_address(), 1);
+    let stream = TcpStream::connect(address).unwrap();
+
+    // Defaults depend on the system configuration.
+    assert!(stream.keepalive_time().unwrap() > Duration::ZERO);
+    assert!(stream.keepalive_interval().unwrap() > Duration::ZERO);
+    assert!(stream.keepalive_retries().unwrap() > 0);
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    {
+        let fd = stream.as_raw_fd();
+        let set = |opt, value: libc::c_int| {
+            let res = unsafe {
+                libc::setsockopt(
+                    fd,
+                    libc::IPPROTO_TCP,
+                    opt,
+                    (&value as *const libc::c_int).cast(),
+                    std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+                )
+            };
+            assert_eq!(res, 0, "setsockopt: {}", io::Error::last_os_error());
+        };
+        set(libc::TCP_KEEPIDLE, 30);
+        set(libc::TCP_KEEPINTVL, 5);
+        set(libc::TCP_KEEPCNT, 3);
+        assert_eq!(stream.keepalive_time().unwrap(), Duration::from_secs(30));
+        assert_eq!(stream.keepalive_interval().unwrap(), Duration::from_secs(5));
+        assert_eq!(stream.keepalive_retries().unwrap(), 3);
+    }
+
+    drop(stream);
+    thread_handle.join().expect("unable to join thread");
+}
+
 #[test]
 fn set_get_nodelay() {
     let (mut poll, mut events) = init_with_poll();
@@ -351,6 +428,44 @@ fn shutdown_write() {
     thread_handle.join().expect("unable to join thread");
 }
 
+#[test]
+fn shutdown_write_continue_reading() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .expect("unable to register TCP stream");
+
+    stream.shutdown(Shutdown::Write).unwrap();
+    // The peer receives the `FIN`.
+    let mut buf = [0; 20];
+    assert_eq!(peer.read(&mut buf).unwrap(), 0);
+
+    // But the local read half is still usable.
+    for data in [DATA1, DATA2] {
+        peer.write_all(data).unwrap();
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Interest::READABLE)],
+        );
+        expect_read!(stream.read(&mut buf), data);
+        assert_would_block(stream.read(&mut buf));
+    }
+
+    drop(peer);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    assert_eq!(stream.read(&mut buf).unwrap(), 0);
+}
+
 #[test]
 fn shutdown_both() {
     let (mut poll, mut events) = init_with_poll();
@@ -511,6 +626,64 @@ fn no_events_after_deregister() {
     thread_handle.join().expect("unable to join thread");
 }
 
+#[test]
+fn try_clone() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (thread_handle, address) = echo_listener(any_local_address(), 1);
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    let mut clone = stream.try_clone().unwrap();
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .expect("unable to register TCP stream");
+    poll.registry()
+        .register(&mut clone, ID2, Interest::READABLE)
+        .expect("unable to register cloned TCP stream");
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    assert_eq!(clone.peer_addr().unwrap(), address);
+    assert_eq!(clone.local_addr().unwrap(), stream.local_addr().unwrap());
+
+    checked_write!(stream.write(DATA1));
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+
+    let mut buf = [0; 16];
+    expect_read!(clone.read(&mut buf), DATA1);
+    assert_would_block(stream.read(&mut buf));
+
+    // Deregistering one handle doesn't affect the other.
+    poll.registry()
+        .deregister(&mut clone)
+        .expect("unable to deregister cloned TCP stream");
+    poll.registry()
+        .reregister(&mut stream, ID1, Interest::READABLE)
+        .expect("unable to reregister TCP stream");
+    checked_write!(clone.write(DATA2));
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    expect_read!(stream.read(&mut buf), DATA2);
+
+    drop(stream);
+    drop(clone);
+    thread_handle.join().expect("unable to join thread");
+}
+
 #[test]
 #[cfg_attr(
     windows,
@@ -622,6 +795,293 @@ fn tcp_shutdown_server_write_close_event() {
     handle.join().expect("failed to join thread");
 }
 
+#[test]
+fn connect_refused_error_event() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Get an address no one is listening on.
+    let address = net::TcpListener::bind(any_local_address())
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let mut stream = match TcpStream::connect(address) {
+        Ok(stream) => stream,
+        // Some platforms refuse the connection immediately.
+        Err(ref err) if err.kind() == io::ErrorKind::ConnectionRefused => return,
+        Err(err) => panic!("unexpected error connecting: {err}"),
+    };
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    loop {
+        poll.poll(&mut events, Some(Duration::from_secs(1)))
+            .unwrap();
+        assert!(!events.is_empty(), "no event for refused connection");
+        if let Some(event) = events.iter().find(|event| event.token() == ID1) {
+            assert!(event.is_error(), "no error in event: {event:?}");
+            break;
+        }
+    }
+    assert!(stream.take_error().unwrap().is_some());
+}
+
+#[test]
+fn take_error_once() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Get an address no one is listening on.
+    let address = net::TcpListener::bind(any_local_address())
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let mut stream = match TcpStream::connect(address) {
+        Ok(stream) => stream,
+        // Some platforms refuse the connection immediately.
+        Err(ref err) if err.kind() == io::ErrorKind::ConnectionRefused => return,
+        Err(err) => panic!("unexpected error connecting: {err}"),
+    };
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    // Windows retries the connection a couple of times before giving up.
+    for _ in 0..10 {
+        poll.poll(&mut events, Some(Duration::from_secs(1)))
+            .unwrap();
+        if events.iter().any(|event| event.token() == ID1) {
+            break;
+        }
+    }
+
+    let err = stream.take_error().unwrap().expect("no error");
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+    assert!(stream.take_error().unwrap().is_none());
+}
+
+#[test]
+fn poll_connected() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+    assert!(stream.poll_connected().unwrap());
+
+    // Refused connection.
+    let address = listener.local_addr().unwrap();
+    drop(listener);
+    let mut stream = match TcpStream::connect(address) {
+        Ok(stream) => stream,
+        Err(ref err) if err.kind() == io::ErrorKind::ConnectionRefused => return,
+        Err(err) => panic!("unexpected error connecting: {err}"),
+    };
+    poll.registry()
+        .register(&mut stream, ID2, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::WRITABLE)],
+    );
+    let err = stream.poll_connected().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+}
+
+#[test]
+#[cfg(windows)]
+fn poll_connected_refused_windows() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Get an address no one is listening on.
+    let address = net::TcpListener::bind(any_local_address())
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    // Not connected yet, but no error either.
+    assert!(!stream.poll_connected().unwrap());
+
+    // Windows retries the connection a couple of times before giving up.
+    for _ in 0..10 {
+        poll.poll(&mut events, Some(Duration::from_secs(1)))
+            .unwrap();
+        if events.iter().any(|event| event.token() == ID1) {
+            break;
+        }
+    }
+    let event = events.iter().find(|event| event.token() == ID1);
+    assert!(
+        event.is_some_and(|event| event.is_error()),
+        "no error event: {event:?}"
+    );
+    let err = stream.poll_connected().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+}
+
+#[test]
+fn recv_exact_nonblocking() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut frame = [0; 8];
+    assert_would_block(stream.recv_exact_nonblocking(&mut frame));
+
+    // Only the first half of the frame is available.
+    peer.write_all(&DATA1[..4]).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let read = stream.recv_exact_nonblocking(&mut frame).unwrap();
+    assert_eq!(read, 4);
+
+    // Resume once the remainder arrives.
+    peer.write_all(&DATA1[4..8]).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let read = read + stream.recv_exact_nonblocking(&mut frame[read..]).unwrap();
+    assert_eq!(read, 8);
+    assert_eq!(&frame, &DATA1[..8]);
+    assert_would_block(stream.recv_exact_nonblocking(&mut frame));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn hup_event() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+
+    // Shutting down one half of the connection is not a hang up.
+    peer.shutdown(Shutdown::Write).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Readiness::READ_CLOSED)],
+    );
+    assert!(events.iter().all(|event| !event.is_hup()));
+
+    // With both halves closed it is, without being an error.
+    stream.shutdown(Shutdown::Write).unwrap();
+    drop(peer);
+    loop {
+        poll.poll(&mut events, Some(Duration::from_secs(1)))
+            .unwrap();
+        assert!(!events.is_empty(), "no hang up event");
+        if let Some(event) = events.iter().find(|event| event.is_hup()) {
+            assert!(event.is_read_closed() && event.is_write_closed());
+            assert!(!event.is_error(), "hang up reported as error: {event:?}");
+            break;
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(target_os = "solaris", ignore = "POLLRDHUP isn't supported on Solaris")]
+#[cfg_attr(target_os = "nto", ignore = "POLLRDHUP isn't supported on NTO")]
+fn read_hup_event() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+
+    peer.shutdown(Shutdown::Write).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Readiness::READ_CLOSED)],
+    );
+    let event = events.iter().next().unwrap();
+    assert!(event.is_read_hup(), "no read hang up: {event:?}");
+    assert!(!event.is_hup(), "half close reported as hang up: {event:?}");
+
+    // The write half of the connection is still usable.
+    checked_write!(stream.write(DATA1));
+}
+
+#[test]
+#[cfg_attr(target_os = "solaris", ignore = "POLLRDHUP isn't supported on Solaris")]
+#[cfg_attr(target_os = "nto", ignore = "POLLRDHUP isn't supported on NTO")]
+fn peer_shutdown_write_only_read_closed() {
+    use std::time::Instant;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE | Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    peer.shutdown(Shutdown::Write).unwrap();
+
+    let mut read_closed = false;
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while Instant::now() < deadline {
+        poll.poll(&mut events, Some(Duration::from_millis(100)))
+            .unwrap();
+        for event in events.iter() {
+            assert!(
+                !event.is_write_closed(),
+                "peer write shutdown reported as write closed: {event:?}"
+            );
+            read_closed |= event.is_read_closed();
+        }
+        if read_closed && events.is_empty() {
+            break;
+        }
+    }
+    assert!(read_closed, "no read closed event");
+
+    // The write half is still open.
+    checked_write!(stream.write(DATA1));
+    let mut buf = [0; 16];
+    let n = peer.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+}
+
 #[test]
 #[cfg_attr(target_os = "solaris", ignore = "POLLRDHUP isn't supported on Solaris")]
 #[cfg_attr(target_os = "nto", ignore = "POLLRDHUP isn't supported on NTO")]
@@ -844,10 +1304,37 @@ fn priority_event_on_oob_data() {
     );
 }
 
+#[test]
 #[cfg(any(target_os = "linux", target_os = "android"))]
-fn send_oob_data<S: AsRawFd>(stream: &S, data: &[u8]) -> io::Result<usize> {
-    unsafe {
-        let res = libc::send(
+fn at_mark() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (peer, _) = listener.accept().unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+
+    // Only the last byte is urgent data, the mark is placed before it.
+    send_oob_data(&peer, DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    assert!(!stream.at_mark().unwrap());
+
+    // Reads stop at the mark.
+    let mut buf = [0; 20];
+    expect_read!(stream.read(&mut buf), &DATA1[..DATA1_LEN - 1]);
+    assert!(stream.at_mark().unwrap());
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn send_oob_data<S: AsRawFd>(stream: &S, data: &[u8]) -> io::Result<usize> {
+    unsafe {
+        let res = libc::send(
             stream.as_raw_fd(),
             data.as_ptr().cast(),
             data.len(),
@@ -860,3 +1347,563 @@ fn send_oob_data<S: AsRawFd>(stream: &S, data: &[u8]) -> io::Result<usize> {
         }
     }
 }
+
+#[test]
+#[cfg(target_os = "linux")]
+fn incoming_cpu() {
+    use mio::net::TcpListener;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut client = net::TcpStream::connect(addr).unwrap();
+    client.write_all(DATA1).unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let (stream, _) = listener.accept().unwrap();
+    let cpu = stream.incoming_cpu().unwrap();
+    let n_cpus = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) };
+    assert!(
+        cpu >= -1 && i64::from(cpu) < n_cpus,
+        "implausible CPU index: {cpu}"
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn set_get_busy_poll() {
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    const TIMEOUT: Duration = Duration::from_micros(50);
+    match stream.set_busy_poll(TIMEOUT) {
+        Ok(()) => assert_eq!(stream.busy_poll().unwrap(), TIMEOUT),
+        // Increasing the value requires `CAP_NET_ADMIN`.
+        Err(ref err) if err.kind() == io::ErrorKind::PermissionDenied => {}
+        Err(err) => panic!("unexpected error setting busy poll: {err}"),
+    }
+
+    stream.set_busy_poll(Duration::ZERO).unwrap();
+    assert_eq!(stream.busy_poll().unwrap(), Duration::ZERO);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn send_zerocopy() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    stream.set_zerocopy(true).unwrap();
+    assert!(stream.zerocopy().unwrap());
+
+    const SIZE: usize = 64 * 1024;
+    let buf = vec![1; SIZE];
+    let (n, id) = stream.send_zerocopy(&buf).unwrap();
+    assert!(n > 0);
+    assert_eq!(id, 0);
+
+    // Clones share the sequence counter of the socket.
+    let clone = stream.try_clone().unwrap();
+    let (n2, id2) = clone.send_zerocopy(&buf[..1024]).unwrap();
+    assert_eq!(n2, 1024);
+    assert_eq!(id2, 1);
+
+    let handle = thread::spawn(move || {
+        let mut buf = vec![0; SIZE + 1024];
+        peer.read_exact(&mut buf[..n + n2]).unwrap();
+    });
+
+    // The completion is reported on the error queue, which triggers an error
+    // event.
+    let mut completions = Vec::new();
+    for _ in 0..10 {
+        poll.poll(&mut events, Some(Duration::from_millis(500)))
+            .unwrap();
+        if events
+            .iter()
+            .any(|event| event.token() == ID1 && event.is_error())
+        {
+            completions.extend(stream.poll_zerocopy_completions().unwrap());
+            if [id, id2]
+                .iter()
+                .all(|id| completions.iter().any(|c| c.contains(*id)))
+            {
+                break;
+            }
+        }
+    }
+    for id in [id, id2] {
+        assert!(
+            completions.iter().any(|completion| completion.contains(id)),
+            "missing completion for send {id}: {completions:?}"
+        );
+    }
+
+    assert!(stream.poll_zerocopy_completions().unwrap().is_empty());
+    handle.join().unwrap();
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn recv_lowat() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    stream.set_recv_lowat(100).unwrap();
+    assert_eq!(stream.recv_lowat().unwrap(), 100);
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+
+    // Below the watermark, so no event.
+    peer.write_all(&[1; 50]).unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    // Reaching the watermark should trigger an event.
+    peer.write_all(&[2; 50]).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; 128];
+    assert_eq!(stream.read(&mut buf).unwrap(), 100);
+}
+
+#[test]
+fn set_nonblocking() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    stream.set_nonblocking(false).unwrap();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        peer.write_all(DATA1).unwrap();
+        peer
+    });
+    // Blocks until the data arrives.
+    let mut buf = [0; DATA1_LEN];
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, DATA1);
+    let mut peer = handle.join().unwrap();
+
+    stream.set_nonblocking(true).unwrap();
+    assert_socket_non_blocking(&stream);
+    assert_would_block(stream.read(&mut buf));
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+    peer.write_all(DATA2).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    expect_read!(stream.read(&mut buf), DATA2);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn corked() {
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+    peer.set_nonblocking(true).unwrap();
+
+    assert!(!stream.cork().unwrap());
+    stream
+        .corked(|mut stream| {
+            assert!(stream.cork().unwrap());
+            checked_write!(stream.write(DATA1));
+            checked_write!(stream.write(DATA2));
+            // Small writes are held back while corked.
+            let mut buf = [0; 64];
+            assert_would_block(peer.read(&mut buf));
+            Ok(())
+        })
+        .unwrap();
+    assert!(!stream.cork().unwrap());
+
+    // After uncorking both chunks are sent together.
+    peer.set_nonblocking(false).unwrap();
+    let mut buf = [0; 64];
+    let n = peer.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], [DATA1, DATA2].concat());
+
+    // The socket is also uncorked if `f` returns an error.
+    let err = stream
+        .corked(|_| -> io::Result<()> { Err(io::ErrorKind::Other.into()) })
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+    assert!(!stream.cork().unwrap());
+}
+
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+fn send_file() {
+    use std::fs::{self, File};
+
+    const LEN: usize = 256 * 1024;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let data = (0..LEN).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    let path = util::temp_file("tcp_stream_send_file");
+    fs::write(&path, &data).unwrap();
+    let file = File::open(&path).unwrap();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+    let reader = thread::spawn(move || {
+        let mut received = Vec::new();
+        peer.read_to_end(&mut received).unwrap();
+        received
+    });
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    // A zero count sends nothing.
+    assert_eq!(stream.send_file(&file, 0, 0).unwrap(), (0, 0));
+
+    let mut offset = 0;
+    while offset < LEN as u64 {
+        match stream.send_file(&file, offset, LEN - offset as usize) {
+            Ok((n, new_offset)) => {
+                assert!(n > 0);
+                assert_eq!(new_offset, offset + n as u64);
+                offset = new_offset;
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                poll.poll(&mut events, Some(Duration::from_secs(5)))
+                    .unwrap();
+            }
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    }
+    // End of file.
+    assert_eq!(stream.send_file(&file, offset, 1).unwrap(), (0, offset));
+
+    stream.shutdown(Shutdown::Write).unwrap();
+    let received = reader.join().unwrap();
+    assert!(received == data, "received data doesn't match file");
+}
+
+#[test]
+fn write_all_nonblocking() {
+    const LEN: usize = 8 * 1024 * 1024;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let data = (0..LEN).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    #[cfg(unix)]
+    set_recv_buffer_size(&listener, 4096);
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    // The peer isn't reading, so only part of the data can be written.
+    let written = stream.write_all_nonblocking(&data).unwrap();
+    assert!(written > 0 && written < LEN, "written: {}", written);
+    // Calling it again doesn't block either.
+    assert_eq!(stream.write_all_nonblocking(&data[written..]).unwrap(), 0);
+
+    let reader = thread::spawn(move || {
+        let mut received = Vec::new();
+        peer.read_to_end(&mut received).unwrap();
+        received
+    });
+
+    let mut written = written;
+    while written < LEN {
+        let n = stream.write_all_nonblocking(&data[written..]).unwrap();
+        written += n;
+        if written < LEN {
+            poll.poll(&mut events, Some(Duration::from_secs(5)))
+                .unwrap();
+        }
+    }
+    stream.shutdown(Shutdown::Write).unwrap();
+    let received = reader.join().unwrap();
+    assert!(received == data, "received data doesn't match");
+}
+
+#[test]
+fn write_all_vectored_nonblocking() {
+    const LEN: usize = 8 * 1024 * 1024;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let data = (0..LEN).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    // Buffers of different sizes, including an empty one.
+    let (a, rest) = data.split_at(1000);
+    let (b, rest) = rest.split_at(LEN / 2);
+    let bufs = [
+        IoSlice::new(a),
+        IoSlice::new(&[]),
+        IoSlice::new(b),
+        IoSlice::new(rest),
+    ];
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    #[cfg(unix)]
+    set_recv_buffer_size(&listener, 4096);
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    let written = stream.write_all_vectored_nonblocking(&bufs).unwrap();
+    assert!(written > 0 && written < LEN, "written: {}", written);
+
+    let reader = thread::spawn(move || {
+        let mut received = Vec::new();
+        peer.read_to_end(&mut received).unwrap();
+        received
+    });
+
+    // Continue with the remaining data, using a single buffer.
+    let mut written = written;
+    while written < LEN {
+        let n = stream
+            .write_all_vectored_nonblocking(&[IoSlice::new(&data[written..])])
+            .unwrap();
+        written += n;
+        if written < LEN {
+            poll.poll(&mut events, Some(Duration::from_secs(5)))
+                .unwrap();
+        }
+    }
+    stream.shutdown(Shutdown::Write).unwrap();
+    let received = reader.join().unwrap();
+    assert!(received == data, "received data doesn't match");
+}
+
+/// Set `SO_RCVBUF` on `listener`, which is inherited by accepted sockets.
+#[cfg(unix)]
+fn set_recv_buffer_size(listener: &net::TcpListener, size: libc::c_int) {
+    let res = unsafe {
+        libc::setsockopt(
+            listener.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_RCVBUF,
+            &size as *const _ as *const libc::c_void,
+            std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+    assert_eq!(res, 0, "setsockopt: {}", io::Error::last_os_error());
+}
+
+#[test]
+#[cfg(windows)]
+fn share() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    let info = stream.share(std::process::id()).unwrap();
+    let mut shared = TcpStream::from_shared(&info).unwrap();
+    drop(stream);
+    assert_eq!(shared.peer_addr().unwrap(), listener.local_addr().unwrap());
+
+    poll.registry()
+        .register(&mut shared, ID1, Interest::READABLE | Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+    checked_write!(shared.write(DATA1));
+    let mut buf = [0; 16];
+    peer.read_exact(&mut buf[..DATA1_LEN]).unwrap();
+    assert_eq!(&buf[..DATA1_LEN], DATA1);
+
+    peer.write_all(DATA2).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    expect_read!(shared.read(&mut buf), DATA2);
+
+    let err = TcpStream::from_shared(&info[1..]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+#[cfg(all(
+    not(mio_unsupported_force_poll_poll),
+    any(
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ),
+))]
+fn set_read_lowat() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry().set_read_lowat(&stream, ID1, 100).unwrap();
+
+    // Below the watermark no event should be returned.
+    peer.write_all(&[1; 50]).unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    peer.write_all(&[2; 50]).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut buf = [0; 128];
+    assert_eq!(stream.read(&mut buf).unwrap(), 100);
+}
+
+#[test]
+#[cfg(any(unix, windows))]
+fn available() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+    assert_eq!(stream.available().unwrap(), 0);
+
+    peer.write_all(&[1; 500]).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    assert_eq!(stream.available().unwrap(), 500);
+
+    let mut buf = [0; 1024];
+    assert_eq!(stream.read(&mut buf).unwrap(), 500);
+    assert_eq!(stream.available().unwrap(), 0);
+}
+
+#[test]
+#[cfg(feature = "socket2")]
+fn try_from_socket2() {
+    use socket2::{Domain, SockRef, Socket, Type};
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    let socket = Socket::new(Domain::for_address(address), Type::STREAM, None).unwrap();
+    socket.set_nodelay(true).unwrap();
+    socket.connect(&address.into()).unwrap();
+
+    let mut stream = TcpStream::try_from(socket).unwrap();
+    assert_socket_non_blocking(&stream);
+    assert!(stream.nodelay().unwrap());
+    // Borrowing as `socket2::SockRef`.
+    assert!(SockRef::from(&stream).nodelay().unwrap());
+
+    let (mut peer, _) = listener.accept().unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+    peer.write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut buf = [0; 20];
+    expect_read!(stream.read(&mut buf), DATA1);
+
+    let socket = Socket::from(stream);
+    assert!(socket.nodelay().unwrap());
+
+    // Only stream sockets can be converted.
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, None).unwrap();
+    socket.bind(&any_local_address().into()).unwrap();
+    let err = TcpStream::try_from(socket).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
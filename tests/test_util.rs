@@ -0,0 +1,182 @@
+#![cfg(not(target_os = "wasi"))]
+#![cfg(all(feature = "test-util", feature = "net"))]
+
+use std::collections::VecDeque;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use mio::test::{DeterministicPoll, Readiness, Selector};
+use mio::{Events, Poll, Token};
+
+mod util;
+use util::init;
+
+/// Selector that returns a predetermined sequence of events, one batch per
+/// poll, recording the timeouts it was called with.
+struct ScriptedSelector {
+    script: VecDeque<Vec<(Token, Readiness)>>,
+    timeouts: Arc<Mutex<Vec<Option<Duration>>>>,
+}
+
+impl Selector for ScriptedSelector {
+    fn select(&mut self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        self.timeouts.lock().unwrap().push(timeout);
+        match self.script.pop_front() {
+            Some(batch) => {
+                for (token, readiness) in batch {
+                    events.push(token, readiness);
+                }
+                Ok(())
+            }
+            None => Err(io::Error::new(io::ErrorKind::Other, "script finished")),
+        }
+    }
+}
+
+#[test]
+fn scripted_selector() {
+    init();
+
+    let timeouts = Arc::new(Mutex::new(Vec::new()));
+    let selector = ScriptedSelector {
+        script: VecDeque::from(vec![
+            vec![(Token(0), Readiness::READABLE)],
+            vec![],
+            vec![
+                (Token(1), Readiness::WRITABLE),
+                (Token(2), Readiness::READABLE | Readiness::READ_CLOSED),
+                (Token(3), Readiness::ERROR),
+            ],
+        ]),
+        timeouts: timeouts.clone(),
+    };
+    let mut poll = Poll::with_selector(Box::new(selector)).unwrap();
+    let mut events = Events::with_capacity(16);
+
+    poll.poll(&mut events, None).unwrap();
+    let got = events.iter().collect::<Vec<_>>();
+    assert_eq!(got.len(), 1);
+    assert_eq!(got[0].token(), Token(0));
+    assert!(got[0].is_readable());
+    assert!(!got[0].is_writable());
+
+    poll.poll(&mut events, Some(Duration::from_millis(10)))
+        .unwrap();
+    assert!(events.is_empty());
+
+    poll.poll(&mut events, Some(Duration::ZERO)).unwrap();
+    let mut writable = false;
+    let mut read_closed = false;
+    let mut error = false;
+    for event in events.iter() {
+        match event.token() {
+            Token(1) => writable |= event.is_writable(),
+            Token(2) => read_closed |= event.is_readable() && event.is_read_closed(),
+            Token(3) => error |= event.is_error(),
+            token => panic!("unexpected token: {:?}", token),
+        }
+    }
+    assert!(writable && read_closed && error);
+
+    let err = poll.poll(&mut events, None).unwrap_err();
+    assert_eq!(err.to_string(), "script finished");
+
+    assert_eq!(
+        *timeouts.lock().unwrap(),
+        vec![
+            None,
+            Some(Duration::from_millis(10)),
+            Some(Duration::ZERO),
+            None
+        ]
+    );
+}
+
+#[test]
+fn deterministic_poll_order() {
+    init();
+
+    let deterministic = DeterministicPoll::new();
+    let mut poll = deterministic.new_poll().unwrap();
+    let mut events = Events::with_capacity(2);
+
+    deterministic.enqueue(Token(0), Readiness::READABLE);
+    deterministic.enqueue(Token(1), Readiness::WRITABLE);
+    deterministic.enqueue(Token(2), Readiness::READABLE);
+
+    // Events are returned in order, at most `capacity` at a time.
+    poll.poll(&mut events, Some(Duration::ZERO)).unwrap();
+    assert_eq!(tokens(&events), vec![Token(0), Token(1)]);
+    poll.poll(&mut events, Some(Duration::ZERO)).unwrap();
+    assert_eq!(tokens(&events), vec![Token(2)]);
+    poll.poll(&mut events, Some(Duration::ZERO)).unwrap();
+    assert!(events.is_empty());
+    assert_eq!(deterministic.now(), Duration::ZERO);
+}
+
+#[test]
+fn deterministic_poll_virtual_time() {
+    init();
+
+    let deterministic = DeterministicPoll::new();
+    let mut poll = deterministic.new_poll().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    deterministic.enqueue_after(Duration::from_secs(60), Token(1), Readiness::READABLE);
+    deterministic.enqueue_after(Duration::from_secs(5), Token(0), Readiness::WRITABLE);
+
+    // Times out without actually waiting.
+    let start = Instant::now();
+    poll.poll(&mut events, Some(Duration::from_secs(2)))
+        .unwrap();
+    assert!(events.is_empty());
+    assert_eq!(deterministic.now(), Duration::from_secs(2));
+
+    // The clock is advanced to the next event, which is before the deadline.
+    poll.poll(&mut events, Some(Duration::from_secs(10)))
+        .unwrap();
+    assert_eq!(tokens(&events), vec![Token(0)]);
+    assert_eq!(deterministic.now(), Duration::from_secs(5));
+
+    deterministic.advance(Duration::from_secs(60));
+    poll.poll(&mut events, Some(Duration::ZERO)).unwrap();
+    assert_eq!(tokens(&events), vec![Token(1)]);
+    assert_eq!(deterministic.now(), Duration::from_secs(65));
+
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
+
+#[test]
+fn deterministic_poll_wake() {
+    init();
+
+    let deterministic = DeterministicPoll::new();
+    let mut poll = deterministic.new_poll().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    // Multiple wakes are coalesced.
+    deterministic.wake(Token(10));
+    deterministic.wake(Token(10));
+    poll.poll(&mut events, None).unwrap();
+    assert_eq!(tokens(&events), vec![Token(10)]);
+    assert!(events.iter().next().unwrap().is_readable());
+
+    // Wake a blocked poll from another thread.
+    let waker = deterministic.clone();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        waker.wake(Token(11));
+    });
+    poll.poll(&mut events, None).unwrap();
+    assert_eq!(tokens(&events), vec![Token(11)]);
+    handle.join().unwrap();
+
+    // Waking doesn't affect the virtual clock.
+    assert_eq!(deterministic.now(), Duration::ZERO);
+}
+
+fn tokens(events: &Events) -> Vec<Token> {
+    events.iter().map(|event| event.token()).collect()
+}
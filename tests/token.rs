@@ -0,0 +1,123 @@
+use mio::{Token, TokenAllocator};
+
+#[test]
+fn token_allocator() {
+    let mut tokens = TokenAllocator::new();
+    assert!(tokens.is_empty());
+
+    let t1 = tokens.alloc();
+    let t2 = tokens.alloc();
+    assert_ne!(t1, t2);
+    assert_eq!(tokens.len(), 2);
+    assert!(tokens.is_allocated(t1));
+    assert!(tokens.is_allocated(t2));
+
+    assert!(tokens.free(t1));
+    assert!(!tokens.is_allocated(t1));
+    // Freeing twice is not allowed.
+    assert!(!tokens.free(t1));
+    assert_eq!(tokens.len(), 1);
+
+    // The freed slot is reused, but the token is different.
+    let t3 = tokens.alloc();
+    assert_eq!(TokenAllocator::index(t3), TokenAllocator::index(t1));
+    assert_ne!(t3, t1);
+    assert!(tokens.is_allocated(t3));
+    // A stale token doesn't match the new generation.
+    assert!(!tokens.is_allocated(t1));
+    assert!(!tokens.free(t1));
+    assert_eq!(tokens.len(), 2);
+
+    // New slots are only created if none are free.
+    let t4 = tokens.alloc();
+    assert_eq!(TokenAllocator::index(t4), 2);
+
+    // Tokens not created by the allocator.
+    assert!(!tokens.is_allocated(Token(100)));
+    assert!(!tokens.free(Token(100)));
+}
+
+#[test]
+fn token_allocator_generation_wraps() {
+    let mut tokens = TokenAllocator::new();
+    let first = tokens.alloc();
+
+    // The generation uses the high quarter of the bits (8 bits on 32 bit
+    // platforms, 16 bits on 64 bit platforms), after that many reuses the
+    // same token is handed out again.
+    let generations = 1usize << (usize::BITS / 4);
+    let mut token = first;
+    for _ in 0..generations {
+        assert!(tokens.free(token));
+        token = tokens.alloc();
+        assert_eq!(TokenAllocator::index(token), TokenAllocator::index(first));
+    }
+    assert_eq!(token, first);
+}
+
+#[test]
+#[cfg(all(feature = "os-poll", feature = "net"))]
+fn remove_stale_events() {
+    use std::time::Duration;
+
+    use mio::net::UdpSocket;
+    use mio::{Events, Interest, Poll};
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+    let mut tokens = TokenAllocator::new();
+
+    let mut socket1 = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    let mut socket2 = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    let sender = UdpSocket::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+
+    let token1 = tokens.alloc();
+    poll.registry()
+        .register(&mut socket1, token1, Interest::READABLE)
+        .unwrap();
+
+    sender
+        .send_to(b"hello", socket1.local_addr().unwrap())
+        .unwrap();
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    assert!(events.iter().any(|event| event.token() == token1));
+
+    // While processing the events the first socket is closed and its slot is
+    // reused for the second socket.
+    poll.registry().deregister(&mut socket1).unwrap();
+    assert!(tokens.free(token1));
+    let token2 = tokens.alloc();
+    assert_eq!(TokenAllocator::index(token1), TokenAllocator::index(token2));
+    poll.registry()
+        .register(&mut socket2, token2, Interest::READABLE)
+        .unwrap();
+
+    // The event for the first socket must not be delivered to the second.
+    tokens.remove_stale(&mut events);
+    assert!(events.is_empty());
+
+    // Events for the new source are kept.
+    sender
+        .send_to(b"hello", socket2.local_addr().unwrap())
+        .unwrap();
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    tokens.remove_stale(&mut events);
+    let mut iter = events.iter();
+    let event = iter.next().unwrap();
+    assert_eq!(event.token(), token2);
+    assert!(event.is_readable());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn token_serde() {
+    let tokens = [Token(0), Token(42), Token::SHUTDOWN];
+    for token in tokens {
+        let json = serde_json::to_string(&token).unwrap();
+        assert_eq!(json, token.0.to_string());
+        assert_eq!(serde_json::from_str::<Token>(&json).unwrap(), token);
+    }
+}
@@ -4,7 +4,7 @@
 use log::{debug, info};
 use mio::net::UdpSocket;
 use mio::{Events, Interest, Poll, Registry, Token};
-use std::net::{self, IpAddr, SocketAddr};
+use std::net::{self, IpAddr, Ipv4Addr, SocketAddr};
 #[cfg(unix)]
 use std::os::fd::{AsRawFd, FromRawFd, IntoRawFd};
 use std::str;
@@ -74,6 +74,254 @@ fn empty_datagram() {
     expect_read!(s2.recv_from(&mut buf), EMPTY, s1.local_addr().unwrap());
 }
 
+#[test]
+#[cfg(unix)]
+fn recv_from_trunc() {
+    let (mut poll, mut events) = init_with_poll();
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let sender = net::UdpSocket::bind(any_local_address()).unwrap();
+    let address = socket.local_addr().unwrap();
+    let sender_address = sender.local_addr().unwrap();
+
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    sender.send_to(&[1; 2000], address).unwrap();
+    sender.send_to(DATA1, address).unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; 100];
+    let (n, truncated, from) = socket.recv_from_trunc(&mut buf).unwrap();
+    assert_eq!(n, 100);
+    assert!(truncated);
+    assert_eq!(from, sender_address);
+    assert!(buf.iter().all(|b| *b == 1));
+
+    let (n, truncated, from) = socket.recv_from_trunc(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert!(!truncated);
+    assert_eq!(from, sender_address);
+
+    assert_would_block(socket.recv_from_trunc(&mut buf));
+}
+
+#[test]
+#[cfg(unix)]
+fn recv_from_vectored() {
+    use std::io::IoSliceMut;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+    let sender_address = sender.local_addr().unwrap();
+
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    checked_write!(sender.send_to(DATA1, socket.local_addr().unwrap()));
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut header = [0; 4];
+    let mut payload = [0; 20];
+    let mut bufs = [IoSliceMut::new(&mut header), IoSliceMut::new(&mut payload)];
+    let (n, from) = socket.recv_from_vectored(&mut bufs).unwrap();
+    assert_eq!(n, DATA1.len());
+    assert_eq!(from, sender_address);
+    assert_eq!(&header, &DATA1[..4]);
+    assert_eq!(&payload[..n - 4], &DATA1[4..]);
+
+    let mut bufs = [IoSliceMut::new(&mut header), IoSliceMut::new(&mut payload)];
+    assert_would_block(socket.recv_from_vectored(&mut bufs));
+}
+
+#[test]
+#[cfg(unix)]
+fn send_to_vectored() {
+    use std::io::IoSlice;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+    let sender_address = sender.local_addr().unwrap();
+
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    let bufs = [IoSlice::new(&DATA1[..4]), IoSlice::new(&DATA1[4..])];
+    let n = sender
+        .send_to_vectored(&bufs, socket.local_addr().unwrap())
+        .unwrap();
+    assert_eq!(n, DATA1.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; 20];
+    expect_read!(socket.recv_from(&mut buf), DATA1, sender_address);
+    assert_would_block(socket.recv_from(&mut buf));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn send_to_with_flags() {
+    use mio::net::SendFlags;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+    let sender_address = sender.local_addr().unwrap();
+
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    let address = socket.local_addr().unwrap();
+    let flags = SendFlags::EMPTY | SendFlags::CONFIRM;
+    assert!(flags.is_confirm());
+    assert!(!SendFlags::EMPTY.is_confirm());
+    let n = sender.send_to_with_flags(DATA1, address, flags).unwrap();
+    assert_eq!(n, DATA1.len());
+    let n = sender
+        .send_to_with_flags(DATA2, address, SendFlags::EMPTY)
+        .unwrap();
+    assert_eq!(n, DATA2.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; 20];
+    expect_read!(socket.recv_from(&mut buf), DATA1, sender_address);
+    expect_read!(socket.recv_from(&mut buf), DATA2, sender_address);
+    assert_would_block(socket.recv_from(&mut buf));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn recv_from_with_flags_dont_wait() {
+    use mio::net::{RecvFlags, SendFlags};
+
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+    // Put the socket in blocking mode behind mio's back.
+    unsafe {
+        let flags = libc::fcntl(socket.as_raw_fd(), libc::F_GETFL);
+        assert!(libc::fcntl(socket.as_raw_fd(), libc::F_SETFL, flags & !libc::O_NONBLOCK) != -1);
+    }
+
+    let flags = RecvFlags::EMPTY | RecvFlags::DONT_WAIT;
+    assert!(flags.is_dont_wait());
+    assert!(!RecvFlags::EMPTY.is_dont_wait());
+    assert!(SendFlags::DONT_WAIT.is_dont_wait());
+    assert!(!SendFlags::DONT_WAIT.is_confirm());
+
+    let mut buf = [0; 20];
+    assert_would_block(socket.recv_from_with_flags(&mut buf, flags));
+
+    let address = socket.local_addr().unwrap();
+    let n = sender
+        .send_to_with_flags(DATA1, address, SendFlags::DONT_WAIT)
+        .unwrap();
+    assert_eq!(n, DATA1.len());
+    // Still works once data is available.
+    expect_read!(
+        socket.recv_from_with_flags(&mut buf, flags),
+        DATA1,
+        sender.local_addr().unwrap()
+    );
+    assert_would_block(socket.recv_from_with_flags(&mut buf, flags));
+}
+
+#[test]
+fn set_nonblocking() {
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+    let address = socket.local_addr().unwrap();
+    let sender = net::UdpSocket::bind(any_local_address()).unwrap();
+    let sender_address = sender.local_addr().unwrap();
+
+    socket.set_nonblocking(false).unwrap();
+    let mut buf = [0; 20];
+    #[cfg(target_os = "linux")]
+    assert_would_block(socket.recv_from_with_flags(&mut buf, mio::net::RecvFlags::DONT_WAIT));
+
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        sender.send_to(DATA1, address).unwrap();
+        sender
+    });
+    // Blocks until the data arrives.
+    expect_read!(socket.recv_from(&mut buf), DATA1, sender_address);
+    let sender = handle.join().unwrap();
+
+    socket.set_nonblocking(true).unwrap();
+    assert_would_block(socket.recv_from(&mut buf));
+    sender.send_to(DATA2, address).unwrap();
+    thread::sleep(Duration::from_millis(10));
+    expect_read!(socket.recv_from(&mut buf), DATA2, sender_address);
+}
+
+#[test]
+fn try_clone() {
+    let (mut poll, mut events) = init_with_poll();
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let mut clone = socket.try_clone().unwrap();
+    let peer = net::UdpSocket::bind(any_local_address()).unwrap();
+    let address = socket.local_addr().unwrap();
+    let peer_address = peer.local_addr().unwrap();
+
+    assert_eq!(clone.local_addr().unwrap(), address);
+
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut clone, ID2, Interest::WRITABLE)
+        .unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::WRITABLE)],
+    );
+
+    // Send using the clone, receive using the original.
+    checked_write!(clone.send_to(DATA1, peer_address));
+    let mut buf = [0; 20];
+    let (n, from) = peer.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(from, address);
+
+    peer.send_to(DATA2, address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    expect_read!(socket.recv_from(&mut buf), DATA2, peer_address);
+    assert_would_block(clone.recv_from(&mut buf));
+}
+
 #[test]
 fn is_send_and_sync() {
     assert_send::<UdpSocket>();
@@ -109,6 +357,16 @@ fn unconnected_udp_socket_std() {
     smoke_test_unconnected_udp_socket(socket1, socket2);
 }
 
+#[test]
+fn unconnected_udp_socket_std_nonblocking() {
+    let socket1 = net::UdpSocket::bind(any_local_address()).unwrap();
+    let socket2 = net::UdpSocket::bind(any_local_address()).unwrap();
+
+    let socket1 = UdpSocket::from_std_nonblocking(socket1).unwrap();
+    let socket2 = UdpSocket::from_std_nonblocking(socket2).unwrap();
+    smoke_test_unconnected_udp_socket(socket1, socket2);
+}
+
 fn smoke_test_unconnected_udp_socket(mut socket1: UdpSocket, mut socket2: UdpSocket) {
     let (mut poll, mut events) = init_with_poll();
 
@@ -211,6 +469,31 @@ fn get_broadcast_without_previous_set() {
         .expect("unable to get broadcast for UDP socket");
 }
 
+#[test]
+fn get_only_v6() {
+    let socket = UdpSocket::bind(any_local_ipv6_address()).unwrap();
+    // The default depends on the system configuration.
+    socket
+        .only_v6()
+        .expect("unable to get IPV6_V6ONLY for UDP socket");
+
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+    assert!(socket.only_v6().is_err(), "IPv4 socket has IPV6_V6ONLY");
+}
+
+#[test]
+#[cfg(unix)]
+fn set_get_dont_route() {
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+    assert!(!socket.dont_route().unwrap());
+
+    socket.set_dont_route(true).unwrap();
+    assert!(socket.dont_route().unwrap());
+
+    socket.set_dont_route(false).unwrap();
+    assert!(!socket.dont_route().unwrap());
+}
+
 #[test]
 fn set_get_multicast_loop_v4() {
     let socket1 = UdpSocket::bind(any_local_address()).unwrap();
@@ -253,6 +536,51 @@ fn get_multicast_ttl_v4_without_previous_set() {
         .expect("unable to get multicast_ttl_v4 for UDP socket");
 }
 
+#[test]
+fn set_get_multicast_if_v4() {
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+
+    assert_eq!(socket1.multicast_if_v4().unwrap(), Ipv4Addr::UNSPECIFIED);
+    socket1.set_multicast_if_v4(Ipv4Addr::LOCALHOST).unwrap();
+    assert_eq!(socket1.multicast_if_v4().unwrap(), Ipv4Addr::LOCALHOST);
+    socket1.set_multicast_if_v4(Ipv4Addr::UNSPECIFIED).unwrap();
+    assert_eq!(socket1.multicast_if_v4().unwrap(), Ipv4Addr::UNSPECIFIED);
+
+    assert!(socket1.take_error().unwrap().is_none());
+}
+
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+))]
+fn join_multicast_v6_by_interface_name() {
+    use mio::net::interface_index_by_name;
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    const LOOPBACK: &str = "lo";
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    const LOOPBACK: &str = "lo0";
+
+    let index = interface_index_by_name(LOOPBACK).unwrap();
+    assert_ne!(index, 0);
+
+    let err = interface_index_by_name("mio-does-not-exist").unwrap_err();
+    assert!(err.raw_os_error().is_some());
+    let err = interface_index_by_name("lo\0").unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+    let socket = UdpSocket::bind(any_local_ipv6_address()).unwrap();
+    let group = "ff02::1234".parse().unwrap();
+    socket.join_multicast_v6_if(group, index).unwrap();
+    // Joining twice on the same interface fails.
+    assert!(socket.join_multicast_v6_if(group, index).is_err());
+    socket.leave_multicast_v6_if(group, index).unwrap();
+}
+
 #[test]
 fn set_get_multicast_loop_v6() {
     let socket1 = UdpSocket::bind(any_local_ipv6_address()).unwrap();
@@ -266,6 +594,27 @@ fn set_get_multicast_loop_v6() {
     assert!(socket1.take_error().unwrap().is_none());
 }
 
+#[test]
+fn set_get_multicast_hops_v6() {
+    let socket1 = UdpSocket::bind(any_local_ipv6_address()).unwrap();
+
+    assert_eq!(socket1.multicast_hops_v6().unwrap(), 1);
+    socket1.set_multicast_hops_v6(16).unwrap();
+    assert_eq!(socket1.multicast_hops_v6().unwrap(), 16);
+
+    assert!(socket1.take_error().unwrap().is_none());
+}
+
+#[test]
+fn set_get_unicast_hops_v6() {
+    let socket1 = UdpSocket::bind(any_local_ipv6_address()).unwrap();
+
+    socket1.set_unicast_hops_v6(42).unwrap();
+    assert_eq!(socket1.unicast_hops_v6().unwrap(), 42);
+
+    assert!(socket1.take_error().unwrap().is_none());
+}
+
 #[test]
 fn get_multicast_loop_v6_without_previous_set() {
     let socket1 = UdpSocket::bind(any_local_ipv6_address()).unwrap();
@@ -275,6 +624,38 @@ fn get_multicast_loop_v6_without_previous_set() {
         .expect("unable to get multicast_loop_v6 for UDP socket");
 }
 
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn take_error_once() {
+    use util::Readiness;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    // Get an address no one is listening on.
+    let address = net::UdpSocket::bind(any_local_address())
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    socket.connect(address).unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    // Results in an ICMP port unreachable message, setting `SO_ERROR`.
+    checked_write!(socket.send(DATA1));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Readiness::ERROR)],
+    );
+
+    let err = socket.take_error().unwrap().expect("no error");
+    assert_eq!(err.kind(), std::io::ErrorKind::ConnectionRefused);
+    assert!(socket.take_error().unwrap().is_none());
+}
+
 #[test]
 fn connected_udp_socket_ipv4() {
     let socket1 = UdpSocket::bind(any_local_address()).unwrap();
@@ -1131,3 +1512,467 @@ fn et_behavior_recv_from() {
     assert!(socket1.take_error().unwrap().is_none());
     assert!(socket2.take_error().unwrap().is_none());
 }
+
+#[test]
+#[cfg(target_os = "linux")]
+fn set_get_busy_poll() {
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+
+    const TIMEOUT: Duration = Duration::from_micros(50);
+    match socket.set_busy_poll(TIMEOUT) {
+        Ok(()) => assert_eq!(socket.busy_poll().unwrap(), TIMEOUT),
+        // Increasing the value requires `CAP_NET_ADMIN`.
+        Err(ref err) if err.kind() == std::io::ErrorKind::PermissionDenied => {}
+        Err(err) => panic!("unexpected error setting busy poll: {err}"),
+    }
+
+    socket.set_busy_poll(Duration::ZERO).unwrap();
+    assert_eq!(socket.busy_poll().unwrap(), Duration::ZERO);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn attach_reuseport_cbpf() {
+    use mio::net::SockFilter;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = reuseport_socket("127.0.0.1:0".parse().unwrap());
+    let address = socket1.local_addr().unwrap();
+    let mut socket2 = reuseport_socket(address);
+
+    // Always steer to the first socket in the group.
+    let program = [SockFilter::new(
+        (libc::BPF_RET | libc::BPF_K) as u16,
+        0,
+        0,
+        0,
+    )];
+    socket2.attach_reuseport_cbpf(&program).unwrap();
+
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut socket2, ID2, Interest::READABLE)
+        .unwrap();
+
+    // Send from multiple source ports, which would normally be spread over the
+    // group.
+    for _ in 0..8 {
+        let sender = net::UdpSocket::bind(any_local_address()).unwrap();
+        sender.send_to(DATA1, address).unwrap();
+    }
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; 20];
+    for _ in 0..8 {
+        expect_read!(socket1.recv_from(&mut buf), DATA1, __anywhere);
+    }
+    assert_would_block(socket1.recv_from(&mut buf));
+    assert_would_block(socket2.recv_from(&mut buf));
+}
+
+/// Create a socket with `SO_REUSEPORT` set bound to the IPv4 `address`.
+#[cfg(target_os = "linux")]
+fn reuseport_socket(address: SocketAddr) -> UdpSocket {
+    let address = match address {
+        SocketAddr::V4(address) => address,
+        SocketAddr::V6(_) => unreachable!(),
+    };
+    unsafe {
+        let fd = libc::socket(
+            libc::AF_INET,
+            libc::SOCK_DGRAM | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+            0,
+        );
+        assert!(fd != -1, "{}", std::io::Error::last_os_error());
+        let socket = UdpSocket::from_raw_fd(fd);
+
+        let on: libc::c_int = 1;
+        let res = libc::setsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            libc::SO_REUSEPORT,
+            &on as *const libc::c_int as *const libc::c_void,
+            std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+        );
+        assert!(res != -1, "{}", std::io::Error::last_os_error());
+
+        let sockaddr = libc::sockaddr_in {
+            sin_family: libc::AF_INET as libc::sa_family_t,
+            sin_port: address.port().to_be(),
+            sin_addr: libc::in_addr {
+                s_addr: u32::from_ne_bytes(address.ip().octets()),
+            },
+            sin_zero: [0; 8],
+        };
+        let res = libc::bind(
+            fd,
+            &sockaddr as *const libc::sockaddr_in as *const libc::sockaddr,
+            std::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+        );
+        assert!(res != -1, "{}", std::io::Error::last_os_error());
+        socket
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn attach_filter() {
+    use mio::net::SockFilter;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let address = socket.local_addr().unwrap();
+    let sender = net::UdpSocket::bind(any_local_address()).unwrap();
+
+    // Drop all datagrams.
+    let program = [SockFilter::new(
+        (libc::BPF_RET | libc::BPF_K) as u16,
+        0,
+        0,
+        0,
+    )];
+    socket.attach_filter(&program).unwrap();
+
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    sender.send_to(DATA1, address).unwrap();
+    expect_no_events(&mut poll, &mut events);
+    let mut buf = [0; 20];
+    assert_would_block(socket.recv_from(&mut buf));
+
+    socket.detach_filter().unwrap();
+    // No filter is attached anymore.
+    assert!(socket.detach_filter().is_err());
+
+    sender.send_to(DATA2, address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    expect_read!(
+        socket.recv_from(&mut buf),
+        DATA2,
+        sender.local_addr().unwrap()
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn send_at() {
+    use std::time::Instant;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+
+    socket1.set_txtime(libc::CLOCK_MONOTONIC, 0).unwrap();
+
+    poll.registry()
+        .register(&mut socket2, ID1, Interest::READABLE)
+        .unwrap();
+
+    // Loopback doesn't use the `fq` or `etf` qdisc, so the transmit time is
+    // ignored and the datagram should be sent immediately.
+    let when = Instant::now() + Duration::from_millis(10);
+    let n = socket1.send_at(DATA1, address2, when).unwrap();
+    assert_eq!(n, DATA1.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; 20];
+    expect_read!(socket2.recv_from(&mut buf), DATA1, address1);
+    assert!(socket1.take_error().unwrap().is_none());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn recv_mmsg_timestamps() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+
+    assert!(!socket2.recv_timestamp().unwrap());
+    socket2.set_recv_timestamp(true).unwrap();
+    assert!(socket2.recv_timestamp().unwrap());
+
+    poll.registry()
+        .register(&mut socket2, ID1, Interest::READABLE)
+        .unwrap();
+
+    checked_write!(socket1.send_to(DATA1, address2));
+    checked_write!(socket1.send_to(DATA2, address2));
+    checked_write!(socket1.send_to(DATA1, address2));
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut bufs = [[0; 20]; 4];
+    let mut bufs: Vec<&mut [u8]> = bufs.iter_mut().map(|buf| &mut buf[..]).collect();
+    let msgs = socket2.recv_mmsg(&mut bufs).unwrap();
+    assert_eq!(msgs.len(), 3);
+    for (msg, (buf, expected)) in msgs.iter().zip(bufs.iter().zip([DATA1, DATA2, DATA1])) {
+        assert_eq!(msg.addr(), address1);
+        assert_eq!(&buf[..msg.len()], expected);
+        assert!(msg.timestamp().is_some(), "missing timestamp");
+    }
+    for pair in msgs.windows(2) {
+        assert!(pair[0].timestamp() <= pair[1].timestamp());
+    }
+
+    assert_would_block(socket2.recv_mmsg(&mut bufs));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn recv_full() {
+    for address in [any_local_address(), any_local_ipv6_address()] {
+        let (mut poll, mut events) = init_with_poll();
+
+        let socket1 = UdpSocket::bind(address).unwrap();
+        let mut socket2 = UdpSocket::bind(address).unwrap();
+        let address1 = socket1.local_addr().unwrap();
+        let address2 = socket2.local_addr().unwrap();
+
+        assert!(!socket2.recv_pktinfo().unwrap());
+        socket2.set_recv_pktinfo(true).unwrap();
+        assert!(socket2.recv_pktinfo().unwrap());
+        assert!(!socket2.recv_ecn().unwrap());
+        socket2.set_recv_ecn(true).unwrap();
+        assert!(socket2.recv_ecn().unwrap());
+        socket2.set_recv_timestamp(true).unwrap();
+
+        poll.registry()
+            .register(&mut socket2, ID1, Interest::READABLE)
+            .unwrap();
+
+        checked_write!(socket1.send_to(DATA1, address2));
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Interest::READABLE)],
+        );
+
+        let mut buf = [0; 20];
+        let recv = socket2.recv_full(&mut buf).unwrap();
+        assert_eq!(&buf[..recv.len()], DATA1);
+        assert_eq!(recv.addr(), address1);
+        assert_eq!(recv.dst_addr(), Some(address2.ip()));
+        assert!(recv.interface().is_some_and(|index| index != 0));
+        assert_eq!(recv.ecn(), Some(0));
+        assert!(recv.timestamp().is_some(), "missing timestamp");
+
+        assert_would_block(socket2.recv_full(&mut buf));
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn recv_full_orig_dst_addr() {
+    for address in [any_local_address(), any_local_ipv6_address()] {
+        let (mut poll, mut events) = init_with_poll();
+
+        let socket1 = UdpSocket::bind(address).unwrap();
+        let mut socket2 = UdpSocket::bind(address).unwrap();
+        let address2 = socket2.local_addr().unwrap();
+
+        assert!(!socket2.recv_orig_dst_addr().unwrap());
+        socket2.set_recv_orig_dst_addr(true).unwrap();
+        assert!(socket2.recv_orig_dst_addr().unwrap());
+
+        poll.registry()
+            .register(&mut socket2, ID1, Interest::READABLE)
+            .unwrap();
+
+        checked_write!(socket1.send_to(DATA1, address2));
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Interest::READABLE)],
+        );
+
+        // Without a `TPROXY` rule the original destination is the local
+        // address.
+        let mut buf = [0; 20];
+        let recv = socket2.recv_full(&mut buf).unwrap();
+        assert_eq!(&buf[..recv.len()], DATA1);
+        assert_eq!(recv.orig_dst_addr(), Some(address2));
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn set_get_transparent() {
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+    assert!(!socket.transparent().unwrap());
+    match socket.set_transparent(true) {
+        Ok(()) => assert!(socket.transparent().unwrap()),
+        // Requires the `CAP_NET_ADMIN` capability.
+        Err(ref err) if err.kind() == std::io::ErrorKind::PermissionDenied => {}
+        Err(err) => panic!("unexpected error: {err}"),
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn recv_full_hw_timestamp() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address2 = socket2.local_addr().unwrap();
+
+    assert!(!socket2.recv_hw_timestamp().unwrap());
+    socket2.set_recv_hw_timestamp(true).unwrap();
+    assert!(socket2.recv_hw_timestamp().unwrap());
+
+    poll.registry()
+        .register(&mut socket2, ID1, Interest::READABLE)
+        .unwrap();
+
+    checked_write!(socket1.send_to(DATA1, address2));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; 20];
+    let recv = socket2.recv_full(&mut buf).unwrap();
+    assert_eq!(&buf[..recv.len()], DATA1);
+    // The loopback device doesn't support hardware timestamping, in which case
+    // there is nothing to check.
+    if let Some(hw_timestamp) = recv.hw_timestamp() {
+        assert!(!hw_timestamp.raw().is_zero());
+    }
+
+    socket2.set_recv_hw_timestamp(false).unwrap();
+    assert!(!socket2.recv_hw_timestamp().unwrap());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn send_batch_segmented() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address2 = socket2.local_addr().unwrap();
+
+    poll.registry()
+        .register(&mut socket2, ID1, Interest::READABLE)
+        .unwrap();
+
+    let buf1 = [1; 30];
+    let buf2 = [2; 25];
+    let n = socket1
+        .send_batch_segmented(&[(&buf1, 10, address2), (&buf2, 10, address2)])
+        .unwrap();
+    assert_eq!(n, 2);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    // Both messages are split into three datagrams.
+    let mut buf = [0; 64];
+    for (byte, len) in [(1, 10), (1, 10), (1, 10), (2, 10), (2, 10), (2, 5)] {
+        let n = socket2.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], &[byte; 10][..len]);
+    }
+    assert_would_block(socket2.recv(&mut buf));
+}
+
+#[test]
+#[cfg(windows)]
+fn no_connreset_after_port_unreachable() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+    let closed = net::UdpSocket::bind(any_local_address()).unwrap();
+    let closed_address = closed.local_addr().unwrap();
+    drop(closed);
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    // Trigger an ICMP port unreachable message.
+    checked_write!(socket.send_to(DATA1, closed_address));
+    thread::sleep(Duration::from_millis(50));
+
+    checked_write!(sender.send_to(DATA2, socket.local_addr().unwrap()));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut buf = [0; 20];
+    let (n, address) = socket.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+    assert_eq!(address, sender.local_addr().unwrap());
+
+    // Restoring the Windows behaviour should be possible.
+    socket.set_connreset(true).unwrap();
+}
+
+#[test]
+#[cfg(any(unix, windows))]
+fn available() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+    let address = socket.local_addr().unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+    assert_eq!(socket.available().unwrap(), 0);
+
+    checked_write!(sender.send_to(DATA1, address));
+    checked_write!(sender.send_to(DATA2, address));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    // Linux returns the size of the next datagram, other platforms the size
+    // of all queued datagrams.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    assert_eq!(socket.available().unwrap(), DATA1.len());
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    assert!(socket.available().unwrap() >= DATA1.len());
+
+    let mut buf = [0; 20];
+    let n = socket.recv(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    assert_eq!(socket.available().unwrap(), DATA2.len());
+}
@@ -165,6 +165,34 @@ fn unix_datagram_pair() {
     assert!(datagram2.take_error().unwrap().is_none());
 }
 
+#[test]
+fn unix_datagram_peek() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut datagram1, datagram2) = UnixDatagram::pair().unwrap();
+    poll.registry()
+        .register(&mut datagram1, TOKEN_1, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    assert_would_block(datagram1.peek(&mut buf));
+    assert_would_block(datagram1.peek_from(&mut buf));
+
+    checked_write!(datagram2.send(DATA1));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+
+    expect_read!(datagram1.peek(&mut buf), DATA1);
+    let (n, addr) = datagram1.peek_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert!(addr.is_unnamed());
+    expect_read!(datagram1.recv(&mut buf), DATA1);
+    assert_would_block(datagram1.recv(&mut buf));
+}
+
 #[test]
 #[cfg_attr(target_os = "solaris", ignore = "POLLRDHUP isn't supported on Solaris")]
 #[cfg_attr(target_os = "nto", ignore = "POLLRDHUP isn't supported on NTO")]
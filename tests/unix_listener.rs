@@ -86,6 +86,37 @@ fn unix_listener_register() {
     expect_no_events(&mut poll, &mut events)
 }
 
+#[test]
+fn unix_listener_incoming() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let path = temp_file("unix_listener_incoming");
+    let mut listener = UnixListener::bind(&path).unwrap();
+    poll.registry()
+        .register(&mut listener, TOKEN_1, Interest::READABLE)
+        .unwrap();
+
+    let _clients: Vec<net::UnixStream> = (0..3)
+        .map(|_| net::UnixStream::connect(&path).unwrap())
+        .collect();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+
+    let mut streams = listener.incoming().collect::<io::Result<Vec<_>>>().unwrap();
+    assert_eq!(streams.len(), 3);
+    for stream in streams.iter_mut() {
+        // The streams should be non-blocking.
+        let mut buf = [0; 8];
+        assert_would_block(stream.read(&mut buf));
+    }
+
+    assert!(listener.incoming().next().is_none());
+}
+
 #[test]
 fn unix_listener_reregister() {
     let (mut poll, mut events) = init_with_poll();
@@ -180,6 +211,77 @@ fn unix_listener_abstract_namespace() {
     handle.join().unwrap();
 }
 
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn unix_listener_accept_abstract_peer_address() {
+    use rand::Rng;
+    #[cfg(target_os = "android")]
+    use std::os::android::net::SocketAddrExt;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    #[cfg(target_os = "linux")]
+    use std::os::linux::net::SocketAddrExt;
+    use std::os::unix::net::SocketAddr;
+
+    /// Returns `name` as an abstract `sockaddr_un` and its length.
+    fn abstract_sockaddr(name: &[u8]) -> (libc::sockaddr_un, libc::socklen_t) {
+        let mut sockaddr: libc::sockaddr_un = unsafe { std::mem::zeroed() };
+        sockaddr.sun_family = libc::AF_UNIX as libc::sa_family_t;
+        for (dst, src) in sockaddr.sun_path[1..].iter_mut().zip(name) {
+            *dst = *src as libc::c_char;
+        }
+        let len = std::mem::size_of::<libc::sa_family_t>() + 1 + name.len();
+        (sockaddr, len as libc::socklen_t)
+    }
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let num: u64 = rand::thread_rng().gen();
+    let listener_name = format!("mio-abstract-uds-listener-{}", num);
+    let client_name = format!("mio-abstract-uds-client-{}", num);
+    let address = SocketAddr::from_abstract_name(listener_name.as_bytes()).unwrap();
+    let mut listener = UnixListener::bind_addr(&address).unwrap();
+    poll.registry()
+        .register(&mut listener, TOKEN_1, Interest::READABLE)
+        .unwrap();
+
+    // The standard library doesn't support binding a stream before
+    // connecting, so we use libc directly.
+    let client = unsafe { libc::socket(libc::AF_UNIX, libc::SOCK_STREAM | libc::SOCK_CLOEXEC, 0) };
+    assert!(client != -1, "{}", io::Error::last_os_error());
+    let client = unsafe { OwnedFd::from_raw_fd(client) };
+    let (sockaddr, len) = abstract_sockaddr(client_name.as_bytes());
+    let res = unsafe {
+        libc::bind(
+            client.as_raw_fd(),
+            (&sockaddr as *const libc::sockaddr_un).cast(),
+            len,
+        )
+    };
+    assert!(res != -1, "{}", io::Error::last_os_error());
+    let (sockaddr, len) = abstract_sockaddr(listener_name.as_bytes());
+    let res = unsafe {
+        libc::connect(
+            client.as_raw_fd(),
+            (&sockaddr as *const libc::sockaddr_un).cast(),
+            len,
+        )
+    };
+    assert!(res != -1, "{}", io::Error::last_os_error());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+
+    let (stream, address) = listener.accept().unwrap();
+    assert_eq!(address.as_abstract_name(), Some(client_name.as_bytes()));
+    assert_eq!(
+        stream.peer_addr().unwrap().as_abstract_name(),
+        Some(client_name.as_bytes())
+    );
+}
+
 fn smoke_test<F>(new_listener: F, test_name: &'static str)
 where
     F: FnOnce(&Path) -> io::Result<UnixListener>,
@@ -1,6 +1,7 @@
 #![cfg(all(unix, feature = "os-poll", feature = "os-ext", feature = "net"))]
 
 use std::io::{Read, Write};
+use std::os::fd::{FromRawFd, IntoRawFd, OwnedFd};
 use std::process::{Command, Stdio};
 use std::sync::{Arc, Barrier};
 use std::thread;
@@ -10,7 +11,7 @@ use mio::unix::pipe::{self, Receiver, Sender};
 use mio::{Events, Interest, Poll, Token};
 
 mod util;
-use util::{assert_would_block, expect_events, ExpectEvent};
+use util::{assert_would_block, expect_events, expect_no_events, ExpectEvent};
 
 const RECEIVER: Token = Token(0);
 const SENDER: Token = Token(1);
@@ -52,6 +53,41 @@ fn smoke() {
     assert_eq!(&buf[..n], DATA1);
 }
 
+#[test]
+fn owned_fd_source() {
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let (mut sender, receiver) = pipe::new().unwrap();
+    // SAFETY: `into_raw_fd` transfers ownership of the FD.
+    let mut receiver = unsafe { OwnedFd::from_raw_fd(receiver.into_raw_fd()) };
+
+    poll.registry()
+        .register(&mut receiver, RECEIVER, Interest::READABLE)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    let n = sender.write(DATA1).unwrap();
+    assert_eq!(n, DATA1.len());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(RECEIVER, Interest::READABLE)],
+    );
+
+    poll.registry()
+        .reregister(&mut receiver, SENDER, Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(SENDER, Interest::READABLE)],
+    );
+
+    poll.registry().deregister(&mut receiver).unwrap();
+    expect_no_events(&mut poll, &mut events);
+}
+
 #[test]
 #[cfg_attr(
     target_os = "nto",
@@ -94,6 +130,47 @@ fn event_when_sender_is_dropped() {
     handle.join().unwrap();
 }
 
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+fn hup_only_event_when_sender_is_dropped() {
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let (mut sender, mut receiver) = pipe::new().unwrap();
+    poll.registry()
+        .register(&mut receiver, RECEIVER, Interest::HUP)
+        .unwrap();
+
+    // On Linux data becoming available doesn't trigger an event, kqueue
+    // doesn't have a hangup only filter.
+    let n = sender.write(DATA1).unwrap();
+    assert_eq!(n, DATA1.len());
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    assert!(events.is_empty(), "unexpected events: {events:?}");
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    assert!(
+        events.iter().all(|event| !event.is_read_closed()),
+        "unexpected events: {events:?}"
+    );
+
+    drop(sender);
+    expect_one_closed_event(&mut poll, &mut events, RECEIVER, true);
+}
+
 #[test]
 #[cfg_attr(
     target_os = "nto",
@@ -225,3 +302,59 @@ pub fn expect_one_closed_event(poll: &mut Poll, events: &mut Events, token: Toke
     }
     assert!(iter.next().is_none());
 }
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_capacity() {
+    const CAPACITY: usize = 1024 * 1024;
+
+    let (sender, _receiver) = pipe::new().unwrap();
+    let default = sender.capacity().unwrap();
+    assert!(default > 0);
+
+    match sender.set_capacity(CAPACITY) {
+        // Limited by `/proc/sys/fs/pipe-max-size`.
+        Err(ref err) if err.kind() == std::io::ErrorKind::PermissionDenied => {
+            assert_eq!(sender.capacity().unwrap(), default);
+        }
+        Err(err) => panic!("unexpected error setting capacity: {}", err),
+        Ok(()) => assert!(sender.capacity().unwrap() >= CAPACITY),
+    }
+
+    // The kernel rounds up to a number of pages.
+    sender.set_capacity(1).unwrap();
+    let capacity = sender.capacity().unwrap();
+    assert!(capacity > 0 && capacity < CAPACITY);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn splice_pipe_to_socket() {
+    use std::net;
+
+    use mio::net::TcpStream;
+    use mio::unix::splice;
+
+    let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut peer = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (stream, _) = listener.accept().unwrap();
+    stream.set_nonblocking(true).unwrap();
+    let stream = TcpStream::from_std(stream);
+
+    let (mut sender, receiver) = pipe::new().unwrap();
+
+    // Nothing to splice yet.
+    assert_would_block(splice(&receiver, &stream, DATA1.len(), 0));
+
+    sender.write_all(DATA1).unwrap();
+    let n = splice(&receiver, &stream, DATA1.len(), 0).unwrap();
+    assert_eq!(n, DATA1.len());
+
+    let mut buf = [0; 20];
+    peer.read_exact(&mut buf[..n]).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+
+    // End of input once the sending end is closed.
+    drop(sender);
+    assert_eq!(splice(&receiver, &stream, DATA1.len(), 0).unwrap(), 0);
+}
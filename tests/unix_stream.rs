@@ -9,6 +9,7 @@ use std::path::Path;
 use std::sync::mpsc::channel;
 use std::sync::{Arc, Barrier};
 use std::thread;
+use std::time::Duration;
 
 #[macro_use]
 mod util;
@@ -133,6 +134,14 @@ fn unix_stream_from_std() {
     )
 }
 
+#[test]
+fn unix_stream_from_std_nonblocking() {
+    smoke_test(
+        |path| UnixStream::from_std_nonblocking(net::UnixStream::connect(path).unwrap()),
+        "unix_stream_from_std_nonblocking",
+    )
+}
+
 #[test]
 fn unix_stream_pair() {
     let (mut poll, mut events) = init_with_poll();
@@ -166,6 +175,124 @@ fn unix_stream_pair() {
     assert_would_block(s2.read(&mut buf));
 }
 
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn unix_stream_send_with_creds() {
+    use mio::net::UCred;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let (s1, mut s2) = UnixStream::pair().unwrap();
+    assert!(!s2.passcred().unwrap());
+    s2.set_passcred(true).unwrap();
+    assert!(s2.passcred().unwrap());
+    poll.registry()
+        .register(&mut s2, TOKEN_2, Interest::READABLE)
+        .unwrap();
+
+    let creds = UCred::current();
+    let n = s1.send_with_creds(&[IoSlice::new(DATA1)], &creds).unwrap();
+    assert_eq!(n, DATA1_LEN);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_2, Interest::READABLE)],
+    );
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    let (n, received) = s2
+        .recv_with_creds(&mut [IoSliceMut::new(&mut buf)])
+        .unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(received, Some(creds));
+    assert_would_block(s2.recv_with_creds(&mut [IoSliceMut::new(&mut buf)]));
+}
+
+#[test]
+fn unix_stream_read_data() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut s1, mut s2) = UnixStream::pair().unwrap();
+    poll.registry()
+        .register(&mut s2, TOKEN_2, Interest::READABLE)
+        .unwrap();
+
+    let data = [1; 500];
+    checked_write!(s1.write(&data));
+
+    poll.poll(&mut events, Some(Duration::from_millis(500)))
+        .unwrap();
+    let event = events
+        .iter()
+        .find(|event| event.token() == TOKEN_2 && event.is_readable())
+        .expect("no readable event");
+
+    #[cfg(any(
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    assert_eq!(event.read_data(), Some(data.len()));
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    assert_eq!(event.read_data(), None);
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    )))]
+    let _ = event;
+
+    let mut buf = [0; 1024];
+    expect_read!(s2.read(&mut buf), &data[..]);
+}
+
+#[test]
+fn unix_stream_try_clone() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut s1, mut s2) = UnixStream::pair().unwrap();
+    let mut clone = s1.try_clone().unwrap();
+    poll.registry()
+        .register(&mut s1, TOKEN_1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut clone, TOKEN_2, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_2, Interest::WRITABLE)],
+    );
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    checked_write!(clone.write(DATA1));
+    expect_read!(s2.read(&mut buf), DATA1);
+
+    checked_write!(s2.write(DATA2));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+    expect_read!(s1.read(&mut buf), DATA2);
+    assert_would_block(clone.read(&mut buf));
+}
+
 #[test]
 fn unix_stream_peer_addr() {
     init();
@@ -302,6 +429,71 @@ fn unix_stream_shutdown_write() {
     handle.join().unwrap();
 }
 
+#[test]
+#[cfg_attr(target_os = "solaris", ignore = "POLLRDHUP isn't supported on Solaris")]
+#[cfg_attr(target_os = "nto", ignore = "POLLRDHUP isn't supported on NTO")]
+fn unix_stream_pair_shutdown_read() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut s1, mut s2) = UnixStream::pair().unwrap();
+    poll.registry()
+        .register(&mut s1, TOKEN_1, Interest::READABLE)
+        .unwrap();
+
+    s1.shutdown(Shutdown::Read).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Readiness::READ_CLOSED)],
+    );
+
+    // Without any pending data reading returns EOF.
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    expect_read!(s1.read(&mut buf), &[]);
+
+    // The write half is unaffected.
+    checked_write!(s1.write(DATA1));
+    expect_read!(s2.read(&mut buf), DATA1);
+}
+
+#[test]
+#[cfg_attr(target_os = "solaris", ignore = "POLLRDHUP isn't supported on Solaris")]
+#[cfg_attr(target_os = "nto", ignore = "POLLRDHUP isn't supported on NTO")]
+fn unix_stream_pair_shutdown_write() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut s1, mut s2) = UnixStream::pair().unwrap();
+    poll.registry()
+        .register(&mut s1, TOKEN_1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut s2, TOKEN_2, Interest::READABLE)
+        .unwrap();
+
+    s1.shutdown(Shutdown::Write).unwrap();
+    let err = s1.write(DATA1).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+
+    // The peer sees EOF.
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_2, Readiness::READ_CLOSED)],
+    );
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    expect_read!(s2.read(&mut buf), &[]);
+
+    // But can still write to the local stream, which can still be read.
+    checked_write!(s2.write(DATA2));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+    expect_read!(s1.read(&mut buf), DATA2);
+    assert_would_block(s1.read(&mut buf));
+}
+
 #[test]
 fn unix_stream_shutdown_both() {
     let (mut poll, mut events) = init_with_poll();
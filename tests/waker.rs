@@ -68,6 +68,53 @@ fn waker_wakeup_different_thread() {
     handle.join().unwrap();
 }
 
+#[test]
+fn shutdown_waker() {
+    init();
+
+    let mut poll = Poll::new().expect("unable to create new Poll instance");
+    let mut events = Events::with_capacity(10);
+
+    // The shutdown waker can be used next to a regular waker.
+    let waker = Waker::new(poll.registry(), Token(10)).expect("unable to create waker");
+    let shutdown = poll.shutdown_waker().expect("unable to create waker");
+
+    let barrier = Arc::new(Barrier::new(2));
+    let barrier1 = Arc::clone(&barrier);
+    let handle = thread::spawn(move || {
+        barrier1.wait();
+        // Give the main thread time to block in `poll`.
+        thread::sleep(Duration::from_millis(100));
+        shutdown.wake().expect("unable to wake");
+    });
+
+    barrier.wait();
+    poll.poll(&mut events, Some(Duration::from_secs(5)))
+        .unwrap();
+    assert!(!events.is_empty());
+    for event in events.iter() {
+        assert!(event.is_shutdown());
+        assert_eq!(event.token(), Token::SHUTDOWN);
+    }
+    handle.join().unwrap();
+
+    // The event is still delivered if the waker is dropped before polling.
+    let shutdown = poll.shutdown_waker().expect("unable to create waker");
+    shutdown.wake().expect("unable to wake");
+    drop(shutdown);
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    assert!(events.iter().any(|event| event.is_shutdown()));
+
+    // Waking the regular waker still returns its own token.
+    waker.wake().expect("unable to wake");
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    let event = events.iter().next().expect("missing event");
+    assert_eq!(event.token(), Token(10));
+    assert!(!event.is_shutdown());
+}
+
 #[test]
 fn waker_multiple_wakeups_different_thread() {
     init();
@@ -152,6 +199,38 @@ fn using_multiple_wakers_panics_different_cloned_registries() {
     drop(waker2);
 }
 
+#[test]
+#[cfg(any(
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+fn waker_evfilt_user() {
+    init();
+
+    let mut poll = Poll::new().expect("unable to create new Poll instance");
+    let mut events = Events::with_capacity(10);
+
+    let token = Token(10);
+    let waker = Waker::new(poll.registry(), token).expect("unable to create waker");
+
+    // `EVFILT_USER` uses `EV_CLEAR`, so multiple wake ups are coalesced into
+    // a single event.
+    waker.wake().expect("unable to wake");
+    waker.wake().expect("unable to wake");
+    expect_waker_event(&mut poll, &mut events, token);
+    assert_eq!(events.iter().count(), 1);
+    expect_no_events(&mut poll, &mut events);
+
+    // After which it can be triggered again.
+    waker.wake().expect("unable to wake");
+    expect_waker_event(&mut poll, &mut events, token);
+}
+
 fn expect_waker_event(poll: &mut Poll, events: &mut Events, token: Token) {
     poll.poll(events, Some(Duration::from_millis(100))).unwrap();
     assert!(!events.is_empty());